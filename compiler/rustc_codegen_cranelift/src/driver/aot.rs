@@ -23,6 +23,7 @@
 use rustc_middle::mir::mono::{CodegenUnit, MonoItem};
 use rustc_session::config::{DebugInfo, OutFileName, OutputFilenames, OutputType};
 use rustc_session::Session;
+use rustc_span::Symbol;
 
 use crate::concurrency_limiter::{ConcurrencyLimiter, ConcurrencyLimiterToken};
 use crate::debuginfo::TypeDebugContext;
@@ -577,11 +578,14 @@ fn module_codegen(
     }))
 }
 
+/// Generates the codegen units of the secondary codegen backend `codegen_backend`, or those of
+/// the main backend along with the allocator shim and the metadata if it is `None`.
 pub(crate) fn run_aot(
     tcx: TyCtxt<'_>,
     backend_config: BackendConfig,
     metadata: EncodedMetadata,
     need_metadata_module: bool,
+    codegen_backend: Option<Symbol>,
 ) -> Box<OngoingCodegen> {
     // FIXME handle `-Ctarget-cpu=native`
     let target_cpu = match tcx.sess.opts.cg.target_cpu {
@@ -590,8 +594,12 @@ pub(crate) fn run_aot(
     }
     .to_owned();
 
-    let cgus = if tcx.sess.opts.output_types.should_codegen() {
-        tcx.collect_and_partition_mono_items(()).1
+    let cgus: Vec<_> = if tcx.sess.opts.output_types.should_codegen() {
+        tcx.collect_and_partition_mono_items(())
+            .1
+            .iter()
+            .filter(|cgu| cgu.codegen_backend() == codegen_backend)
+            .collect()
     } else {
         // If only `--emit metadata` is used, we shouldn't perform any codegen.
         // Also `tcx.collect_and_partition_mono_items` may panic in that case.
@@ -606,7 +614,7 @@ pub(crate) fn run_aot(
     };
 
     if tcx.dep_graph.is_fully_enabled() {
-        for cgu in cgus {
+        for cgu in &cgus {
             tcx.ensure().codegen_unit(cgu.name());
         }
     }
@@ -661,7 +669,8 @@ pub(crate) fn run_aot(
     });
 
     let mut allocator_module = make_module(tcx.sess, &backend_config, "allocator_shim".to_string());
-    let created_alloc_shim = crate::allocator::codegen(tcx, &mut allocator_module);
+    let created_alloc_shim =
+        codegen_backend.is_none() && crate::allocator::codegen(tcx, &mut allocator_module);
 
     let allocator_module = if created_alloc_shim {
         let product = allocator_module.finish();
@@ -222,7 +222,9 @@ fn codegen_crate(
         tcx.dcx().abort_if_errors();
         let config = self.config.borrow().clone().unwrap();
         match config.codegen_mode {
-            CodegenMode::Aot => driver::aot::run_aot(tcx, config, metadata, need_metadata_module),
+            CodegenMode::Aot => {
+                driver::aot::run_aot(tcx, config, metadata, need_metadata_module, None)
+            }
             CodegenMode::Jit | CodegenMode::JitLazy => {
                 #[cfg(feature = "jit")]
                 driver::jit::run_jit(tcx, config);
@@ -233,6 +235,22 @@ fn codegen_crate(
         }
     }
 
+    fn codegen_secondary_units(&self, tcx: TyCtxt<'_>, name: Symbol) -> Option<Box<dyn Any>> {
+        tcx.dcx().abort_if_errors();
+        let config = self.config.borrow().clone().unwrap();
+        match config.codegen_mode {
+            CodegenMode::Aot => {
+                Some(driver::aot::run_aot(tcx, config, EncodedMetadata::empty(), false, Some(name)))
+            }
+            CodegenMode::Jit | CodegenMode::JitLazy => None,
+        }
+    }
+
+    fn supports_unwinding(&self) -> bool {
+        // FIXME implement unwinding
+        false
+    }
+
     fn join_codegen(
         &self,
         ongoing_codegen: Box<dyn Any>,
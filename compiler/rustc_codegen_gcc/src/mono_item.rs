@@ -82,4 +82,20 @@ fn predefine_fn(
         self.functions.borrow_mut().insert(symbol_name.to_string(), decl);
         self.function_instances.borrow_mut().insert(instance, decl);
     }
+
+    fn supports_aliases(&self) -> bool {
+        // Aliases don't seem to work with LTO, see `rust_eh_personality` in `declare_raw_fn`.
+        false
+    }
+
+    fn predefine_alias(
+        &self,
+        _instance: Instance<'tcx>,
+        _aliasee: Instance<'tcx>,
+        _linkage: Linkage,
+        _visibility: Visibility,
+        _symbol_name: &str,
+    ) {
+        bug!("aliases are not supported by the GCC backend")
+    }
 }
@@ -0,0 +1,85 @@
+// .debug_lldb_scripts binary section.
+
+use rustc_codegen_ssa::base::collect_debugger_visualizers_transitive;
+use rustc_codegen_ssa::traits::*;
+use rustc_hir::def_id::LOCAL_CRATE;
+use rustc_middle::bug;
+use rustc_middle::middle::debugger_visualizer::DebuggerVisualizerType;
+
+use super::gdb::can_embed_debug_scripts;
+use crate::builder::Builder;
+use crate::common::CodegenCx;
+use crate::llvm;
+use crate::value::Value;
+
+/// Inserts a side-effect free instruction sequence that makes sure that the
+/// .debug_lldb_scripts global is referenced, so it isn't removed by the linker.
+pub(crate) fn insert_reference_to_lldb_debug_scripts_section_global(bx: &mut Builder<'_, '_, '_>) {
+    if needs_lldb_debug_scripts_section(bx) {
+        let lldb_debug_scripts_section = get_or_insert_lldb_debug_scripts_section_global(bx);
+        // Load just the first byte as that's all that's necessary to force
+        // LLVM to keep around the reference to the global.
+        let volatile_load_instruction = bx.volatile_load(bx.type_i8(), lldb_debug_scripts_section);
+        unsafe {
+            llvm::LLVMSetAlignment(volatile_load_instruction, 1);
+        }
+    }
+}
+
+/// Allocates the global variable responsible for the .debug_lldb_scripts binary
+/// section.
+///
+/// Unlike GDB, LLDB doesn't look for scripts in the binaries it loads, so the
+/// section is read by `lldb_lookup.py` instead, which `rust-lldb` imports. It
+/// uses the same format as `.debug_gdb_scripts` does for inline scripts: each
+/// script is introduced by the byte `4`, followed by its name, a newline, the
+/// script itself and a final byte `0`.
+pub(crate) fn get_or_insert_lldb_debug_scripts_section_global<'ll>(
+    cx: &CodegenCx<'ll, '_>,
+) -> &'ll Value {
+    let c_section_var_name = c"__rustc_debug_lldb_scripts_section__";
+    let section_var_name = c_section_var_name.to_str().unwrap();
+
+    let section_var = unsafe { llvm::LLVMGetNamedGlobal(cx.llmod, c_section_var_name.as_ptr()) };
+
+    section_var.unwrap_or_else(|| {
+        let mut section_contents = Vec::new();
+
+        let visualizers =
+            collect_debugger_visualizers_transitive(cx.tcx, DebuggerVisualizerType::LldbFormatter);
+        let crate_name = cx.tcx.crate_name(LOCAL_CRATE);
+        for (index, visualizer) in visualizers.iter().enumerate() {
+            // The name is used as the name of the Python module the script is
+            // loaded as, so it must be a valid identifier.
+            section_contents.extend_from_slice(b"\x04");
+            let vis_name = format!("lldb_formatter_{crate_name}_{index}\n");
+            section_contents.extend_from_slice(vis_name.as_bytes());
+            section_contents.extend_from_slice(&visualizer.src);
+            section_contents.extend_from_slice(b"\0");
+        }
+
+        unsafe {
+            let section_contents = section_contents.as_slice();
+            let llvm_type = cx.type_array(cx.type_i8(), section_contents.len() as u64);
+
+            let section_var = cx
+                .define_global(section_var_name, llvm_type)
+                .unwrap_or_else(|| bug!("symbol `{}` is already defined", section_var_name));
+            llvm::LLVMSetSection(section_var, c".debug_lldb_scripts".as_ptr());
+            llvm::LLVMSetInitializer(section_var, cx.const_bytes(section_contents));
+            llvm::LLVMSetGlobalConstant(section_var, llvm::True);
+            llvm::LLVMSetUnnamedAddress(section_var, llvm::UnnamedAddr::Global);
+            llvm::LLVMRustSetLinkage(section_var, llvm::Linkage::LinkOnceODRLinkage);
+            llvm::LLVMSetAlignment(section_var, 1);
+            section_var
+        }
+    })
+}
+
+/// Only crates that embed LLDB formatters get the section, as the formatters
+/// for the standard library are always loaded by `rust-lldb`.
+pub(crate) fn needs_lldb_debug_scripts_section(cx: &CodegenCx<'_, '_>) -> bool {
+    can_embed_debug_scripts(cx)
+        && !collect_debugger_visualizers_transitive(cx.tcx, DebuggerVisualizerType::LldbFormatter)
+            .is_empty()
+}
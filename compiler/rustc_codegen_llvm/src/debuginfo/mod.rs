@@ -41,6 +41,7 @@
 
 mod create_scope_map;
 mod gdb;
+mod lldb;
 pub(crate) mod metadata;
 mod namespace;
 mod utils;
@@ -146,6 +147,12 @@ pub(crate) fn finalize(cx: &CodegenCx<'_, '_>) {
             gdb::get_or_insert_gdb_debug_scripts_section_global(cx);
         }
 
+        if lldb::needs_lldb_debug_scripts_section(cx) {
+            // Add a .debug_lldb_scripts section with the formatters that crates embedded
+            // with `#[debugger_visualizer(lldb_script_file = "...")]`.
+            lldb::get_or_insert_lldb_debug_scripts_section_global(cx);
+        }
+
         dbg_cx.finalize(cx.sess());
     }
 }
@@ -210,7 +217,9 @@ fn set_dbg_loc(&mut self, dbg_loc: &'ll DILocation) {
     }
 
     fn insert_reference_to_gdb_debug_scripts_section_global(&mut self) {
-        gdb::insert_reference_to_gdb_debug_scripts_section_global(self)
+        gdb::insert_reference_to_gdb_debug_scripts_section_global(self);
+        // The LLDB scripts are embedded under the same conditions, so keep them alive as well.
+        lldb::insert_reference_to_lldb_debug_scripts_section_global(self)
     }
 
     fn set_var_name(&mut self, value: &'ll Value, name: &str) {
@@ -368,6 +368,19 @@ fn codegen_crate<'tcx>(
         ))
     }
 
+    fn codegen_secondary_units<'tcx>(
+        &self,
+        tcx: TyCtxt<'tcx>,
+        name: Symbol,
+    ) -> Option<Box<dyn Any>> {
+        Some(Box::new(rustc_codegen_ssa::base::codegen_secondary_units(
+            LlvmCodegenBackend(()),
+            tcx,
+            crate::llvm_util::target_cpu(tcx.sess).to_string(),
+            name,
+        )))
+    }
+
     fn join_codegen(
         &self,
         ongoing_codegen: Box<dyn Any>,
@@ -1,4 +1,5 @@
 use rustc_codegen_ssa::traits::*;
+use rustc_data_structures::small_c_str::SmallCStr;
 use rustc_hir::def::DefKind;
 use rustc_hir::def_id::{DefId, LOCAL_CRATE};
 use rustc_middle::bug;
@@ -6,9 +7,11 @@
 use rustc_middle::ty::layout::{FnAbiOf, LayoutOf};
 use rustc_middle::ty::{self, Instance, TypeVisitableExt};
 use rustc_session::config::CrateType;
+use rustc_target::abi::HasDataLayout;
 use rustc_target::spec::RelocModel;
 use tracing::debug;
 
+use crate::abi::FnAbiLlvmExt;
 use crate::context::CodegenCx;
 use crate::errors::SymbolAlreadyDefined;
 use crate::type_of::LayoutLlvmExt;
@@ -95,6 +98,43 @@ fn predefine_fn(
 
         self.instances.borrow_mut().insert(instance, lldecl);
     }
+
+    fn supports_aliases(&self) -> bool {
+        true
+    }
+
+    fn predefine_alias(
+        &self,
+        instance: Instance<'tcx>,
+        aliasee: Instance<'tcx>,
+        linkage: Linkage,
+        visibility: Visibility,
+        symbol_name: &str,
+    ) {
+        let llaliasee = self.instances.borrow()[&aliasee];
+        let fn_abi = self.fn_abi_of_instance(instance, ty::List::empty());
+        let symbol_name = SmallCStr::new(symbol_name);
+        let llalias = unsafe {
+            llvm::LLVMAddAlias2(
+                self.llmod,
+                fn_abi.llvm_type(self),
+                self.data_layout().instruction_address_space.0,
+                llaliasee,
+                symbol_name.as_ptr(),
+            )
+        };
+        unsafe {
+            llvm::LLVMRustSetLinkage(llalias, base::linkage_to_llvm(linkage));
+            llvm::LLVMRustSetVisibility(llalias, base::visibility_to_llvm(visibility));
+            if self.should_assume_dso_local(llalias, false) {
+                llvm::LLVMRustSetDSOLocal(llalias, true);
+            }
+        }
+
+        debug!("predefine_alias: instance = {:?}, aliasee = {:?}", instance, aliasee);
+
+        self.instances.borrow_mut().insert(instance, llalias);
+    }
 }
 
 impl CodegenCx<'_, '_> {
@@ -42,6 +42,27 @@ codegen_ssa_error_writing_def_file =
 
 codegen_ssa_expected_used_symbol = expected `used`, `used(compiler)` or `used(linker)`
 
+codegen_ssa_export_list_alias_conflict = alias `{$name}` in the export list `{$path}` has the name of an exported symbol
+
+codegen_ssa_export_list_aliases_unsupported = aliases in `-Z export-list` are not supported on this target
+
+codegen_ssa_export_list_executable = `-Z export-list` has no effect on executables without `-Z export-executable-symbols`
+
+codegen_ssa_export_list_malformed = failed to read the export list `{$path}`: {$error}
+
+codegen_ssa_export_list_unknown_alias_target = alias `{$name}` in the export list `{$path}` refers to `{$target}`, which is not exported
+
+codegen_ssa_export_list_unknown_symbol = symbol `{$name}` in the export list `{$path}` is not exported
+    .note = only the symbols of `#[no_mangle]` and `#[export_name]` items, and the other symbols that rustc exports, can be listed
+
+codegen_ssa_export_list_unknown_version = version `{$version}` in the export list `{$path}` is not in its `versions`
+
+codegen_ssa_export_list_unsupported = the linker does not support `-Z export-list`
+
+codegen_ssa_export_list_weak_alias_unsupported = weak alias `{$name}` in `-Z export-list` is not supported on this target
+
+codegen_ssa_export_list_write_failure = failed to write the export list to `{$path}`: {$error}
+
 codegen_ssa_extern_funcs_not_found = some `extern` functions couldn't be found; some native libraries may need to be installed or have their path specified
 
 codegen_ssa_extract_bundled_libs_archive_member = failed to get data from archive member '{$rlib}': {$error}
@@ -169,6 +190,12 @@ codegen_ssa_link_script_write_failure = failed to write link script to {$path}:
 
 codegen_ssa_linker_file_stem = couldn't extract file stem from specified linker
 
+codegen_ssa_linker_map_unreadable = failed to read the linker map `{$path}`: {$error}
+
+codegen_ssa_linker_map_unrecognized = could not find any symbols in the linker map `{$path}`, its format is not supported
+
+codegen_ssa_linker_map_unsupported = the linker does not support writing a map file, ignoring `-Z linker-map` and `-Z print-symbol-sizes`
+
 codegen_ssa_linker_not_found = linker `{$linker_path}` not found
     .note = {$error}
 
@@ -335,6 +362,9 @@ codegen_ssa_unknown_atomic_operation = unknown atomic operation
 
 codegen_ssa_unknown_atomic_ordering = unknown ordering in atomic intrinsic
 
+codegen_ssa_unknown_codegen_backend = codegen backend `{$name}` is not loaded
+    .help = load it with `-Z secondary-codegen-backends={$name}`
+
 codegen_ssa_unknown_reuse_kind = unknown cgu-reuse-kind `{$kind}` specified
 
 codegen_ssa_unsupported_arch = unsupported arch `{$arch}` for os `{$os}`
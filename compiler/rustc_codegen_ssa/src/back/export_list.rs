@@ -0,0 +1,188 @@
+//! Support for `-Z export-list`, which reads a TOML file describing how the symbols of a cdylib,
+//! dylib, executable or staticlib are exported:
+//!
+//! ```toml
+//! # The version nodes of the ELF version script, from the oldest to the newest.
+//! versions = ["MYLIB_1.0", "MYLIB_1.1"]
+//!
+//! [symbols]
+//! mylib_init = { version = "MYLIB_1.0" }
+//! mylib_frob = { version = "MYLIB_1.1" }
+//! mylib_test_hook = { visibility = "hidden" }
+//!
+//! [aliases]
+//! mylib_frobnicate = { target = "mylib_frob", version = "MYLIB_1.1", weak = true }
+//! ```
+//!
+//! The list is merged with the symbols that rustc exports: every name in `[symbols]` and every
+//! alias target must be one of them. Symbols that are not listed keep being exported, in the
+//! oldest version if the symbols are versioned. Versions only exist on ELF targets, and are
+//! ignored elsewhere. Executables only export symbols with `-Z export-executable-symbols`, so the
+//! list is ignored with a warning without it.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use rustc_data_structures::fx::FxHashSet;
+use rustc_session::config::CrateType;
+use rustc_session::Session;
+use serde::Deserialize;
+
+use crate::errors;
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExportListFile {
+    #[serde(default)]
+    versions: Vec<String>,
+    #[serde(default)]
+    symbols: BTreeMap<String, SymbolEntry>,
+    #[serde(default)]
+    aliases: BTreeMap<String, AliasEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SymbolEntry {
+    version: Option<String>,
+    #[serde(default)]
+    visibility: Visibility,
+}
+
+#[derive(Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum Visibility {
+    #[default]
+    Default,
+    Hidden,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AliasEntry {
+    target: String,
+    version: Option<String>,
+    #[serde(default)]
+    weak: bool,
+}
+
+/// The symbols to export, after merging the `-Z export-list` file into the exported symbols.
+#[derive(Debug)]
+pub struct ExportList {
+    /// The version nodes, from the oldest to the newest. Empty if the symbols are not versioned.
+    pub versions: Vec<String>,
+    /// The exported symbols, including the aliases, with their version.
+    pub symbols: Vec<(String, Option<String>)>,
+    pub aliases: Vec<ExportAlias>,
+}
+
+/// An additional name for an exported symbol.
+#[derive(Debug)]
+pub struct ExportAlias {
+    pub name: String,
+    pub target: String,
+    /// Whether the alias yields to another definition of its name in the linked objects.
+    pub weak: bool,
+}
+
+impl ExportList {
+    /// The names of the exported symbols and aliases.
+    pub fn names(&self) -> Vec<String> {
+        self.symbols.iter().map(|(name, _)| name.clone()).collect()
+    }
+}
+
+/// Reads the `-Z export-list` file and merges it into `exported_symbols`, the symbols that rustc
+/// would export otherwise. Returns `None` if there is no such file, if it has errors, or if the
+/// crate type does not export symbols.
+pub(crate) fn export_list(
+    sess: &Session,
+    crate_type: CrateType,
+    exported_symbols: &[String],
+) -> Option<ExportList> {
+    let path = sess.opts.unstable_opts.export_list.as_deref()?;
+    if crate_type == CrateType::Executable && !sess.opts.unstable_opts.export_executable_symbols {
+        sess.dcx().emit_warn(errors::ExportListExecutable);
+        return None;
+    }
+    let file = match fs::read_to_string(path) {
+        Ok(src) => toml::from_str::<ExportListFile>(&src).map_err(|error| error.to_string()),
+        Err(error) => Err(error.to_string()),
+    };
+    let file = match file {
+        Ok(file) => file,
+        Err(error) => {
+            sess.dcx().emit_err(errors::ExportListMalformed { path, error });
+            return None;
+        }
+    };
+    merge(sess, path, file, exported_symbols)
+}
+
+fn merge(
+    sess: &Session,
+    path: &Path,
+    mut file: ExportListFile,
+    exported_symbols: &[String],
+) -> Option<ExportList> {
+    let dcx = sess.dcx();
+    let mut has_errors = false;
+    let exported: FxHashSet<&str> = exported_symbols.iter().map(String::as_str).collect();
+    for (name, entry) in &file.symbols {
+        if let Some(version) = &entry.version
+            && !file.versions.contains(version)
+        {
+            dcx.emit_err(errors::ExportListUnknownVersion { path, version });
+            has_errors = true;
+        }
+        if !exported.contains(name.as_str()) {
+            dcx.emit_err(errors::ExportListUnknownSymbol { path, name });
+            has_errors = true;
+        }
+    }
+    for (name, alias) in &file.aliases {
+        if let Some(version) = &alias.version
+            && !file.versions.contains(version)
+        {
+            dcx.emit_err(errors::ExportListUnknownVersion { path, version });
+            has_errors = true;
+        }
+        if exported.contains(name.as_str()) {
+            dcx.emit_err(errors::ExportListAliasConflict { path, name });
+            has_errors = true;
+        }
+        let target_is_hidden = file
+            .symbols
+            .get(&alias.target)
+            .is_some_and(|target| target.visibility == Visibility::Hidden);
+        if !exported.contains(alias.target.as_str()) || target_is_hidden {
+            let target = &alias.target;
+            dcx.emit_err(errors::ExportListUnknownAliasTarget { path, name, target });
+            has_errors = true;
+        }
+    }
+    if has_errors {
+        return None;
+    }
+
+    // Unversioned symbols are part of the oldest version.
+    let default_version = file.versions.first().cloned();
+    let mut symbols = vec![];
+    for name in exported_symbols {
+        let entry = file.symbols.remove(name);
+        if entry.as_ref().is_some_and(|entry| entry.visibility == Visibility::Hidden) {
+            continue;
+        }
+        let version = entry.and_then(|entry| entry.version).or_else(|| default_version.clone());
+        symbols.push((name.clone(), version));
+    }
+    let mut aliases = vec![];
+    for (name, alias) in file.aliases {
+        let version = alias.version.or_else(|| default_version.clone());
+        symbols.push((name.clone(), version));
+        aliases.push(ExportAlias { name, target: alias.target, weak: alias.weak });
+    }
+
+    Some(ExportList { versions: file.versions, symbols, aliases })
+}
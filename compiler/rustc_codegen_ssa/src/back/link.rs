@@ -26,7 +26,7 @@
 use rustc_middle::middle::exported_symbols::SymbolExportKind;
 use rustc_session::config::{
     self, CFGuard, CrateType, DebugInfo, LinkerFeaturesCli, OutFileName, OutputFilenames,
-    OutputType, PrintKind, SplitDwarfKind, Strip,
+    OutputType, PrintKind, SplitDwarfKind, Strip, SwitchWithOptPath,
 };
 use rustc_session::cstore::DllImport;
 use rustc_session::output::{check_file_is_writeable, invalid_output_for_target, out_filename};
@@ -47,9 +47,11 @@
 
 use super::archive::{ArchiveBuilder, ArchiveBuilderBuilder};
 use super::command::Command;
+use super::export_list;
 use super::linker::{self, Linker};
 use super::metadata::{create_wrapper_file, MetadataPosition};
 use super::rpath::{self, RPathConfig};
+use super::symbol_sizes;
 use crate::{
     common, errors, looks_like_rust_object_file, CodegenResults, CompiledModule, CrateInfo,
     NativeLib,
@@ -609,6 +611,11 @@ fn link_staticlib(
 
     ab.build(out_filename);
 
+    let exported_symbols = &codegen_results.crate_info.exported_symbols[&CrateType::Staticlib];
+    if let Some(list) = export_list::export_list(sess, CrateType::Staticlib, exported_symbols) {
+        linker::write_staticlib_export_list(sess, out_filename, &list);
+    }
+
     let crates = codegen_results.crate_info.used_crates.iter();
 
     let fmts = codegen_results
@@ -765,7 +772,7 @@ fn link_natively(
     info!("preparing {:?} to {:?}", crate_type, out_filename);
     let (linker_path, flavor) = linker_and_flavor(sess);
     let self_contained_components = self_contained_components(sess, crate_type);
-    let mut cmd = linker_with_args(
+    let (mut cmd, map_path) = linker_with_args(
         &linker_path,
         flavor,
         sess,
@@ -1047,6 +1054,13 @@ fn is_illegal_instruction(_status: &ExitStatus) -> bool {
         }
     }
 
+    // The map is only missing if the linker does not support it, which was already reported.
+    if sess.opts.unstable_opts.print_symbol_sizes
+        && let Some(map_path) = map_path
+    {
+        symbol_sizes::print_symbol_sizes(sess, &map_path);
+    }
+
     match sess.split_debuginfo() {
         // If split debug information is disabled or located in individual files
         // there's nothing to do here.
@@ -2172,7 +2186,8 @@ fn add_rpath_args(
     }
 }
 
-/// Produce the linker command line containing linker path and arguments.
+/// Produce the linker command line containing linker path and arguments, along with the path of
+/// the map file the linker writes, if one was requested and the linker supports it.
 ///
 /// When comments in the function say "order-(in)dependent" they mean order-dependence between
 /// options and libraries/object files. For example `--whole-archive` (order-dependent) applies
@@ -2190,7 +2205,7 @@ fn linker_with_args(
     out_filename: &Path,
     codegen_results: &CodegenResults,
     self_contained_components: LinkSelfContainedComponents,
-) -> Result<Command, ErrorGuaranteed> {
+) -> Result<(Command, Option<PathBuf>), ErrorGuaranteed> {
     let self_contained_crt_objects = self_contained_components.is_crt_objects_enabled();
     let cmd = &mut *super::linker::get_linker(
         sess,
@@ -2208,11 +2223,14 @@ fn linker_with_args(
     // dynamic library.
     // Must be passed before any libraries to prevent the symbols to export from being thrown away,
     // at least on some platforms (e.g. windows-gnu).
-    cmd.export_symbols(
-        tmpdir,
-        crate_type,
-        &codegen_results.crate_info.exported_symbols[&crate_type],
-    );
+    let exported_symbols = &codegen_results.crate_info.exported_symbols[&crate_type];
+    if let Some(list) = export_list::export_list(sess, crate_type, exported_symbols) {
+        if !cmd.export_list(tmpdir, crate_type, &list) {
+            sess.dcx().emit_err(errors::ExportListUnsupported);
+        }
+    } else {
+        cmd.export_symbols(tmpdir, crate_type, exported_symbols);
+    }
 
     // Can be used for adding custom CRT objects or overriding order-dependent options above.
     // FIXME: In practice built-in target specs use this for arbitrary order-independent options,
@@ -2375,6 +2393,14 @@ fn linker_with_args(
         tmpdir,
     );
 
+    let map_path = linker_map_path(sess, out_filename, tmpdir).filter(|map_path| {
+        let supported = cmd.map_file(map_path);
+        if !supported {
+            sess.dcx().emit_warn(errors::LinkerMapUnsupported);
+        }
+        supported
+    });
+
     // Can be used for arbitrary order-independent options.
     // In practice may also be occasionally used for linking native libraries.
     // Passed after compiler-generated options to support manual overriding when necessary.
@@ -2393,7 +2419,23 @@ fn linker_with_args(
     // to it and remove the option. Currently the last holdout is wasm32-unknown-emscripten.
     add_post_link_args(cmd, sess, flavor);
 
-    Ok(cmd.take_cmd())
+    Ok((cmd.take_cmd(), map_path))
+}
+
+/// The path of the map file requested with `-Z linker-map`, or of a temporary one if only
+/// `-Z print-symbol-sizes` was passed.
+fn linker_map_path(sess: &Session, out_filename: &Path, tmpdir: &Path) -> Option<PathBuf> {
+    match &sess.opts.unstable_opts.linker_map {
+        SwitchWithOptPath::Enabled(Some(path)) => Some(path.clone()),
+        SwitchWithOptPath::Enabled(None) => {
+            let mut path = out_filename.as_os_str().to_owned();
+            path.push(".map");
+            Some(path.into())
+        }
+        SwitchWithOptPath::Disabled => {
+            sess.opts.unstable_opts.print_symbol_sizes.then(|| tmpdir.join("linker.map"))
+        }
+    }
 }
 
 fn add_order_independent_options(
@@ -20,6 +20,7 @@
 use tracing::{debug, warn};
 
 use super::command::Command;
+use super::export_list::{ExportAlias, ExportList};
 use super::symbol_export;
 use crate::errors;
 
@@ -290,6 +291,11 @@ fn output_filename(&mut self, path: &Path) {
     fn add_object(&mut self, path: &Path) {
         link_or_cc_args(self, &[path]);
     }
+    /// Asks the linker to write a map file listing the sections and symbols of the output to
+    /// `path`. Returns `false` if the linker does not support it.
+    fn map_file(&mut self, _path: &Path) -> bool {
+        false
+    }
     fn gc_sections(&mut self, keep_metadata: bool);
     fn no_gc_sections(&mut self);
     fn full_relro(&mut self);
@@ -303,6 +309,11 @@ fn add_object(&mut self, path: &Path) {
     fn no_crt_objects(&mut self);
     fn no_default_libraries(&mut self);
     fn export_symbols(&mut self, tmpdir: &Path, crate_type: CrateType, symbols: &[String]);
+    /// Exports the symbols of a `-Z export-list` file with their versions and aliases, instead of
+    /// `export_symbols`. Returns `false` if the linker does not support it.
+    fn export_list(&mut self, _tmpdir: &Path, _crate_type: CrateType, _list: &ExportList) -> bool {
+        false
+    }
     fn subsystem(&mut self, subsystem: &str);
     fn linker_plugin_lto(&mut self);
     fn add_eh_frame_header(&mut self) {}
@@ -454,6 +465,140 @@ fn with_as_needed(&mut self, as_needed: bool, f: impl FnOnce(&mut Self)) {
             }
         }
     }
+
+    fn export_symbols_with_list(
+        &mut self,
+        tmpdir: &Path,
+        crate_type: CrateType,
+        symbols: &[String],
+        list: Option<&ExportList>,
+    ) {
+        // Symbol visibility in object files typically takes care of this.
+        if crate_type == CrateType::Executable {
+            let should_export_executable_symbols =
+                self.sess.opts.unstable_opts.export_executable_symbols;
+            if self.sess.target.override_export_symbols.is_none()
+                && !should_export_executable_symbols
+            {
+                return;
+            }
+        }
+
+        // We manually create a list of exported symbols to ensure we don't expose any more.
+        // The object files have far more public symbols than we actually want to export,
+        // so we hide them all here.
+
+        if !self.sess.target.limit_rdylib_exports {
+            return;
+        }
+
+        let is_windows = self.sess.target.is_like_windows;
+        let path = tmpdir.join(if is_windows { "list.def" } else { "list" });
+
+        debug!("EXPORTED SYMBOLS:");
+
+        if self.sess.target.is_like_osx {
+            // Write a plain, newline-separated list of symbols
+            let res: io::Result<()> = try {
+                let mut f = BufWriter::new(File::create(&path)?);
+                for sym in symbols {
+                    debug!("  _{sym}");
+                    writeln!(f, "_{sym}")?;
+                }
+            };
+            if let Err(error) = res {
+                self.sess.dcx().emit_fatal(errors::LibDefWriteFailure { error });
+            }
+        } else if is_windows {
+            let res: io::Result<()> = try {
+                let mut f = BufWriter::new(File::create(&path)?);
+
+                // .def file similar to MSVC one but without LIBRARY section
+                // because LD doesn't like when it's empty
+                writeln!(f, "EXPORTS")?;
+                for symbol in symbols {
+                    debug!("  _{symbol}");
+                    writeln!(f, "  {symbol}")?;
+                }
+            };
+            if let Err(error) = res {
+                self.sess.dcx().emit_fatal(errors::LibDefWriteFailure { error });
+            }
+        } else {
+            // Write an LD version script
+            let res: io::Result<()> = try {
+                let mut f = BufWriter::new(File::create(&path)?);
+                write_version_script(&mut f, symbols, list)?;
+            };
+            if let Err(error) = res {
+                self.sess.dcx().emit_fatal(errors::VersionScriptWriteFailure { error });
+            }
+        }
+
+        if self.sess.target.is_like_osx {
+            self.link_arg("-exported_symbols_list").link_arg(path);
+        } else if self.sess.target.is_like_solaris {
+            self.link_arg("-M").link_arg(path);
+        } else {
+            if is_windows {
+                self.link_arg(path);
+            } else {
+                let mut arg = OsString::from("--version-script=");
+                arg.push(path);
+                self.link_arg(arg).link_arg("--no-undefined-version");
+            }
+        }
+
+        if let Some(list) = list
+            && !list.aliases.is_empty()
+        {
+            self.define_aliases(tmpdir, &list.aliases);
+        }
+    }
+
+    /// Defines the aliases of a `-Z export-list` file. ld64 defines them with `-alias`, and other
+    /// linkers with an implicit linker script. Weak aliases are only provided by the linker
+    /// script if no object defines their name, which `--undefined` makes it look for.
+    fn define_aliases(&mut self, tmpdir: &Path, aliases: &[ExportAlias]) {
+        if self.sess.target.is_like_osx {
+            for alias in aliases {
+                if alias.weak {
+                    self.sess
+                        .dcx()
+                        .emit_err(errors::ExportListWeakAliasUnsupported { name: &alias.name });
+                }
+                self.link_args(&[
+                    "-alias",
+                    &format!("_{}", alias.target),
+                    &format!("_{}", alias.name),
+                ]);
+            }
+            return;
+        }
+        if self.sess.target.is_like_solaris {
+            self.sess.dcx().emit_err(errors::ExportListAliasesUnsupported);
+            return;
+        }
+
+        let path = tmpdir.join("aliases.ld");
+        let res: io::Result<()> = try {
+            let mut f = BufWriter::new(File::create(&path)?);
+            for alias in aliases {
+                if alias.weak {
+                    writeln!(f, "PROVIDE({} = {});", alias.name, alias.target)?;
+                } else {
+                    writeln!(f, "{} = {};", alias.name, alias.target)?;
+                }
+            }
+        };
+        if let Err(error) = res {
+            self.sess.dcx().emit_fatal(errors::LinkScriptWriteFailure { path, error });
+        }
+        for alias in aliases.iter().filter(|alias| alias.weak) {
+            self.link_arg(format!("--undefined={}", alias.name));
+        }
+        self.link_or_cc_arg(path);
+    }
 }
 
 impl<'a> Linker for GccLinker<'a> {
@@ -717,89 +862,15 @@ fn no_default_libraries(&mut self) {
     }
 
     fn export_symbols(&mut self, tmpdir: &Path, crate_type: CrateType, symbols: &[String]) {
-        // Symbol visibility in object files typically takes care of this.
-        if crate_type == CrateType::Executable {
-            let should_export_executable_symbols =
-                self.sess.opts.unstable_opts.export_executable_symbols;
-            if self.sess.target.override_export_symbols.is_none()
-                && !should_export_executable_symbols
-            {
-                return;
-            }
-        }
-
-        // We manually create a list of exported symbols to ensure we don't expose any more.
-        // The object files have far more public symbols than we actually want to export,
-        // so we hide them all here.
+        self.export_symbols_with_list(tmpdir, crate_type, symbols, None);
+    }
 
+    fn export_list(&mut self, tmpdir: &Path, crate_type: CrateType, list: &ExportList) -> bool {
         if !self.sess.target.limit_rdylib_exports {
-            return;
-        }
-
-        let is_windows = self.sess.target.is_like_windows;
-        let path = tmpdir.join(if is_windows { "list.def" } else { "list" });
-
-        debug!("EXPORTED SYMBOLS:");
-
-        if self.sess.target.is_like_osx {
-            // Write a plain, newline-separated list of symbols
-            let res: io::Result<()> = try {
-                let mut f = BufWriter::new(File::create(&path)?);
-                for sym in symbols {
-                    debug!("  _{sym}");
-                    writeln!(f, "_{sym}")?;
-                }
-            };
-            if let Err(error) = res {
-                self.sess.dcx().emit_fatal(errors::LibDefWriteFailure { error });
-            }
-        } else if is_windows {
-            let res: io::Result<()> = try {
-                let mut f = BufWriter::new(File::create(&path)?);
-
-                // .def file similar to MSVC one but without LIBRARY section
-                // because LD doesn't like when it's empty
-                writeln!(f, "EXPORTS")?;
-                for symbol in symbols {
-                    debug!("  _{symbol}");
-                    writeln!(f, "  {symbol}")?;
-                }
-            };
-            if let Err(error) = res {
-                self.sess.dcx().emit_fatal(errors::LibDefWriteFailure { error });
-            }
-        } else {
-            // Write an LD version script
-            let res: io::Result<()> = try {
-                let mut f = BufWriter::new(File::create(&path)?);
-                writeln!(f, "{{")?;
-                if !symbols.is_empty() {
-                    writeln!(f, "  global:")?;
-                    for sym in symbols {
-                        debug!("    {sym};");
-                        writeln!(f, "    {sym};")?;
-                    }
-                }
-                writeln!(f, "\n  local:\n    *;\n}};")?;
-            };
-            if let Err(error) = res {
-                self.sess.dcx().emit_fatal(errors::VersionScriptWriteFailure { error });
-            }
-        }
-
-        if self.sess.target.is_like_osx {
-            self.link_arg("-exported_symbols_list").link_arg(path);
-        } else if self.sess.target.is_like_solaris {
-            self.link_arg("-M").link_arg(path);
-        } else {
-            if is_windows {
-                self.link_arg(path);
-            } else {
-                let mut arg = OsString::from("--version-script=");
-                arg.push(path);
-                self.link_arg(arg).link_arg("--no-undefined-version");
-            }
+            return false;
         }
+        self.export_symbols_with_list(tmpdir, crate_type, &list.names(), Some(list));
+        true
     }
 
     fn subsystem(&mut self, subsystem: &str) {
@@ -847,6 +918,115 @@ fn add_as_needed(&mut self) {
             self.link_args(&["-z", "ignore"]);
         }
     }
+
+    fn map_file(&mut self, path: &Path) -> bool {
+        if self.sess.target.is_like_osx {
+            self.link_arg("-map").link_arg(path);
+        } else if self.sess.target.is_like_solaris {
+            // The Solaris linker only reads map files, `-M` is not the GNU ld `-M`.
+            return false;
+        } else {
+            // GNU ld, gold, lld and mold all accept the GNU ld syntax. They demangle the names of
+            // the symbols in the map by default, but the sizes are reported by mangled name.
+            let mut arg = OsString::from("-Map=");
+            arg.push(path);
+            self.link_arg(arg).link_arg("--no-demangle");
+        }
+        true
+    }
+}
+
+/// Writes an LD version script exporting `symbols`. If `list` has versions, each of them is a
+/// version node that inherits from the previous one, with the symbols of that version.
+fn write_version_script(
+    f: &mut impl Write,
+    symbols: &[String],
+    list: Option<&ExportList>,
+) -> io::Result<()> {
+    let Some(list) = list.filter(|list| !list.versions.is_empty()) else {
+        writeln!(f, "{{")?;
+        if !symbols.is_empty() {
+            writeln!(f, "  global:")?;
+            for sym in symbols {
+                debug!("    {sym};");
+                writeln!(f, "    {sym};")?;
+            }
+        }
+        writeln!(f, "\n  local:\n    *;\n}};")?;
+        return Ok(());
+    };
+
+    for (i, version) in list.versions.iter().enumerate() {
+        writeln!(f, "{version} {{")?;
+        let mut version_symbols =
+            list.symbols.iter().filter(|(_, v)| v.as_ref() == Some(version)).peekable();
+        if version_symbols.peek().is_some() {
+            writeln!(f, "  global:")?;
+            for (sym, _) in version_symbols {
+                debug!("    {sym}@{version};");
+                writeln!(f, "    {sym};")?;
+            }
+        }
+        // The oldest version hides everything else, the others inherit from their predecessor.
+        match i.checked_sub(1) {
+            None => writeln!(f, "\n  local:\n    *;\n}};")?,
+            Some(prev) => writeln!(f, "}} {};", list.versions[prev])?,
+        }
+    }
+    Ok(())
+}
+
+/// The lines of the `EXPORTS` section of a `.def` file for `list`, which exports strong aliases
+/// as `alias=target`.
+fn def_exports(list: &ExportList) -> Vec<String> {
+    list.symbols
+        .iter()
+        .map(|(name, _)| match list.aliases.iter().find(|alias| &alias.name == name) {
+            Some(alias) if !alias.weak => format!("{name}={}", alias.target),
+            _ => name.clone(),
+        })
+        .collect()
+}
+
+/// Writes the export list of a staticlib next to it, since rustc does not link it: an LD version
+/// script, an ld64 exported symbols list or a `.def` file, depending on the target.
+pub(crate) fn write_staticlib_export_list(sess: &Session, out_filename: &Path, list: &ExportList) {
+    let target = &sess.target;
+    if !target.is_like_windows && !list.aliases.is_empty() {
+        sess.dcx().emit_err(errors::ExportListAliasesUnsupported);
+        return;
+    }
+    if let Some(alias) = list.aliases.iter().find(|alias| alias.weak) {
+        sess.dcx().emit_err(errors::ExportListWeakAliasUnsupported { name: &alias.name });
+        return;
+    }
+
+    let extension = if target.is_like_osx {
+        "exp"
+    } else if target.is_like_windows {
+        "def"
+    } else {
+        "version"
+    };
+    let path = out_filename.with_extension(extension);
+    let res: io::Result<()> = try {
+        let mut f = BufWriter::new(File::create(&path)?);
+        if target.is_like_osx {
+            for (sym, _) in &list.symbols {
+                writeln!(f, "_{sym}")?;
+            }
+        } else if target.is_like_windows {
+            writeln!(f, "EXPORTS")?;
+            for export in def_exports(list) {
+                writeln!(f, "  {export}")?;
+            }
+        } else {
+            write_version_script(&mut f, &list.names(), Some(list))?;
+        }
+    };
+    if let Err(error) = res {
+        sess.dcx().emit_err(errors::ExportListWriteFailure { path, error });
+    }
 }
 
 pub struct MsvcLinker<'a> {
@@ -961,6 +1141,13 @@ fn output_filename(&mut self, path: &Path) {
         self.link_arg(&arg);
     }
 
+    fn map_file(&mut self, path: &Path) -> bool {
+        let mut arg = OsString::from("/MAP:");
+        arg.push(path);
+        self.link_arg(&arg);
+        true
+    }
+
     fn optimize(&mut self) {
         // Needs more investigation of `/OPT` arguments
     }
@@ -1064,6 +1251,20 @@ fn export_symbols(&mut self, tmpdir: &Path, crate_type: CrateType, symbols: &[St
         self.link_arg(&arg);
     }
 
+    fn export_list(&mut self, tmpdir: &Path, crate_type: CrateType, list: &ExportList) -> bool {
+        // Strong aliases are exported by the `.def` file, while weak ones are only resolved to
+        // their target by `/ALTERNATENAME` if no object defines them.
+        self.export_symbols(tmpdir, crate_type, &def_exports(list));
+        let prefix = if self.sess.target.arch == "x86" { "_" } else { "" };
+        for alias in list.aliases.iter().filter(|alias| alias.weak) {
+            self.link_arg(&format!(
+                "/ALTERNATENAME:{prefix}{}={prefix}{}",
+                alias.name, alias.target
+            ));
+        }
+        true
+    }
+
     fn subsystem(&mut self, subsystem: &str) {
         // Note that previous passes of the compiler validated this subsystem,
         // so we just blindly pass it to the linker.
@@ -1301,6 +1502,13 @@ fn link_staticlib_by_path(&mut self, path: &Path, whole_archive: bool) {
         }
     }
 
+    fn map_file(&mut self, path: &Path) -> bool {
+        let mut arg = OsString::from("--Map=");
+        arg.push(path);
+        self.link_arg(arg).link_arg("--no-demangle");
+        true
+    }
+
     fn full_relro(&mut self) {}
 
     fn partial_relro(&mut self) {}
@@ -1,9 +1,11 @@
 pub mod archive;
 pub mod command;
+pub mod export_list;
 pub mod link;
 pub mod linker;
 pub mod lto;
 pub mod metadata;
 pub mod rpath;
 pub mod symbol_export;
+mod symbol_sizes;
 pub mod write;
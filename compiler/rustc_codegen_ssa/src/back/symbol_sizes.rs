@@ -0,0 +1,507 @@
+//! Reads the map file written by the linker for `-Z linker-map` and prints the size of the
+//! symbols of the linked output for `-Z print-symbol-sizes`, grouped by crate, module and
+//! generic item.
+//!
+//! Each linker has its own map format, which is recognized from its contents. Only some of them
+//! list the size of every symbol, so the size of a symbol is otherwise computed from the address
+//! of the next symbol in its input section, or from the size of its input section when the
+//! section is named after the symbol (`-Z function-sections`, which is the default for most
+//! targets). The sizes include the padding between symbols.
+
+use std::path::Path;
+use std::{fs, iter};
+
+use rustc_data_structures::fx::FxIndexMap;
+use rustc_session::config::PrintSizesFormat;
+use rustc_session::Session;
+use rustc_symbol_mangling::symbol_path::{symbol_path, SymbolPath};
+
+use crate::errors;
+
+pub(crate) fn print_symbol_sizes(sess: &Session, map_path: &Path) {
+    let map = match fs::read(map_path) {
+        Ok(map) => map,
+        Err(error) => {
+            sess.dcx().emit_warn(errors::LinkerMapUnreadable { path: map_path, error });
+            return;
+        }
+    };
+    let symbols = parse_map(&String::from_utf8_lossy(&map));
+    if symbols.is_empty() {
+        sess.dcx().emit_warn(errors::LinkerMapUnrecognized { path: map_path });
+        return;
+    }
+
+    if sess.opts.unstable_opts.print_sizes_format == PrintSizesFormat::Json {
+        for (symbol, size) in symbols {
+            let path = symbol_path(&symbol);
+            let json = serde_json::json!({
+                "symbol": symbol,
+                "size": size,
+                "demangled": path.as_ref().map(|path| &path.demangled),
+                "crate": path.as_ref().map(|path| &path.crate_name),
+                "module": path.as_ref().map(|path| path.module.join("::")),
+                "item": path.as_ref().map(|path| path.item.join("::")),
+                "instantiation": path.as_ref().is_some_and(|path| path.is_instantiation),
+            });
+            println!("{json}");
+        }
+        return;
+    }
+
+    let report = SizeReport::new(symbols);
+    println!(
+        "print-symbol-size total: {} bytes in {} symbols",
+        report.total_size, report.symbol_count
+    );
+    for krate in &report.crates {
+        println!("print-symbol-size crate `{}`: {} bytes", krate.name, krate.size);
+        for module in &krate.modules {
+            println!("print-symbol-size     module `{}`: {} bytes", module.name, module.size);
+            for item in &module.items {
+                print_item(item, "        ");
+            }
+        }
+    }
+    if !report.other.is_empty() {
+        let size: u64 = report.other.iter().map(|(_, size)| size).sum();
+        println!("print-symbol-size other symbols: {size} bytes");
+        for (symbol, size) in &report.other {
+            println!("print-symbol-size     `{symbol}`: {size} bytes");
+        }
+    }
+}
+
+fn print_item(item: &ItemSizes, indent: &str) {
+    match &item.symbols[..] {
+        [(symbol, size)] if !item.is_generic => {
+            println!("print-symbol-size {indent}`{symbol}`: {size} bytes");
+        }
+        symbols => {
+            let kind = if item.is_generic { "instantiations" } else { "symbols" };
+            println!(
+                "print-symbol-size {indent}`{}`: {} bytes in {} {kind}",
+                item.name,
+                item.size,
+                symbols.len()
+            );
+            for (symbol, size) in symbols {
+                println!("print-symbol-size {indent}    `{symbol}`: {size} bytes");
+            }
+        }
+    }
+}
+
+/// The sizes of the symbols of the linked output, sorted from large to small at each level.
+struct SizeReport {
+    total_size: u64,
+    symbol_count: usize,
+    crates: Vec<CrateSizes>,
+    /// The symbols that do not come from Rust code, with their mangled names.
+    other: Vec<(String, u64)>,
+}
+
+struct CrateSizes {
+    name: String,
+    size: u64,
+    modules: Vec<ModuleSizes>,
+}
+
+struct ModuleSizes {
+    name: String,
+    size: u64,
+    items: Vec<ItemSizes>,
+}
+
+struct ItemSizes {
+    name: String,
+    size: u64,
+    is_generic: bool,
+    /// The demangled symbols of the item, one for each instantiation of a generic item.
+    symbols: Vec<(String, u64)>,
+}
+
+impl SizeReport {
+    fn new(symbols: Vec<(String, u64)>) -> SizeReport {
+        let total_size = symbols.iter().map(|(_, size)| size).sum();
+        let symbol_count = symbols.len();
+
+        let mut crates: FxIndexMap<String, FxIndexMap<String, FxIndexMap<String, ItemSizes>>> =
+            Default::default();
+        let mut other = vec![];
+        for (symbol, size) in symbols {
+            let Some(SymbolPath { crate_name, module, item, demangled, is_instantiation }) =
+                symbol_path(&symbol)
+            else {
+                other.push((symbol, size));
+                continue;
+            };
+            let module: Vec<_> =
+                iter::once(&crate_name).chain(&module).map(String::as_str).collect();
+            let module = module.join("::");
+            let item_name = format!("{module}::{}", item.join("::"));
+            let item = crates
+                .entry(crate_name)
+                .or_default()
+                .entry(module)
+                .or_default()
+                .entry(item_name.clone())
+                .or_insert_with(|| ItemSizes {
+                    name: item_name,
+                    size: 0,
+                    is_generic: false,
+                    symbols: vec![],
+                });
+            item.size += size;
+            item.is_generic |= is_instantiation;
+            item.symbols.push((demangled, size));
+        }
+
+        let mut crates: Vec<_> = crates
+            .into_iter()
+            .map(|(name, modules)| {
+                let mut modules: Vec<_> = modules
+                    .into_iter()
+                    .map(|(name, items)| {
+                        let mut items: Vec<_> = items.into_values().collect();
+                        for item in &mut items {
+                            sort_by_size(&mut item.symbols, |(symbol, size)| {
+                                (*size, symbol.as_str())
+                            });
+                        }
+                        sort_by_size(&mut items, |item| (item.size, item.name.as_str()));
+                        let size = items.iter().map(|item| item.size).sum();
+                        ModuleSizes { name, size, items }
+                    })
+                    .collect();
+                sort_by_size(&mut modules, |module| (module.size, module.name.as_str()));
+                let size = modules.iter().map(|module| module.size).sum();
+                CrateSizes { name, size, modules }
+            })
+            .collect();
+        sort_by_size(&mut crates, |krate| (krate.size, krate.name.as_str()));
+        sort_by_size(&mut other, |(symbol, size)| (*size, symbol.as_str()));
+
+        SizeReport { total_size, symbol_count, crates, other }
+    }
+}
+
+/// Sorts from large to small, and then by name.
+fn sort_by_size<T>(entries: &mut [T], key: impl Fn(&T) -> (u64, &str)) {
+    entries.sort_by(|a, b| {
+        let (a_size, a_name) = key(a);
+        let (b_size, b_name) = key(b);
+        b_size.cmp(&a_size).then_with(|| a_name.cmp(b_name))
+    });
+}
+
+/// Returns the symbols listed in a linker map and their sizes, or nothing if the format of the
+/// map is not recognized.
+fn parse_map(map: &str) -> Vec<(String, u64)> {
+    let symbols = if map.starts_with("# Path:") {
+        parse_ld64_map(map)
+    } else if map.contains("Publics by Value") {
+        parse_msvc_map(map)
+    } else if map.lines().next().is_some_and(|header| header.contains(" Out ")) {
+        parse_lld_map(map)
+    } else if map.contains("Linker script and memory map") || map.contains("\nMemory map\n") {
+        parse_gnu_map(map)
+    } else {
+        vec![]
+    };
+
+    // The same symbol can be listed more than once, e.g. for local symbols of different object
+    // files.
+    let mut merged: FxIndexMap<String, u64> = Default::default();
+    for (symbol, size) in symbols {
+        if size > 0 {
+            *merged.entry(symbol).or_default() += size;
+        }
+    }
+    merged.into_iter().collect()
+}
+
+fn hex(s: &str) -> Option<u64> {
+    u64::from_str_radix(s.strip_prefix("0x").unwrap_or(s), 16).ok()
+}
+
+/// An input section of the linked output, and the symbols it defines.
+struct Section {
+    /// The symbol the section is named after, if any.
+    symbol: Option<String>,
+    address: u64,
+    size: u64,
+    symbols: Vec<(u64, String)>,
+}
+
+impl Section {
+    fn new(name: &str, address: u64, size: u64) -> Section {
+        Section { symbol: section_symbol(name), address, size, symbols: vec![] }
+    }
+}
+
+/// The symbol that a section is named after, e.g. `foo` for `.text.foo`.
+fn section_symbol(name: &str) -> Option<String> {
+    const PREFIXES: &[&str] = &[
+        ".text.unlikely.",
+        ".text.hot.",
+        ".text.startup.",
+        ".text.",
+        ".rodata.",
+        ".data.rel.ro.local.",
+        ".data.rel.ro.",
+        ".data.rel.local.",
+        ".data.",
+        ".bss.",
+        ".tdata.",
+        ".tbss.",
+    ];
+    PREFIXES
+        .iter()
+        .find_map(|prefix| name.strip_prefix(prefix))
+        .filter(|symbol| !symbol.is_empty())
+        .map(str::to_string)
+}
+
+/// Computes the size of each symbol from the address of the next symbol in its section, or
+/// uses the size of the section for a section without symbols that is named after a symbol.
+fn sizes_from_sections(sections: Vec<Section>) -> Vec<(String, u64)> {
+    let mut sizes = vec![];
+    for mut section in sections {
+        if section.symbols.is_empty() {
+            if let Some(symbol) = section.symbol {
+                sizes.push((symbol, section.size));
+            }
+            continue;
+        }
+        let end = section.address.saturating_add(section.size);
+        // The values of thread-local symbols are offsets in the thread-local storage block rather
+        // than addresses, so they are moved into their section.
+        if section.symbols.iter().any(|&(address, _)| address < section.address || address > end) {
+            let base = section.symbols.iter().map(|&(address, _)| address).min().unwrap();
+            for (address, _) in &mut section.symbols {
+                *address = section.address.saturating_add(*address - base);
+            }
+        }
+        section.symbols.sort_by_key(|&(address, _)| address);
+        // Aliases get the size of the first symbol at their address.
+        section.symbols.dedup_by_key(|&mut (address, _)| address);
+        let ends = section.symbols.iter().skip(1).map(|&(address, _)| address);
+        let ends = ends.chain(iter::once(end)).collect::<Vec<_>>();
+        for ((address, symbol), end) in section.symbols.into_iter().zip(ends) {
+            sizes.push((symbol, end.saturating_sub(address)));
+        }
+    }
+    sizes
+}
+
+/// Parses the map written by ld64 for `-map`, which lists the size of every symbol:
+///
+/// ```text
+/// # Symbols:
+/// # Address	Size    	File  Name
+/// 0x100003F70	0x00000024	[  2] __ZN4main4main17h6e5d2c5f4e1b6b1aE
+/// ```
+fn parse_ld64_map(map: &str) -> Vec<(String, u64)> {
+    let mut symbols = vec![];
+    let mut in_symbols = false;
+    for line in map.lines() {
+        if line.starts_with('#') {
+            in_symbols = line.starts_with("# Symbols:") || (in_symbols && line.starts_with("# A"));
+            continue;
+        }
+        if !in_symbols {
+            continue;
+        }
+        let mut columns = line.split_whitespace();
+        let (Some(_address), Some(size), Some((_file, name))) =
+            (columns.next(), columns.next().and_then(hex), line.split_once(']'))
+        else {
+            continue;
+        };
+        // Mach-O prefixes the names of all symbols with an underscore.
+        let name = name.trim();
+        symbols.push((name.strip_prefix('_').unwrap_or(name).to_string(), size));
+    }
+    symbols
+}
+
+/// Parses the map written by `link.exe` for `/MAP`, which lists the segments, then the public
+/// and static symbols with their addresses:
+///
+/// ```text
+///  Start         Length     Name                   Class
+///  0001:00000000 000123a0H .text$mn                CODE
+///
+///   Address         Publics by Value              Rva+Base               Lib:Object
+///
+///  0001:00000050       main                       0000000140001050 f   main.o
+/// ```
+fn parse_msvc_map(map: &str) -> Vec<(String, u64)> {
+    fn address(s: &str) -> Option<(u32, u64)> {
+        let (segment, offset) = s.split_once(':')?;
+        Some((u32::from_str_radix(segment, 16).ok()?, hex(offset)?))
+    }
+
+    let mut segment_ends: FxIndexMap<u32, u64> = Default::default();
+    let mut segments: FxIndexMap<u32, Vec<(u64, String)>> = Default::default();
+    let mut in_symbols = false;
+    for line in map.lines() {
+        if line.contains("Publics by Value") {
+            in_symbols = true;
+            continue;
+        }
+        let columns: Vec<_> = line.split_whitespace().collect();
+        let Some((segment, offset)) = columns.first().and_then(|column| address(column)) else {
+            continue;
+        };
+        if !in_symbols {
+            if let Some(length) = columns.get(1).and_then(|length| length.strip_suffix('H'))
+                && let Some(length) = hex(length)
+            {
+                let end = segment_ends.entry(segment).or_default();
+                *end = (*end).max(offset + length);
+            }
+        } else if segment != 0
+            && let Some(name) = columns.get(1)
+        {
+            segments.entry(segment).or_default().push((offset, name.to_string()));
+        }
+    }
+
+    let sections = segments
+        .into_iter()
+        .map(|(segment, symbols)| {
+            let end = segment_ends.get(&segment).copied().unwrap_or(0);
+            Section { symbol: None, address: 0, size: end, symbols }
+        })
+        .collect();
+    sizes_from_sections(sections)
+}
+
+/// Parses the map written by LLD (for ELF and WebAssembly) and mold for `-Map`, which has a
+/// column for each kind of entry after the numeric columns:
+///
+/// ```text
+///              VMA              LMA     Size Align Out     In      Symbol
+///           201120           201120       25    16 .text
+///           201120           201120       25    16         main.o:(.text.main)
+///           201120           201120        0     1                 main
+/// ```
+fn parse_lld_map(map: &str) -> Vec<(String, u64)> {
+    let mut lines = map.lines();
+    let header = lines.next().unwrap();
+    let columns: Vec<_> = header.split_whitespace().collect();
+    let (Some(numeric_columns), Some(size_column), Some(in_column), Some(symbol_column)) = (
+        columns.iter().position(|&column| column == "Out"),
+        columns.iter().position(|&column| column == "Size"),
+        header.find(" In ").map(|offset| offset + 1),
+        header.find(" Symbol").map(|offset| offset + 1),
+    ) else {
+        return vec![];
+    };
+    if size_column >= numeric_columns {
+        return vec![];
+    }
+
+    let mut sections: Vec<Section> = vec![];
+    let mut in_section = false;
+    for line in lines {
+        let mut rest = line;
+        let mut values = vec![];
+        for _ in 0..numeric_columns {
+            let trimmed = rest.trim_start();
+            let end = trimmed.find(char::is_whitespace).unwrap_or(trimmed.len());
+            values.push(hex(&trimmed[..end]));
+            rest = &trimmed[end..];
+        }
+        // WebAssembly sections without an address, like the code section, have `-` as their
+        // address, but an offset.
+        let address = values[..size_column].iter().flatten().next();
+        let (Some(&address), Some(&Some(size))) = (address, values.get(size_column)) else {
+            continue;
+        };
+        let name = rest.trim();
+        let indent = line.len() - rest.trim_start().len();
+        if indent >= symbol_column {
+            if in_section && let Some(section) = sections.last_mut() {
+                section.symbols.push((address, name.to_string()));
+            }
+        } else if indent >= in_column {
+            // Input sections are named `file:(section)`.
+            let name =
+                name.rsplit_once(":(").and_then(|(_, name)| name.strip_suffix(')')).unwrap_or(name);
+            let mut section = Section::new(name, address, size);
+            if section.symbol.is_none() && !name.starts_with('.') {
+                // WebAssembly functions and data segments are named after their symbol.
+                section.symbol = Some(name.to_string());
+            }
+            sections.push(section);
+            in_section = true;
+        } else {
+            in_section = false;
+        }
+    }
+    sizes_from_sections(sections)
+}
+
+/// Parses the map written by GNU ld and gold for `-Map`, which lists the input sections of
+/// each output section, followed by the global symbols they define:
+///
+/// ```text
+///  .text.main     0x0000000000001130       0x25 main.o
+///                 0x0000000000001130                main
+///  .text._ZN4main4main17h6e5d2c5f4e1b6b1aE
+///                 0x0000000000001160       0x3a main.o
+/// ```
+fn parse_gnu_map(map: &str) -> Vec<(String, u64)> {
+    // gold only prints the second part of the map written by GNU ld, with a different title.
+    let Some((_, map)) =
+        map.split_once("Linker script and memory map").or_else(|| map.split_once("\nMemory map\n"))
+    else {
+        return vec![];
+    };
+    let mut sections: Vec<Section> = vec![];
+    let mut in_section = false;
+    // The name of an input section that is too long for its address and size to be on the same
+    // line.
+    let mut pending_section = None;
+    for line in map.lines() {
+        let columns: Vec<_> = line.split_whitespace().collect();
+        let section = match (pending_section.take(), &columns[..]) {
+            (Some(name), [address, size, ..]) => Some((name, address, size)),
+            (None, [name, rest @ ..]) if line.starts_with(" .") => match rest {
+                [] => {
+                    pending_section = Some(*name);
+                    None
+                }
+                [address, size, ..] => Some((*name, address, size)),
+                _ => None,
+            },
+            (None, [address, name]) if line.starts_with(char::is_whitespace) => {
+                if in_section
+                    && address.starts_with("0x")
+                    && !name.starts_with("0x")
+                    && let Some(address) = hex(address)
+                    && let Some(section) = sections.last_mut()
+                {
+                    section.symbols.push((address, name.to_string()));
+                }
+                continue;
+            }
+            _ => None,
+        };
+        in_section = false;
+        if let Some((name, address, size)) = section
+            && address.starts_with("0x")
+            && let (Some(address), Some(size)) = (hex(address), hex(size))
+        {
+            sections.push(Section::new(name, address, size));
+            in_section = true;
+        }
+    }
+    sizes_from_sections(sections)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,171 @@
+use super::parse_map;
+
+const MAIN: &str = "_RNvCs32gj3y4fnmh_4main4main";
+const AREA: &str = "_RNvMNtCs32gj3y4fnmh_4main6shapesNtB2_6Circle4area";
+
+#[track_caller]
+fn check(map: &[&str], expected: &[(&str, u64)]) {
+    let symbols = parse_map(&map.join("\n"));
+    let symbols: Vec<_> = symbols.iter().map(|(symbol, size)| (symbol.as_str(), *size)).collect();
+    assert_eq!(symbols, expected);
+}
+
+#[test]
+fn test_gnu_map() {
+    check(
+        &[
+            "Discarded input sections",
+            "",
+            " .text._RNvCs32gj3y4fnmh_4main6unused",
+            "                0x0000000000000000       0x10 main.o",
+            "",
+            "Linker script and memory map",
+            "",
+            "LOAD main.o",
+            "",
+            ".text           0x0000000000001000     0xa000",
+            " .text          0x0000000000001000       0x30 start.o",
+            "                0x0000000000001000                _start",
+            "                0x0000000000001010                _start_main",
+            " .text._RNvCs32gj3y4fnmh_4main4main",
+            "                0x000000000000a980       0xea main.o",
+            "                0x000000000000a980                _RNvCs32gj3y4fnmh_4main4main",
+            " *fill*         0x000000000000aa6a        0x6 ",
+            " .text._RNvMNtCs32gj3y4fnmh_4main6shapesNtB2_6Circle4area",
+            "                0x000000000000aa70       0x42 main.o",
+            " *fill*         0x000000000000aab2        0xe ",
+            " .text.main     0x000000000000aac0       0x27 main.o",
+            "                0x000000000000aac0                main",
+        ],
+        &[("_start", 0x10), ("_start_main", 0x20), (MAIN, 0xea), (AREA, 0x42), ("main", 0x27)],
+    );
+}
+
+#[test]
+fn test_gold_map() {
+    check(
+        &[
+            "Discarded input sections",
+            "",
+            " .text._RNvCs32gj3y4fnmh_4main6unused",
+            "                0x0000000000000000       0x10 main.o",
+            "",
+            "Memory map",
+            "",
+            " ** file header",
+            "                0x0000000000000000       0x40",
+            ".text           0x000000000000b000     0xa000",
+            " .text._RNvCs32gj3y4fnmh_4main4main",
+            "                0x000000000000b7b0       0xea main.o",
+            "                0x000000000000b7b0                _RNvCs32gj3y4fnmh_4main4main",
+            " ** fill        0x000000000000b89a        0x6",
+            " .text._RNvMNtCs32gj3y4fnmh_4main6shapesNtB2_6Circle4area",
+            "                0x000000000000b8a0       0x42 main.o",
+            " ** fill        0x000000000000b8e2        0xe",
+        ],
+        &[(MAIN, 0xea), (AREA, 0x42)],
+    );
+}
+
+#[test]
+fn test_lld_map() {
+    check(
+        &[
+            "             VMA              LMA     Size Align Out     In      Symbol",
+            "             2e0              2e0       1c     1 .interp",
+            "             2e0              2e0       1c     1         <internal>:(.interp)",
+            "           14000            14000     a000    16 .text",
+            "           14000            14000       30    16         start.o:(.text)",
+            "           14000            14000        0     1                 _start",
+            "           14010            14010        0     1                 _start_main",
+            "           14e70            14e70       ea    16         main.o:(.text._RNvCs32gj3y4fnmh_4main4main)",
+            "           14e70            14e70       ea     1                 _RNvCs32gj3y4fnmh_4main4main",
+            "           14f60            14f60       42    16         main.o:(.text._RNvMNtCs32gj3y4fnmh_4main6shapesNtB2_6Circle4area)",
+            "           14f60            14f60       42     1                 _RNvMNtCs32gj3y4fnmh_4main6shapesNtB2_6Circle4area",
+            "           53cd0            53cd0       10     8 .tbss",
+            "           53cd0            53cd0       10     8         std.o:(.tbss)",
+            // The values of thread-local symbols are offsets.
+            "              40               40        0     1                 CURRENT",
+            "              48               48        0     1                 ID",
+        ],
+        &[
+            ("_start", 0x10),
+            ("_start_main", 0x20),
+            (MAIN, 0xea),
+            (AREA, 0x42),
+            ("CURRENT", 8),
+            ("ID", 8),
+        ],
+    );
+}
+
+#[test]
+fn test_lld_wasm_map() {
+    check(
+        &[
+            "    Addr   Off   Size Out     In      Symbol",
+            "       -     8     33 TYPE",
+            "       -   1a4   1f30 CODE",
+            "       -   1a5     2d         main.o:(_RNvCs32gj3y4fnmh_4main4main)",
+            "       -   1a5     2d                 _RNvCs32gj3y4fnmh_4main4main",
+            "       -   1d2     12         main.o:(_RNvMNtCs32gj3y4fnmh_4main6shapesNtB2_6Circle4area)",
+            "       -   1d2     12                 _RNvMNtCs32gj3y4fnmh_4main6shapesNtB2_6Circle4area",
+        ],
+        &[(MAIN, 0x2d), (AREA, 0x12)],
+    );
+}
+
+#[test]
+fn test_ld64_map() {
+    check(
+        &[
+            "# Path: main",
+            "# Arch: arm64",
+            "# Object files:",
+            "[  0] linker synthesized",
+            "[  1] main.o",
+            "# Sections:",
+            "# Address\tSize    \tSegment\tSection",
+            "0x100003E40\t0x00000120\t__TEXT\t__text",
+            "# Symbols:",
+            "# Address\tSize    \tFile  Name",
+            "0x100003E40\t0x000000EC\t[  1] __RNvCs32gj3y4fnmh_4main4main",
+            "0x100003F2C\t0x00000010\t[  1] __RNvMNtCs32gj3y4fnmh_4main6shapesNtB2_6Circle4area",
+            "0x100003F3C\t0x00000024\t[  1] _main",
+            "# Dead Stripped Symbols:",
+            "#        \tSize    \tFile  Name",
+            "<<dead>> \t0x00000010\t[  1] __RNvCs32gj3y4fnmh_4main6unused",
+        ],
+        &[(MAIN, 0xec), (AREA, 0x10), ("main", 0x24)],
+    );
+}
+
+#[test]
+fn test_msvc_map() {
+    check(
+        &[
+            " main",
+            "",
+            " Preferred load address is 0000000140000000",
+            "",
+            " Start         Length     Name                   Class",
+            " 0001:00000000 00000100H .text$mn                CODE",
+            " 0001:00000100 00000020H .text$mn$00             CODE",
+            "",
+            "  Address         Publics by Value              Rva+Base               Lib:Object",
+            "",
+            " 0000:00000000       __guard_flags              0000000000000000     <absolute>",
+            " 0001:00000000       _RNvCs32gj3y4fnmh_4main4main 0000000140001000 f   main.o",
+            " 0001:000000f0       _RNvMNtCs32gj3y4fnmh_4main6shapesNtB2_6Circle4area 00000001400010f0 f   main.o",
+            " 0001:00000100       main                       0000000140001100 f   main.o",
+            "",
+            " entry point at        0001:00000100",
+        ],
+        &[(MAIN, 0xf0), (AREA, 0x10), ("main", 0x20)],
+    );
+}
+
+#[test]
+fn test_unknown_map() {
+    check(&["main.o: file format elf64-x86-64", "", "SYMBOL TABLE:"], &[]);
+}
@@ -574,6 +574,31 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
     target_cpu: String,
     metadata: EncodedMetadata,
     need_metadata_module: bool,
+) -> OngoingCodegen<B> {
+    codegen_units_of_backend(backend, tcx, target_cpu, metadata, need_metadata_module, None)
+}
+
+/// Generates the codegen units that `#[codegen_backend]` assigns to the secondary codegen backend
+/// `name`, see `CodegenBackend::codegen_secondary_units`. The allocator shim and the metadata are
+/// generated by the main backend.
+pub fn codegen_secondary_units<B: ExtraBackendMethods>(
+    backend: B,
+    tcx: TyCtxt<'_>,
+    target_cpu: String,
+    name: Symbol,
+) -> OngoingCodegen<B> {
+    codegen_units_of_backend(backend, tcx, target_cpu, EncodedMetadata::empty(), false, Some(name))
+}
+
+/// Generates the codegen units of the secondary codegen backend `codegen_backend`, or those of the
+/// main backend if it is `None`.
+fn codegen_units_of_backend<B: ExtraBackendMethods>(
+    backend: B,
+    tcx: TyCtxt<'_>,
+    target_cpu: String,
+    metadata: EncodedMetadata,
+    need_metadata_module: bool,
+    codegen_backend: Option<Symbol>,
 ) -> OngoingCodegen<B> {
     // Skip crate items and just output metadata in -Z no-codegen mode.
     if tcx.sess.opts.unstable_opts.no_codegen || !tcx.sess.opts.output_types.should_codegen() {
@@ -590,7 +615,12 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
 
     // Run the monomorphization collector and partition the collected items into
     // codegen units.
-    let codegen_units = tcx.collect_and_partition_mono_items(()).1;
+    let codegen_units: Vec<_> = tcx
+        .collect_and_partition_mono_items(())
+        .1
+        .iter()
+        .filter(|cgu| cgu.codegen_backend() == codegen_backend)
+        .collect();
 
     // Force all codegen_unit queries so they are already either red or green
     // when compile_codegen_unit accesses them. We are not able to re-execute
@@ -598,7 +628,7 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
     // lead to having to re-execute compile_codegen_unit, possibly
     // unnecessarily.
     if tcx.dep_graph.is_fully_enabled() {
-        for cgu in codegen_units {
+        for cgu in &codegen_units {
             tcx.ensure().codegen_unit(cgu.name());
         }
     }
@@ -634,7 +664,9 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
         start_async_codegen(backend.clone(), tcx, target_cpu, metadata, metadata_module);
 
     // Codegen an allocator shim, if necessary.
-    if let Some(kind) = allocator_kind_for_codegen(tcx) {
+    if codegen_backend.is_none()
+        && let Some(kind) = allocator_kind_for_codegen(tcx)
+    {
         let llmod_id =
             cgu_name_builder.build_cgu_name(LOCAL_CRATE, &["crate"], Some("allocator")).to_string();
         let module_llvm = tcx.sess.time("write_allocator_module", || {
@@ -673,7 +705,7 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
     // smallest are first, second largest and smallest are next, etc. If there
     // are large size variations, this can reduce memory usage significantly.
     let codegen_units: Vec<_> = {
-        let mut sorted_cgus = codegen_units.iter().collect::<Vec<_>>();
+        let mut sorted_cgus = codegen_units.iter().copied().collect::<Vec<_>>();
         sorted_cgus.sort_by_key(|cgu| cmp::Reverse(cgu.size_estimate()));
 
         let (first_half, second_half) = sorted_cgus.split_at(sorted_cgus.len() / 2);
@@ -77,6 +77,7 @@ fn codegen_fn_attrs(tcx: TyCtxt<'_>, did: LocalDefId) -> CodegenFnAttrs {
     let mut inline_span = None;
     let mut link_ordinal_span = None;
     let mut no_sanitize_span = None;
+    let mut codegen_backend_span = None;
 
     for attr in attrs.iter() {
         // In some cases, attribute are only valid on functions, but it's the `check_attr`
@@ -339,6 +340,19 @@ fn codegen_fn_attrs(tcx: TyCtxt<'_>, did: LocalDefId) -> CodegenFnAttrs {
                 }
             }
             sym::link_name => codegen_fn_attrs.link_name = attr.value_str(),
+            sym::codegen_backend => {
+                codegen_backend_span = Some(attr.span);
+                if let Some(name) = attr.value_str() {
+                    let secondary_backends =
+                        &tcx.sess.opts.unstable_opts.secondary_codegen_backends;
+                    if name.as_str() != tcx.sess.codegen_backend_name()
+                        && !secondary_backends.iter().any(|backend| backend == name.as_str())
+                    {
+                        tcx.dcx().emit_err(errors::UnknownCodegenBackend { span: attr.span, name });
+                    }
+                    codegen_fn_attrs.codegen_backend = Some(name);
+                }
+            }
             sym::link_ordinal => {
                 link_ordinal_span = Some(attr.span);
                 if let ordinal @ Some(_) = check_link_ordinal(tcx, attr) {
@@ -629,6 +643,14 @@ fn emit_error_with_label(
         }
     }
 
+    // The copies of an `#[inline]` function are generated by the codegen backend of the function
+    // that they are inlined into.
+    if let Some(span) = codegen_backend_span
+        && matches!(codegen_fn_attrs.inline, InlineAttr::Hint | InlineAttr::Always)
+    {
+        tcx.dcx().span_err(span, "cannot use `#[codegen_backend]` with `#[inline]`");
+    }
+
     if !codegen_fn_attrs.no_sanitize.is_empty() {
         if codegen_fn_attrs.inline == InlineAttr::Always {
             if let (Some(no_sanitize_span), Some(inline_span)) = (no_sanitize_span, inline_span) {
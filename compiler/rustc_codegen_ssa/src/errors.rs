@@ -462,6 +462,85 @@ pub struct UnableToRun<'a> {
 #[diag(codegen_ssa_linker_file_stem)]
 pub struct LinkerFileStem;
 
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_linker_map_unreadable)]
+pub struct LinkerMapUnreadable<'a> {
+    pub path: &'a Path,
+    pub error: Error,
+}
+
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_linker_map_unrecognized)]
+pub struct LinkerMapUnrecognized<'a> {
+    pub path: &'a Path,
+}
+
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_linker_map_unsupported)]
+pub struct LinkerMapUnsupported;
+
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_export_list_executable)]
+pub struct ExportListExecutable;
+
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_export_list_malformed)]
+pub struct ExportListMalformed<'a> {
+    pub path: &'a Path,
+    pub error: String,
+}
+
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_export_list_unknown_version)]
+pub struct ExportListUnknownVersion<'a> {
+    pub path: &'a Path,
+    pub version: &'a str,
+}
+
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_export_list_unknown_symbol)]
+#[note]
+pub struct ExportListUnknownSymbol<'a> {
+    pub path: &'a Path,
+    pub name: &'a str,
+}
+
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_export_list_alias_conflict)]
+pub struct ExportListAliasConflict<'a> {
+    pub path: &'a Path,
+    pub name: &'a str,
+}
+
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_export_list_unknown_alias_target)]
+pub struct ExportListUnknownAliasTarget<'a> {
+    pub path: &'a Path,
+    pub name: &'a str,
+    pub target: &'a str,
+}
+
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_export_list_unsupported)]
+pub struct ExportListUnsupported;
+
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_export_list_aliases_unsupported)]
+pub struct ExportListAliasesUnsupported;
+
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_export_list_weak_alias_unsupported)]
+pub struct ExportListWeakAliasUnsupported<'a> {
+    pub name: &'a str,
+}
+
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_export_list_write_failure)]
+pub struct ExportListWriteFailure {
+    pub path: PathBuf,
+    pub error: Error,
+}
+
 #[derive(Diagnostic)]
 #[diag(codegen_ssa_static_library_native_artifacts)]
 pub struct StaticLibraryNativeArtifacts;
@@ -611,6 +690,15 @@ pub struct ShuffleIndicesEvaluation {
 #[diag(codegen_ssa_unknown_atomic_ordering)]
 pub struct UnknownAtomicOrdering;
 
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_unknown_codegen_backend)]
+#[help]
+pub struct UnknownCodegenBackend {
+    #[primary_span]
+    pub span: Span,
+    pub name: Symbol,
+}
+
 #[derive(Diagnostic)]
 #[diag(codegen_ssa_atomic_compare_exchange)]
 pub struct AtomicCompareExchange;
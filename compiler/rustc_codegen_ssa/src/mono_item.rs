@@ -29,6 +29,8 @@ fn define<Bx: BuilderMethods<'a, 'tcx>>(&self, cx: &'a Bx::CodegenCx) {
             cx.codegen_unit().name()
         );
 
+        let _item_timer = cx.tcx().sess.item_times.as_ref().map(|t| t.start_codegen(self.def_id()));
+
         match *self {
             MonoItem::Static(def_id) => {
                 cx.codegen_static(def_id);
@@ -101,7 +103,10 @@ fn define<Bx: BuilderMethods<'a, 'tcx>>(&self, cx: &'a Bx::CodegenCx) {
                 }
             }
             MonoItem::Fn(instance) => {
-                base::codegen_instance::<Bx>(cx, instance);
+                // Aliases share the code of their aliasee.
+                if !is_alias(cx, instance) {
+                    base::codegen_instance::<Bx>(cx, instance);
+                }
             }
         }
 
@@ -134,6 +139,10 @@ fn predefine<Bx: BuilderMethods<'a, 'tcx>>(
             MonoItem::Static(def_id) => {
                 cx.predefine_static(def_id, linkage, visibility, symbol_name);
             }
+            MonoItem::Fn(instance) if is_alias(cx, instance) => {
+                let aliasee = cx.codegen_unit().aliases()[&instance];
+                cx.predefine_alias(instance, aliasee, linkage, visibility, symbol_name);
+            }
             MonoItem::Fn(instance) => {
                 cx.predefine_fn(instance, linkage, visibility, symbol_name);
             }
@@ -158,3 +167,7 @@ fn to_raw_string(&self) -> String {
         }
     }
 }
+
+fn is_alias<'tcx>(cx: &impl CodegenMethods<'tcx>, instance: Instance<'tcx>) -> bool {
+    cx.supports_aliases() && cx.codegen_unit().aliases().contains_key(&instance)
+}
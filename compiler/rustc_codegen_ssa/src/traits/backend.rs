@@ -82,6 +82,29 @@ fn codegen_crate<'tcx>(
         need_metadata_module: bool,
     ) -> Box<dyn Any>;
 
+    /// Generates the codegen units that `#[codegen_backend]` assigns to this backend when it is
+    /// loaded as the secondary backend `name`, while the main backend generates the rest of the
+    /// crate with `codegen_crate`. The result is passed to `join_codegen`, and the main backend
+    /// links the resulting modules with its own ones.
+    ///
+    /// Returns `None` if this backend cannot be used as a secondary backend.
+    fn codegen_secondary_units<'tcx>(
+        &self,
+        _tcx: TyCtxt<'tcx>,
+        _name: Symbol,
+    ) -> Option<Box<dyn Any>> {
+        None
+    }
+
+    /// Returns `true` if the code generated by this backend runs the cleanups of the panics that
+    /// unwind through it and can catch them with `-C panic=unwind`. Secondary backends must
+    /// support unwinding unless the crate is compiled with `-C panic=abort`.
+    ///
+    /// Defaults to `true`.
+    fn supports_unwinding(&self) -> bool {
+        true
+    }
+
     /// This is called on the returned `Box<dyn Any>` from `codegen_backend`
     ///
     /// # Panics
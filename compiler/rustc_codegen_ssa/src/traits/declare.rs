@@ -19,4 +19,19 @@ fn predefine_fn(
         visibility: Visibility,
         symbol_name: &str,
     );
+
+    /// Whether the aliases of a codegen unit are emitted with `predefine_alias`. Otherwise, they
+    /// are emitted like the other functions.
+    fn supports_aliases(&self) -> bool;
+
+    /// Predefines `instance` as an alias of `aliasee`, which is predefined and defined in the
+    /// same codegen unit. Only called if `supports_aliases` returns `true`.
+    fn predefine_alias(
+        &self,
+        instance: Instance<'tcx>,
+        aliasee: Instance<'tcx>,
+        linkage: Linkage,
+        visibility: Visibility,
+        symbol_name: &str,
+    );
 }
@@ -867,6 +867,9 @@ fn print_crate_info(
                     }
                 }
             }
+            LintsJson => {
+                println_info!("{}", serde_json::to_string_pretty(&lints_json(sess)).unwrap());
+            }
             DeploymentTarget => {
                 use rustc_target::spec::current_apple_deployment_target;
 
@@ -1115,6 +1118,84 @@ fn sort_lint_groups(
     }
 }
 
+/// Describes all registered lints and lint groups for `--print=lints-json`, including those
+/// loaded by tools like Clippy.
+fn lints_json(sess: &Session) -> serde_json::Value {
+    use rustc_session::lint::FutureIncompatibilityReason;
+    use rustc_span::edition::ALL_EDITIONS;
+
+    let lint_store = unerased_lint_store(sess);
+    let mut groups: Vec<_> = lint_store.get_lint_groups().collect();
+    groups.sort_by_key(|&(name, ..)| name);
+    let mut lints: Vec<&Lint> = lint_store.get_lints().to_vec();
+    lints.sort_by_key(|lint| lint.name);
+
+    let lints = lints
+        .into_iter()
+        .map(|lint| {
+            let id = LintId::of(lint);
+            let lint_groups: Vec<_> = groups
+                .iter()
+                .filter(|(_, ids, _)| ids.contains(&id))
+                .map(|&(name, ..)| name)
+                .collect();
+            let default_levels: serde_json::Map<_, _> = ALL_EDITIONS
+                .iter()
+                .map(|&edition| (edition.to_string(), lint.default_level(edition).as_str().into()))
+                .collect();
+            let future_incompatible = lint.future_incompatible.map(|info| {
+                let reason = match info.reason {
+                    FutureIncompatibilityReason::FutureReleaseErrorDontReportInDeps => {
+                        "future-release-error"
+                    }
+                    FutureIncompatibilityReason::FutureReleaseErrorReportInDeps => {
+                        "future-release-error-report-in-deps"
+                    }
+                    FutureIncompatibilityReason::FutureReleaseSemanticsChange => {
+                        "future-release-semantics-change"
+                    }
+                    FutureIncompatibilityReason::EditionError(_) => "edition-error",
+                    FutureIncompatibilityReason::EditionSemanticsChange(_) => {
+                        "edition-semantics-change"
+                    }
+                    FutureIncompatibilityReason::Custom(_) => "custom",
+                };
+                serde_json::json!({
+                    "reason": reason,
+                    "edition": info.reason.edition().map(|edition| edition.to_string()),
+                    "reference": info.reference,
+                })
+            });
+            serde_json::json!({
+                "name": lint.name_lower(),
+                "description": lint.desc,
+                "groups": lint_groups,
+                "default_level": default_levels,
+                "future_incompatible": future_incompatible,
+                "feature_gate": lint.feature_gate.map(|feature| feature.to_string()),
+                "crate_level_only": lint.crate_level_only,
+                "report_in_external_macro": lint.report_in_external_macro,
+                "is_externally_loaded": lint.is_externally_loaded,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let groups = groups
+        .into_iter()
+        .map(|(name, ids, is_externally_loaded)| {
+            let mut lints: Vec<_> = ids.iter().map(|id| id.to_string()).collect();
+            lints.sort();
+            serde_json::json!({
+                "name": name,
+                "lints": lints,
+                "is_externally_loaded": is_externally_loaded,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    serde_json::json!({ "lints": lints, "groups": groups })
+}
+
 /// Show help for flag categories shared between rustdoc and rustc.
 ///
 /// Returns whether a help option was printed.
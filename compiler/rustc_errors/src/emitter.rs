@@ -200,6 +200,11 @@ fn should_show_explain(&self) -> bool {
         true
     }
 
+    /// Checks if should show the "aborting due to N previous errors" summary.
+    fn should_show_error_count(&self) -> bool {
+        true
+    }
+
     /// Checks if we can use colors in the current output stream.
     fn supports_color(&self) -> bool {
         false
@@ -0,0 +1,363 @@
+//! Built-in application of machine-applicable suggestions (`-Zapply-suggestions`).
+//!
+//! The `FixEmitter` wraps the emitter chosen by `--error-format` and records every
+//! `Applicability::MachineApplicable` suggestion it sees in a shared `CollectedFixes`. Once all
+//! diagnostics have been emitted, the session calls `CollectedFixes::apply`, which resolves the
+//! suggestions to byte ranges in the original source files, drops the ones that overlap an
+//! earlier suggestion, and either rewrites the files in place or prints a unified diff.
+//!
+//! This mirrors what `rustfix` does on top of the JSON output, but works for any driver and
+//! build system.
+
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::{fmt, fs};
+
+use rustc_data_structures::fx::FxIndexMap;
+use rustc_data_structures::sync::{Lock, Lrc};
+use rustc_lint_defs::Applicability;
+use rustc_span::source_map::SourceMap;
+use rustc_span::{FileName, SourceFile};
+
+use crate::emitter::{DynEmitter, Emitter};
+use crate::translation::Translate;
+use crate::{DiagInner, FluentBundle, SubstitutionPart};
+
+#[cfg(test)]
+mod tests;
+
+/// Number of unchanged lines shown around each change in the unified diff.
+const DIFF_CONTEXT: usize = 3;
+
+/// What `-Zapply-suggestions` does with the collected suggestions.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ApplySuggestions {
+    /// Rewrite the source files.
+    InPlace,
+    /// Print a unified diff to stdout and leave the source files untouched.
+    Diff,
+}
+
+/// A single replacement of the bytes `lo..hi` of the original source file.
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct Edit {
+    lo: usize,
+    hi: usize,
+    text: String,
+}
+
+impl Edit {
+    /// Whether applying both edits would be ambiguous. Two insertions at the same position
+    /// overlap, since there is no way to tell which one should come first.
+    fn overlaps(&self, other: &Edit) -> bool {
+        (self.lo < other.hi && other.lo < self.hi)
+            || (self.lo == other.lo && (self.lo == self.hi || other.lo == other.hi))
+    }
+}
+
+/// Why the suggestions for a file could not be applied.
+#[derive(Debug)]
+pub enum FixError {
+    /// The file no longer matches the contents the compiler read.
+    Modified(PathBuf),
+    Io(PathBuf, io::Error),
+}
+
+impl fmt::Display for FixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FixError::Modified(path) => {
+                write!(f, "`{}` was modified during compilation", path.display())
+            }
+            FixError::Io(path, err) => write!(f, "failed to update `{}`: {err}", path.display()),
+        }
+    }
+}
+
+/// The machine-applicable suggestions recorded by a `FixEmitter`.
+#[derive(Default)]
+pub struct CollectedFixes {
+    /// Only record suggestions of these lints or error codes, if set.
+    filter: Option<Vec<String>>,
+    /// Each entry is one suggestion whose parts must be applied together.
+    fixes: Vec<Vec<SubstitutionPart>>,
+}
+
+impl CollectedFixes {
+    pub fn new(filter: Option<Vec<String>>) -> CollectedFixes {
+        CollectedFixes { filter, fixes: Vec::new() }
+    }
+
+    fn collect(&mut self, diag: &DiagInner) {
+        if let Some(filter) = &self.filter {
+            let code = diag.code.map(|code| code.to_string());
+            let lint = diag.is_lint.as_ref().map(|lint| &lint.name);
+            if !filter.iter().any(|f| code.as_ref() == Some(f) || lint == Some(f)) {
+                return;
+            }
+        }
+
+        for suggestion in diag.suggestions.iter().flatten() {
+            // Suggestions with several alternatives need a human to pick one.
+            if suggestion.applicability == Applicability::MachineApplicable
+                && let [substitution] = &suggestion.substitutions[..]
+            {
+                self.fixes.push(substitution.parts.clone());
+            }
+        }
+    }
+
+    /// Applies the collected suggestions. Returns one error per file that could not be updated;
+    /// the other files are still processed.
+    pub fn apply(&self, sm: &SourceMap, mode: ApplySuggestions) -> Vec<FixError> {
+        let mut errors = vec![];
+        let mut stdout = io::stdout().lock();
+        for (path, (file, edits)) in self.resolve(sm) {
+            let result = match fs::read_to_string(&path) {
+                Ok(src) if !file.src_hash.matches(&src) => Err(FixError::Modified(path.clone())),
+                Ok(src) => match mode {
+                    ApplySuggestions::InPlace => fs::write(&path, apply_edits(&src, &edits))
+                        .map_err(|err| FixError::Io(path.clone(), err)),
+                    ApplySuggestions::Diff => stdout
+                        .write_all(unified_diff(&path, &src, &edits).as_bytes())
+                        .map_err(|err| FixError::Io(path.clone(), err)),
+                },
+                Err(err) => Err(FixError::Io(path.clone(), err)),
+            };
+            if let Err(err) = result {
+                errors.push(err);
+            }
+        }
+        errors
+    }
+
+    /// Resolves the suggestions to edits of the original source files, sorted by position.
+    /// Suggestions that overlap an earlier one, or that don't point into a local source file of
+    /// the current crate, are dropped entirely.
+    fn resolve(&self, sm: &SourceMap) -> FxIndexMap<PathBuf, (Lrc<SourceFile>, Vec<Edit>)> {
+        let mut files: FxIndexMap<PathBuf, (Lrc<SourceFile>, Vec<Edit>)> = FxIndexMap::default();
+        'fixes: for parts in &self.fixes {
+            let Some(first) = parts.first() else { continue };
+            if first.span.from_expansion() {
+                continue;
+            }
+            let file = sm.lookup_source_file(first.span.lo());
+            let FileName::Real(name) = &file.name else { continue };
+            let Some(path) = name.local_path() else { continue };
+            if file.is_imported() {
+                continue;
+            }
+
+            let mut edits = Vec::with_capacity(parts.len());
+            for part in parts {
+                if part.span.from_expansion()
+                    || !file.contains(part.span.lo())
+                    || !file.contains(part.span.hi())
+                {
+                    continue 'fixes;
+                }
+                edits.push(Edit {
+                    lo: file.original_relative_byte_pos(part.span.lo()).0 as usize,
+                    hi: file.original_relative_byte_pos(part.span.hi()).0 as usize,
+                    text: part.snippet.clone(),
+                });
+            }
+
+            let (_, accepted) =
+                files.entry(path.to_owned()).or_insert_with(|| (file.clone(), Vec::new()));
+            // The same suggestion is often emitted more than once, e.g. for each expansion of a
+            // macro; only apply it once.
+            if edits.iter().all(|edit| accepted.contains(edit)) {
+                continue;
+            }
+            if edits.iter().any(|edit| accepted.iter().any(|a| a.overlaps(edit))) {
+                continue;
+            }
+            accepted.extend(edits);
+        }
+        for (_, edits) in files.values_mut() {
+            edits.sort_by_key(|edit| (edit.lo, edit.hi));
+        }
+        files
+    }
+}
+
+/// Applies the sorted, non-overlapping `edits` to `src`.
+fn apply_edits(src: &str, edits: &[Edit]) -> String {
+    let mut out = String::with_capacity(src.len());
+    let mut pos = 0;
+    for edit in edits {
+        out.push_str(&src[pos..edit.lo]);
+        out.push_str(&edit.text);
+        pos = edit.hi;
+    }
+    out.push_str(&src[pos..]);
+    out
+}
+
+/// Renders the sorted, non-overlapping `edits` to `src` as a unified diff of `path`.
+fn unified_diff(path: &Path, src: &str, edits: &[Edit]) -> String {
+    // Byte offset of the start of each line. A trailing newline (or an empty file) starts an
+    // extra, empty line so that insertions at the very end have a line to attach to.
+    let mut line_starts = vec![0];
+    line_starts.extend(src.match_indices('\n').map(|(i, _)| i + 1));
+    let line_of = |pos: usize| line_starts.partition_point(|&start| start <= pos) - 1;
+    let line_start = |line: usize| line_starts[line];
+    let line_end = |line: usize| line_starts.get(line + 1).copied().unwrap_or(src.len());
+
+    // Group the edits that touch the same lines, as `(first_line, last_line, edits)`.
+    let mut changes: Vec<(usize, usize, Vec<&Edit>)> = vec![];
+    for edit in edits {
+        let first = line_of(edit.lo);
+        let last = if edit.hi > edit.lo { line_of(edit.hi - 1) } else { first };
+        match changes.last_mut() {
+            Some((_, prev_last, group)) if first <= *prev_last => {
+                *prev_last = (*prev_last).max(last);
+                group.push(edit);
+            }
+            _ => changes.push((first, last, vec![edit])),
+        }
+    }
+
+    // Group the changes that are close enough to share context lines into hunks.
+    let mut hunks: Vec<Vec<(usize, usize, Vec<&Edit>)>> = vec![];
+    for change in changes {
+        match hunks.last_mut() {
+            Some(hunk) if change.0 <= hunk.last().unwrap().1 + 2 * DIFF_CONTEXT + 1 => {
+                hunk.push(change)
+            }
+            _ => hunks.push(vec![change]),
+        }
+    }
+
+    let last_line = line_starts.len() - 1;
+    let mut out = format!("--- a/{0}\n+++ b/{0}\n", path.display());
+    // How many lines the new file has gained so far, to compute the new line numbers.
+    let mut line_delta = 0isize;
+    for hunk in hunks {
+        let hunk_first = hunk.first().unwrap().0.saturating_sub(DIFF_CONTEXT);
+        let hunk_last = (hunk.last().unwrap().1 + DIFF_CONTEXT).min(last_line);
+        let mut body = String::new();
+        let (mut old_count, mut new_count) = (0, 0);
+        let context = |body: &mut String, from: usize, to: usize, count: &mut usize| {
+            for line in from..to {
+                if push_diff_line(body, ' ', &src[line_start(line)..line_end(line)]) {
+                    *count += 1;
+                }
+            }
+        };
+
+        let mut next = hunk_first;
+        for (first, last, group) in &hunk {
+            let mut shared = 0;
+            context(&mut body, next, *first, &mut shared);
+            old_count += shared;
+            new_count += shared;
+
+            let old = &src[line_start(*first)..line_end(*last)];
+            let edits: Vec<Edit> = group
+                .iter()
+                .map(|edit| Edit {
+                    lo: edit.lo - line_start(*first),
+                    hi: edit.hi - line_start(*first),
+                    text: edit.text.clone(),
+                })
+                .collect();
+            let new = apply_edits(old, &edits);
+            for line in old.split_inclusive('\n') {
+                push_diff_line(&mut body, '-', line);
+                old_count += 1;
+            }
+            for line in new.split_inclusive('\n') {
+                push_diff_line(&mut body, '+', line);
+                new_count += 1;
+            }
+            next = last + 1;
+        }
+        let mut shared = 0;
+        context(&mut body, next, hunk_last + 1, &mut shared);
+        old_count += shared;
+        new_count += shared;
+
+        // An empty range is numbered after the line it follows.
+        let old_start = if old_count == 0 { hunk_first } else { hunk_first + 1 };
+        let new_first = (hunk_first as isize + line_delta) as usize;
+        let new_start = if new_count == 0 { new_first } else { new_first + 1 };
+        out.push_str(&format!("@@ -{old_start},{old_count} +{new_start},{new_count} @@\n"));
+        out.push_str(&body);
+        line_delta += new_count as isize - old_count as isize;
+    }
+    out
+}
+
+/// Appends `line` to the diff, returning `false` for the empty line after a trailing newline,
+/// which doesn't exist in the file.
+fn push_diff_line(out: &mut String, marker: char, line: &str) -> bool {
+    if line.is_empty() {
+        return false;
+    }
+    out.push(marker);
+    out.push_str(line);
+    if !line.ends_with('\n') {
+        out.push_str("\n\\ No newline at end of file\n");
+    }
+    true
+}
+
+/// An emitter that records machine-applicable suggestions before handing the diagnostic on to
+/// the wrapped emitter.
+pub struct FixEmitter {
+    inner: Box<DynEmitter>,
+    fixes: Lrc<Lock<CollectedFixes>>,
+}
+
+impl FixEmitter {
+    pub fn new(inner: Box<DynEmitter>, fixes: Lrc<Lock<CollectedFixes>>) -> FixEmitter {
+        FixEmitter { inner, fixes }
+    }
+}
+
+impl Translate for FixEmitter {
+    fn fluent_bundle(&self) -> Option<&Lrc<FluentBundle>> {
+        self.inner.fluent_bundle()
+    }
+
+    fn fallback_fluent_bundle(&self) -> &FluentBundle {
+        self.inner.fallback_fluent_bundle()
+    }
+}
+
+impl Emitter for FixEmitter {
+    fn emit_diagnostic(&mut self, diag: DiagInner) {
+        self.fixes.lock().collect(&diag);
+        self.inner.emit_diagnostic(diag);
+    }
+
+    fn emit_artifact_notification(&mut self, path: &Path, artifact_type: &str) {
+        self.inner.emit_artifact_notification(path, artifact_type);
+    }
+
+    fn emit_future_breakage_report(&mut self, diags: Vec<DiagInner>) {
+        self.inner.emit_future_breakage_report(diags);
+    }
+
+    fn emit_unused_externs(&mut self, lint_level: rustc_lint_defs::Level, unused_externs: &[&str]) {
+        self.inner.emit_unused_externs(lint_level, unused_externs);
+    }
+
+    fn should_show_explain(&self) -> bool {
+        self.inner.should_show_explain()
+    }
+
+    fn should_show_error_count(&self) -> bool {
+        self.inner.should_show_error_count()
+    }
+
+    fn supports_color(&self) -> bool {
+        self.inner.supports_color()
+    }
+
+    fn source_map(&self) -> Option<&Lrc<SourceMap>> {
+        self.inner.source_map()
+    }
+}
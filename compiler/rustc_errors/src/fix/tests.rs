@@ -0,0 +1,85 @@
+use super::*;
+
+fn edit(lo: usize, hi: usize, text: &str) -> Edit {
+    Edit { lo, hi, text: text.to_owned() }
+}
+
+#[test]
+fn overlapping_edits() {
+    assert!(edit(0, 4, "a").overlaps(&edit(2, 6, "b")));
+    assert!(edit(2, 6, "a").overlaps(&edit(3, 3, "b")));
+    // Two insertions at the same position are ambiguous.
+    assert!(edit(3, 3, "a").overlaps(&edit(3, 3, "b")));
+    assert!(edit(3, 3, "a").overlaps(&edit(3, 5, "b")));
+    // Adjacent replacements are fine.
+    assert!(!edit(0, 3, "a").overlaps(&edit(3, 6, "b")));
+    assert!(!edit(0, 3, "a").overlaps(&edit(6, 6, "b")));
+}
+
+#[test]
+fn apply() {
+    let src = "let x = 1;\nlet y = 2;\n";
+    let edits = [edit(4, 5, "_x"), edit(15, 16, "_y"), edit(22, 22, "// end\n")];
+    assert_eq!(apply_edits(src, &edits), "let _x = 1;\nlet _y = 2;\n// end\n");
+}
+
+#[test]
+fn diff_single_line() {
+    let src = "fn main() {\n    let x = 1;\n}\n";
+    let diff = unified_diff(Path::new("src/main.rs"), src, &[edit(20, 21, "_x")]);
+    assert_eq!(
+        diff,
+        "--- a/src/main.rs\n\
+         +++ b/src/main.rs\n\
+         @@ -1,3 +1,3 @@\n \
+         fn main() {\n\
+         -    let x = 1;\n\
+         +    let _x = 1;\n \
+         }\n"
+    );
+}
+
+#[test]
+fn diff_separate_hunks() {
+    let src: String = (1..=20).map(|i| format!("line {i}\n")).collect();
+    let line2 = src.find("line 2\n").unwrap();
+    let line18 = src.find("line 18\n").unwrap();
+    let edits = [edit(line2, line2 + 7, ""), edit(line18, line18, "new\n")];
+    let diff = unified_diff(Path::new("a.rs"), &src, &edits);
+    assert_eq!(
+        diff,
+        "--- a/a.rs\n\
+         +++ b/a.rs\n\
+         @@ -1,5 +1,4 @@\n \
+         line 1\n\
+         -line 2\n \
+         line 3\n \
+         line 4\n \
+         line 5\n\
+         @@ -15,6 +14,7 @@\n \
+         line 15\n \
+         line 16\n \
+         line 17\n\
+         -line 18\n\
+         +new\n\
+         +line 18\n \
+         line 19\n \
+         line 20\n"
+    );
+}
+
+#[test]
+fn diff_no_trailing_newline() {
+    let diff = unified_diff(Path::new("a.rs"), "a\nb", &[edit(2, 3, "c")]);
+    assert_eq!(
+        diff,
+        "--- a/a.rs\n\
+         +++ b/a.rs\n\
+         @@ -1,2 +1,2 @@\n \
+         a\n\
+         -b\n\
+         \\ No newline at end of file\n\
+         +c\n\
+         \\ No newline at end of file\n"
+    );
+}
@@ -83,10 +83,12 @@
 mod diagnostic_impls;
 pub mod emitter;
 pub mod error;
+pub mod fix;
 pub mod json;
 mod lock;
 pub mod markdown;
 pub mod registry;
+pub mod sarif;
 mod snippet;
 mod styled_buffer;
 #[cfg(test)]
@@ -954,7 +956,7 @@ pub fn print_error_count(&self, registry: &Registry) {
         // `emit_stashed_diagnostics` by now.
         assert!(inner.stashed_diagnostics.is_empty());
 
-        if inner.treat_err_as_bug() {
+        if inner.treat_err_as_bug() || !inner.emitter.should_show_error_count() {
             return;
         }
 
@@ -0,0 +1,549 @@
+//! A SARIF emitter for errors.
+//!
+//! [SARIF](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html) (Static Analysis
+//! Results Interchange Format) is the format consumed by code-scanning dashboards. Unlike the
+//! JSON emitter, which writes one object per diagnostic, a SARIF log is a single document
+//! describing the whole run. Diagnostics are therefore buffered and the log is written once the
+//! emitter is dropped, i.e. when the `DiagCtxt` owning it goes away. Emitters can continue the
+//! log of another emitter (see [`SarifEmitter::with_log`]), in which case it is written once all
+//! of them are dropped.
+//!
+//! Error codes and lint names (including tool lints such as `clippy::...` and `rustdoc::...`)
+//! become `rules`, spans become physical locations and `CodeSuggestion`s become `fixes`.
+
+use std::error::Report;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use derive_setters::Setters;
+use rustc_data_structures::fx::FxIndexMap;
+use rustc_data_structures::sync::{IntoDynSyncSend, Lrc};
+use rustc_error_messages::FluentArgs;
+use rustc_lint_defs::Applicability;
+use rustc_span::source_map::SourceMap;
+use rustc_span::{FileName, Span};
+use serde::Serialize;
+
+use crate::diagnostic::IsLint;
+use crate::emitter::Emitter;
+use crate::registry::Registry;
+use crate::translation::{to_fluent_args, Translate};
+use crate::{
+    CodeSuggestion, DiagInner, FluentBundle, LazyFallbackBundle, Level, MultiSpan, SpanLabel,
+};
+
+#[cfg(test)]
+mod tests;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+#[derive(Setters)]
+pub struct SarifEmitter {
+    #[setters(skip)]
+    log: SharedSarifLog,
+    registry: Option<Registry>,
+    #[setters(skip)]
+    sm: Lrc<SourceMap>,
+    fluent_bundle: Option<Lrc<FluentBundle>>,
+    #[setters(skip)]
+    fallback_bundle: LazyFallbackBundle,
+}
+
+/// A SARIF log that several emitters can add their results to, e.g. the emitter of an
+/// `EarlyDiagCtxt` and the emitter of the session that replaces it, so that a run only ever
+/// produces one document. The log is written when the last emitter sharing it is dropped.
+#[derive(Clone)]
+pub struct SharedSarifLog(Arc<Mutex<LogState>>);
+
+struct LogState {
+    dst: IntoDynSyncSend<Box<dyn Write + Send>>,
+    /// The name reported as the analysis tool, e.g. `rustc` or `rustdoc`.
+    tool_name: String,
+    pretty: bool,
+    /// Don't write a log at all if no diagnostics were emitted. Used for the emitters of
+    /// short-lived contexts (e.g. `EarlyDiagCtxt`) so that a successful compilation only ever
+    /// produces the log of the session's main emitter.
+    omit_empty_log: bool,
+    rules: FxIndexMap<String, SarifRule>,
+    results: Vec<SarifResult>,
+    execution_successful: bool,
+}
+
+impl SharedSarifLog {
+    fn state(&self) -> MutexGuard<'_, LogState> {
+        // A panic while the log was locked can't leave it in an inconsistent state.
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl SarifEmitter {
+    pub fn new(
+        dst: Box<dyn Write + Send>,
+        sm: Lrc<SourceMap>,
+        fallback_bundle: LazyFallbackBundle,
+    ) -> SarifEmitter {
+        let log = SharedSarifLog(Arc::new(Mutex::new(LogState {
+            dst: IntoDynSyncSend(dst),
+            tool_name: "rustc".to_owned(),
+            pretty: false,
+            omit_empty_log: false,
+            rules: FxIndexMap::default(),
+            results: Vec::new(),
+            execution_successful: true,
+        })));
+        SarifEmitter::with_log(log, sm, fallback_bundle)
+    }
+
+    /// Creates an emitter that adds its results to `log`, which is written once this emitter and
+    /// the others sharing the log are dropped. The emitter always writes the log, even if no
+    /// diagnostics are emitted.
+    pub fn with_log(
+        log: SharedSarifLog,
+        sm: Lrc<SourceMap>,
+        fallback_bundle: LazyFallbackBundle,
+    ) -> SarifEmitter {
+        log.state().omit_empty_log = false;
+        SarifEmitter { log, registry: None, sm, fluent_bundle: None, fallback_bundle }
+    }
+
+    /// Returns the log this emitter adds its results to, see [`SarifEmitter::with_log`].
+    pub fn log(&self) -> SharedSarifLog {
+        self.log.clone()
+    }
+
+    /// Sets the name reported as the analysis tool, e.g. `rustc` or `rustdoc`.
+    pub fn tool_name(self, tool_name: impl Into<String>) -> Self {
+        self.log.state().tool_name = tool_name.into();
+        self
+    }
+
+    pub fn pretty(self, pretty: bool) -> Self {
+        self.log.state().pretty = pretty;
+        self
+    }
+
+    /// Don't write the log if no diagnostics were emitted, unless another emitter continues it.
+    pub fn omit_empty_log(self, omit_empty_log: bool) -> Self {
+        self.log.state().omit_empty_log = omit_empty_log;
+        self
+    }
+}
+
+impl LogState {
+    fn write_log(&mut self) -> io::Result<()> {
+        let log = SarifLog {
+            schema: SARIF_SCHEMA,
+            version: SARIF_VERSION,
+            runs: [SarifRun {
+                tool: SarifTool {
+                    driver: SarifDriver {
+                        name: &self.tool_name,
+                        information_uri: "https://www.rust-lang.org/",
+                        rules: self.rules.values().collect(),
+                    },
+                },
+                column_kind: "unicodeCodePoints",
+                invocations: [SarifInvocation { execution_successful: self.execution_successful }],
+                results: &self.results,
+            }],
+        };
+        if self.pretty {
+            serde_json::to_writer_pretty(&mut *self.dst, &log)?
+        } else {
+            serde_json::to_writer(&mut *self.dst, &log)?
+        };
+        self.dst.write_all(b"\n")?;
+        self.dst.flush()
+    }
+
+    /// Returns the index of the rule for `id` in `tool.driver.rules`, registering it on first use.
+    fn rule_index(
+        &mut self,
+        registry: Option<&Registry>,
+        diag: &DiagInner,
+    ) -> Option<(String, usize)> {
+        let (id, full_description, help_uri) = if let Some(code) = diag.code {
+            let explanation =
+                registry.and_then(|registry| registry.try_find_description(code).ok());
+            (
+                code.to_string(),
+                explanation.map(|e| SarifMessage { text: e.to_owned(), markdown: Some(e) }),
+                explanation.map(|_| format!("https://doc.rust-lang.org/error_codes/{code}.html")),
+            )
+        } else if let Some(IsLint { name, .. }) = &diag.is_lint {
+            (name.clone(), None, None)
+        } else {
+            return None;
+        };
+
+        let entry = self.rules.entry(id.clone());
+        let index = entry.index();
+        entry.or_insert_with(|| SarifRule { id: id.clone(), full_description, help_uri });
+        Some((id, index))
+    }
+}
+
+impl SarifEmitter {
+    fn region(&self, span: Span) -> SarifRegion {
+        let start = self.sm.lookup_char_pos(span.lo());
+        let end = self.sm.lookup_char_pos(span.hi());
+        let byte_start = start.file.original_relative_byte_pos(span.lo()).0;
+        let byte_end = start.file.original_relative_byte_pos(span.hi()).0;
+        SarifRegion {
+            start_line: start.line,
+            start_column: start.col.0 + 1,
+            end_line: end.line,
+            end_column: end.col.0 + 1,
+            byte_offset: byte_start,
+            byte_length: byte_end - byte_start,
+        }
+    }
+
+    fn artifact_location(&self, span: Span) -> SarifArtifactLocation {
+        let file = self.sm.lookup_source_file(span.lo());
+        SarifArtifactLocation { uri: file_name_to_uri(&self.sm, &file.name) }
+    }
+
+    fn location(&self, span: Span, message: Option<String>) -> SarifLocation {
+        SarifLocation {
+            physical_location: SarifPhysicalLocation {
+                artifact_location: self.artifact_location(span),
+                region: self.region(span),
+            },
+            message: message.map(|text| SarifMessage { text, markdown: None }),
+        }
+    }
+
+    fn translate_label(&self, label: &SpanLabel, args: &FluentArgs<'_>) -> Option<String> {
+        label.label.as_ref().map(|m| self.translate_message(m, args).unwrap().to_string())
+    }
+
+    /// Splits the labels of `msp` into the result's locations (primary spans) and related
+    /// locations (secondary spans).
+    fn locations(
+        &self,
+        msp: &MultiSpan,
+        args: &FluentArgs<'_>,
+    ) -> (Vec<SarifLocation>, Vec<SarifLocation>) {
+        let mut locations = vec![];
+        let mut related_locations = vec![];
+        for label in msp.span_labels() {
+            if label.span.is_dummy() {
+                continue;
+            }
+            let location = self.location(label.span, self.translate_label(&label, args));
+            if label.is_primary {
+                locations.push(location);
+            } else {
+                related_locations.push(location);
+            }
+        }
+        (locations, related_locations)
+    }
+
+    fn fixes(&self, suggestion: &CodeSuggestion, args: &FluentArgs<'_>) -> Vec<SarifFix> {
+        let description =
+            self.translate_message(&suggestion.msg, args).map_err(Report::new).unwrap();
+        suggestion
+            .substitutions
+            .iter()
+            .map(|substitution| {
+                // Group the replacements by file, keeping the order in which files first appear.
+                let mut changes: FxIndexMap<String, Vec<SarifReplacement>> = FxIndexMap::default();
+                for part in &substitution.parts {
+                    let location = self.artifact_location(part.span);
+                    changes.entry(location.uri).or_default().push(SarifReplacement {
+                        deleted_region: self.region(part.span),
+                        inserted_content: SarifArtifactContent { text: part.snippet.clone() },
+                    });
+                }
+                SarifFix {
+                    description: SarifMessage { text: description.to_string(), markdown: None },
+                    artifact_changes: changes
+                        .into_iter()
+                        .map(|(uri, replacements)| SarifArtifactChange {
+                            artifact_location: SarifArtifactLocation { uri },
+                            replacements,
+                        })
+                        .collect(),
+                    properties: SarifFixProperties { applicability: suggestion.applicability },
+                }
+            })
+            .collect()
+    }
+}
+
+impl Drop for LogState {
+    fn drop(&mut self) {
+        if self.omit_empty_log && self.results.is_empty() {
+            return;
+        }
+        // The `DiagCtxt` is going away, so there is nothing left to report the failure to.
+        if let Err(e) = self.write_log() {
+            let _ = writeln!(io::stderr(), "error: failed to write the SARIF log: {e}");
+        }
+    }
+}
+
+impl Translate for SarifEmitter {
+    fn fluent_bundle(&self) -> Option<&Lrc<FluentBundle>> {
+        self.fluent_bundle.as_ref()
+    }
+
+    fn fallback_fluent_bundle(&self) -> &FluentBundle {
+        &self.fallback_bundle
+    }
+}
+
+impl Emitter for SarifEmitter {
+    fn emit_diagnostic(&mut self, diag: DiagInner) {
+        // Failure notes (e.g. "For more information about this error, try ...") carry no
+        // information about the code being compiled.
+        if diag.level.is_failure_note() {
+            return;
+        }
+
+        let args = to_fluent_args(diag.args.iter());
+        let mut message = self.translate_messages(&diag.messages, &args).into_owned();
+        let (locations, mut related_locations) = self.locations(&diag.span, &args);
+
+        // Subdiagnostics with a span become related locations, the others are appended to the
+        // message the way they would be rendered after the main message.
+        for child in &diag.children {
+            let child_message = format!(
+                "{}: {}",
+                child.level.to_str(),
+                self.translate_messages(&child.messages, &args)
+            );
+            match child.span.primary_span() {
+                Some(span) if !span.is_dummy() => {
+                    related_locations.push(self.location(span, Some(child_message)))
+                }
+                _ => {
+                    message.push('\n');
+                    message.push_str(&child_message);
+                }
+            }
+        }
+
+        let fixes = diag
+            .suggestions
+            .iter()
+            .flatten()
+            .flat_map(|suggestion| self.fixes(suggestion, &args))
+            .collect();
+        let mut log = self.log.state();
+        if diag.is_error() {
+            log.execution_successful = false;
+        }
+        let rule = log.rule_index(self.registry.as_ref(), &diag);
+        log.results.push(SarifResult {
+            rule_id: rule.as_ref().map(|(id, _)| id.clone()),
+            rule_index: rule.map(|(_, index)| index),
+            level: sarif_level(diag.level),
+            message: SarifMessage { text: message, markdown: None },
+            locations,
+            related_locations,
+            fixes,
+        });
+    }
+
+    fn source_map(&self) -> Option<&Lrc<SourceMap>> {
+        Some(&self.sm)
+    }
+
+    fn should_show_explain(&self) -> bool {
+        false
+    }
+
+    fn should_show_error_count(&self) -> bool {
+        false
+    }
+}
+
+fn sarif_level(level: Level) -> &'static str {
+    match level {
+        Level::Bug | Level::DelayedBug | Level::Fatal | Level::Error => "error",
+        Level::ForceWarning(_) | Level::Warning => "warning",
+        Level::Note | Level::OnceNote | Level::Help | Level::OnceHelp | Level::FailureNote => {
+            "note"
+        }
+        // Only reachable through future breakage reports.
+        Level::Allow | Level::Expect(_) => "none",
+    }
+}
+
+/// Converts a file name to the URI reference SARIF expects in `artifactLocation.uri`.
+///
+/// Relative paths stay relative (consumers resolve them against the checkout), absolute paths
+/// become `file://` URIs. Characters outside of the unreserved set are percent-encoded.
+fn file_name_to_uri(sm: &SourceMap, name: &FileName) -> String {
+    let name = sm.filename_for_diagnostics(name).to_string();
+    let path = name.replace('\\', "/");
+    let mut uri = String::with_capacity(path.len());
+    if Path::new(&name).is_absolute() || path.starts_with("//") {
+        uri.push_str("file://");
+        if !path.starts_with('/') {
+            // Windows drive paths, `C:/...` becomes `file:///C:/...`.
+            uri.push('/');
+        }
+    }
+    for (i, byte) in path.bytes().enumerate() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                uri.push(byte as char)
+            }
+            // Keep the colon of a drive letter, everywhere else it would start a scheme.
+            b':' if i == 1 && uri.starts_with("file://") => uri.push(':'),
+            _ => uri.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    uri
+}
+
+// The following data types are provided just for serialisation.
+
+#[derive(Serialize)]
+struct SarifLog<'a> {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: [SarifRun<'a>; 1],
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRun<'a> {
+    tool: SarifTool<'a>,
+    /// rustc reports columns in characters, not in the UTF-16 code units SARIF defaults to.
+    column_kind: &'static str,
+    invocations: [SarifInvocation; 1],
+    results: &'a [SarifResult],
+}
+
+#[derive(Serialize)]
+struct SarifTool<'a> {
+    driver: SarifDriver<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifDriver<'a> {
+    name: &'a str,
+    information_uri: &'static str,
+    rules: Vec<&'a SarifRule>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifInvocation {
+    /// `false` if any error was emitted.
+    execution_successful: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRule {
+    /// The error code (e.g. "E0308") or the lint name (e.g. "unused_variables" or
+    /// "clippy::needless_return").
+    id: String,
+    /// The long-form explanation of an error code, as shown by `rustc --explain`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    full_description: Option<SarifMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    help_uri: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_index: Option<usize>,
+    /// "error", "warning", "note" or "none".
+    level: &'static str,
+    message: SarifMessage,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    locations: Vec<SarifLocation>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    related_locations: Vec<SarifLocation>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fixes: Vec<SarifFix>,
+}
+
+#[derive(Serialize)]
+struct SarifMessage {
+    text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    markdown: Option<&'static str>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifLocation {
+    physical_location: SarifPhysicalLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<SarifMessage>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifPhysicalLocation {
+    artifact_location: SarifArtifactLocation,
+    region: SarifRegion,
+}
+
+#[derive(Serialize)]
+struct SarifArtifactLocation {
+    uri: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRegion {
+    /// 1-based.
+    start_line: usize,
+    /// 1-based, character offset.
+    start_column: usize,
+    end_line: usize,
+    end_column: usize,
+    /// 0-based, in bytes from the start of the original file.
+    byte_offset: u32,
+    byte_length: u32,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifFix {
+    description: SarifMessage,
+    artifact_changes: Vec<SarifArtifactChange>,
+    properties: SarifFixProperties,
+}
+
+#[derive(Serialize)]
+struct SarifFixProperties {
+    /// Whether the fix can be applied mechanically, see `Applicability`.
+    applicability: Applicability,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifArtifactChange {
+    artifact_location: SarifArtifactLocation,
+    replacements: Vec<SarifReplacement>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifReplacement {
+    deleted_region: SarifRegion,
+    inserted_content: SarifArtifactContent,
+}
+
+#[derive(Serialize)]
+struct SarifArtifactContent {
+    text: String,
+}
@@ -0,0 +1,163 @@
+use std::str;
+use std::sync::{Arc, Mutex};
+
+use rustc_span::source_map::FilePathMapping;
+use rustc_span::BytePos;
+use serde_json::Value;
+
+use super::*;
+use crate::codes::E0308;
+use crate::DiagCtxt;
+
+struct Shared<T> {
+    data: Arc<Mutex<T>>,
+}
+
+impl<T: Write> Write for Shared<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.data.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.data.lock().unwrap().flush()
+    }
+}
+
+/// Runs `f` against a `DiagCtxt` backed by a `SarifEmitter` and returns the resulting log.
+fn with_sarif_log(code: &str, f: impl FnOnce(&DiagCtxt)) -> Value {
+    rustc_span::create_default_session_globals_then(|| {
+        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        sm.new_source_file(Path::new("src/test.rs").to_owned().into(), code.to_owned());
+        let fallback_bundle =
+            crate::fallback_fluent_bundle(vec![crate::DEFAULT_LOCALE_RESOURCE], false);
+
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let se = SarifEmitter::new(Box::new(Shared { data: output.clone() }), sm, fallback_bundle);
+
+        let dcx = DiagCtxt::new(Box::new(se));
+        f(&dcx);
+        // The log is only written once the emitter is dropped.
+        drop(dcx);
+
+        let bytes = output.lock().unwrap();
+        serde_json::from_str(str::from_utf8(&bytes).unwrap()).unwrap()
+    })
+}
+
+fn span(lo: u32, hi: u32) -> Span {
+    Span::with_root_ctxt(BytePos(lo), BytePos(hi))
+}
+
+#[test]
+fn empty_log() {
+    let log = with_sarif_log("", |_| {});
+    assert_eq!(log["version"], "2.1.0");
+    assert_eq!(log["runs"][0]["tool"]["driver"]["name"], "rustc");
+    assert_eq!(log["runs"][0]["results"], Value::Array(vec![]));
+    assert_eq!(log["runs"][0]["invocations"][0]["executionSuccessful"], true);
+}
+
+#[test]
+fn error_with_code() {
+    let log = with_sarif_log("\nmod foo;\nmod bar;\n", |dcx| {
+        dcx.handle().struct_span_err(span(5, 12), "foo").with_code(E0308).emit();
+    });
+    let run = &log["runs"][0];
+    assert_eq!(run["invocations"][0]["executionSuccessful"], false);
+    assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "E0308");
+
+    let result = &run["results"][0];
+    assert_eq!(result["ruleId"], "E0308");
+    assert_eq!(result["ruleIndex"], 0);
+    assert_eq!(result["level"], "error");
+    assert_eq!(result["message"]["text"], "foo");
+
+    let location = &result["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "src/test.rs");
+    assert_eq!(location["region"]["startLine"], 2);
+    assert_eq!(location["region"]["startColumn"], 5);
+    assert_eq!(location["region"]["endLine"], 3);
+    assert_eq!(location["region"]["endColumn"], 3);
+    assert_eq!(location["region"]["byteOffset"], 5);
+    assert_eq!(location["region"]["byteLength"], 7);
+}
+
+#[test]
+fn rules_are_shared() {
+    let log = with_sarif_log("fn main() {}", |dcx| {
+        dcx.handle().struct_span_err(span(0, 2), "first").with_code(E0308).emit();
+        dcx.handle().struct_span_err(span(3, 7), "second").with_code(E0308).emit();
+    });
+    let run = &log["runs"][0];
+    assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 1);
+    assert_eq!(run["results"][1]["ruleIndex"], 0);
+}
+
+#[test]
+fn suggestion_becomes_fix() {
+    let log = with_sarif_log("let x = 1;", |dcx| {
+        dcx.handle()
+            .struct_span_warn(span(4, 5), "unused variable")
+            .with_span_suggestion(span(4, 5), "prefix it", "_x", Applicability::MachineApplicable)
+            .emit();
+    });
+    let result = &log["runs"][0]["results"][0];
+    assert_eq!(result["level"], "warning");
+
+    let fix = &result["fixes"][0];
+    assert_eq!(fix["description"]["text"], "prefix it");
+    assert_eq!(fix["properties"]["applicability"], "MachineApplicable");
+    let change = &fix["artifactChanges"][0];
+    assert_eq!(change["artifactLocation"]["uri"], "src/test.rs");
+    assert_eq!(change["replacements"][0]["deletedRegion"]["byteOffset"], 4);
+    assert_eq!(change["replacements"][0]["deletedRegion"]["byteLength"], 1);
+    assert_eq!(change["replacements"][0]["insertedContent"]["text"], "_x");
+}
+
+#[test]
+fn uri_escaping() {
+    rustc_span::create_default_session_globals_then(|| {
+        let sm = SourceMap::new(FilePathMapping::empty());
+        let uri = |name: &str| file_name_to_uri(&sm, &Path::new(name).to_owned().into());
+        assert_eq!(uri("src/lib.rs"), "src/lib.rs");
+        assert_eq!(uri("src/my file.rs"), "src/my%20file.rs");
+        if cfg!(windows) {
+            assert_eq!(uri(r"C:\src\lib.rs"), "file:///C:/src/lib.rs");
+        } else {
+            assert_eq!(uri("/src/lib.rs"), "file:///src/lib.rs");
+        }
+    })
+}
+
+#[test]
+fn shared_log() {
+    rustc_span::create_default_session_globals_then(|| {
+        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        let fallback_bundle =
+            || crate::fallback_fluent_bundle(vec![crate::DEFAULT_LOCALE_RESOURCE], false);
+
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let early = SarifEmitter::new(
+            Box::new(Shared { data: output.clone() }),
+            sm.clone(),
+            fallback_bundle(),
+        )
+        .omit_empty_log(true);
+        let log = early.log();
+        let early_dcx = DiagCtxt::new(Box::new(early));
+        early_dcx.handle().warn("early");
+
+        // The emitter replacing the early one continues its log, which is only written once.
+        let dcx = DiagCtxt::new(Box::new(SarifEmitter::with_log(log, sm, fallback_bundle())));
+        drop(early_dcx);
+        assert!(output.lock().unwrap().is_empty());
+        dcx.handle().warn("late");
+        drop(dcx);
+
+        let bytes = output.lock().unwrap();
+        let log: Value = serde_json::from_str(str::from_utf8(&bytes).unwrap()).unwrap();
+        let results = &log["runs"][0]["results"];
+        assert_eq!(results[0]["message"]["text"], "early");
+        assert_eq!(results[1]["message"]["text"], "late");
+    })
+}
@@ -496,7 +496,9 @@ pub struct BuiltinAttribute {
     // Debugging
     ungated!(
         debugger_visualizer, Normal,
-        template!(List: r#"natvis_file = "...", gdb_script_file = "...""#),
+        template!(
+            List: r#"natvis_file = "...", gdb_script_file = "...", lldb_script_file = "...""#
+        ),
         DuplicatesOk, EncodeCrossCrate::No
     ),
     ungated!(collapse_debuginfo, Normal, template!(List: "no|external|yes"), ErrorFollowing,
@@ -537,6 +539,10 @@ pub struct BuiltinAttribute {
         optimize, Normal, template!(List: "size|speed"), ErrorPreceding,
         EncodeCrossCrate::No, optimize_attribute, experimental!(optimize)
     ),
+    gated!(
+        codegen_backend, Normal, template!(NameValueStr: "name"), ErrorPreceding,
+        EncodeCrossCrate::No, codegen_backend_attribute, experimental!(codegen_backend)
+    ),
 
     gated!(
         unsafe ffi_pure, Normal, template!(Word), WarnFollowing,
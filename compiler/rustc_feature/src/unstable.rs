@@ -417,6 +417,9 @@ pub fn internal(&self, feature: Symbol) -> bool {
     (unstable, const_trait_impl, "1.42.0", Some(67792)),
     /// Allows the `?` operator in const contexts.
     (unstable, const_try, "1.56.0", Some(74935)),
+    /// Allows function attribute `#[codegen_backend = "name"]`, to generate the code of that
+    /// function with another codegen backend than that of the rest of the crate.
+    (unstable, codegen_backend_attribute, "CURRENT_RUSTC_VERSION", None),
     /// Allows coroutines to be cloned.
     (unstable, coroutine_clone, "1.65.0", Some(95360)),
     /// Allows defining coroutines.
@@ -514,6 +517,8 @@ pub fn internal(&self, feature: Symbol) -> bool {
     /// Allows using `#[link(kind = "link-arg", name = "...")]`
     /// to pass custom arguments to the linker.
     (unstable, link_arg_attribute, "1.76.0", Some(99427)),
+    /// Allows embedding LLDB formatters with `#[debugger_visualizer(lldb_script_file = "...")]`.
+    (unstable, lldb_debugger_visualizer, "CURRENT_RUSTC_VERSION", None),
     /// Give access to additional metadata about declarative macro meta-variables.
     (unstable, macro_metavar_expr, "1.61.0", Some(83527)),
     /// Provides a way to concatenate identifiers using metavariable expressions.
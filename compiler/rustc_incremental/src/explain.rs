@@ -0,0 +1,173 @@
+//! Support for `-Z incremental-explain`, which explains why work from the
+//! previous session could not be reused.
+//!
+//! While trying to mark a node green, the dep-graph records the first
+//! dependency that turned out to be red. Following these records from a
+//! re-executed node gives the chain of changed inputs that caused the
+//! re-execution, e.g. `typeck(foo)` <- `opt_hir_owner_nodes(bar)` <-
+//! `hir_crate(())`. The deepest node of that chain that refers to an item
+//! is reported as the edit that caused it, using the item's span as its
+//! location. The spans are from the current session, so they point to the
+//! item as it is now, not to the exact place of the edit.
+//!
+//! A summary grouped by edit is printed to stderr, and every re-executed node
+//! with its full chain is written as JSON to
+//! `<dir>/<crate_name>.incremental-explain.json`.
+
+use std::fs;
+use std::path::Path;
+
+use rustc_data_structures::fx::{FxHashMap, FxIndexMap};
+use rustc_middle::dep_graph::{DepNode, DepNodeExt, RebuildCause};
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::TyCtxt;
+use rustc_session::config::SwitchWithOptPath;
+use rustc_span::def_id::LOCAL_CRATE;
+use rustc_span::Symbol;
+use serde::Serialize;
+
+use crate::errors;
+
+/// The number of re-executed queries listed for each edit in the summary.
+const QUERIES_PER_EDIT: usize = 10;
+
+#[derive(Serialize)]
+struct Rebuild {
+    node: String,
+    /// Whether the result of `node` changed after re-executing it.
+    changed: bool,
+    /// The name of the codegen unit, if `node` is a codegen unit.
+    codegen_unit: Option<String>,
+    /// The chain of red dependencies, starting with the direct dependency of `node`.
+    because: Vec<String>,
+    edit: Option<Edit>,
+}
+
+#[derive(Serialize, PartialEq, Eq, Hash)]
+struct Edit {
+    /// The node of the edited item that changed.
+    node: String,
+    item: String,
+    location: String,
+}
+
+/// Reports the nodes of the previous session that had to be re-executed. This runs queries to
+/// describe the nodes, so it must be called before the dep-graph is saved.
+pub fn explain_rebuilds(tcx: TyCtxt<'_>) {
+    let SwitchWithOptPath::Enabled(ref dir) = tcx.sess.opts.unstable_opts.incremental_explain
+    else {
+        return;
+    };
+    if tcx.sess.opts.incremental.is_none() {
+        return;
+    }
+
+    let causes = tcx.dep_graph.rebuild_causes();
+    let cause_of: FxHashMap<DepNode, DepNode> =
+        causes.iter().map(|cause| (cause.node, cause.dependency)).collect();
+
+    // Codegen units are not keyed by a `DefId`, so map their nodes back to their names. Only
+    // do so if codegen ran, so that the partitioning is already computed.
+    let mut cgu_names = FxHashMap::default();
+    if tcx.sess.opts.output_types.should_codegen() {
+        for cgu in tcx.collect_and_partition_mono_items(()).1 {
+            cgu_names.insert(cgu.codegen_dep_node(tcx), cgu.name());
+        }
+    }
+
+    let rebuilds: Vec<_> =
+        causes.iter().map(|cause| explain(tcx, cause, &cause_of, &cgu_names)).collect();
+
+    print_summary(&rebuilds);
+
+    let dir = dir.as_deref().unwrap_or(Path::new("."));
+    let path = dir.join(format!("{}.incremental-explain.json", tcx.crate_name(LOCAL_CRATE)));
+    let json = serde_json::to_string(&rebuilds).unwrap();
+    if let Err(err) = fs::create_dir_all(dir).and_then(|()| fs::write(&path, json)) {
+        tcx.dcx().emit_err(errors::WriteNew { name: "incremental explanation", path, err });
+    }
+}
+
+fn explain(
+    tcx: TyCtxt<'_>,
+    cause: &RebuildCause,
+    cause_of: &FxHashMap<DepNode, DepNode>,
+    cgu_names: &FxHashMap<DepNode, Symbol>,
+) -> Rebuild {
+    let mut chain = vec![cause.dependency];
+    while let Some(&dependency) = cause_of.get(chain.last().unwrap()) {
+        chain.push(dependency);
+    }
+
+    // Nodes from the previous session may refer to items that were removed since, in this or
+    // in an upstream crate, in which case `extract_def_id` returns `None`.
+    let edit = chain.iter().rev().find_map(|node| {
+        let def_id = node.extract_def_id(tcx)?;
+        let loc = tcx.sess.source_map().lookup_char_pos(tcx.def_span(def_id).lo());
+        Some(Edit {
+            node: describe(tcx, *node, cgu_names),
+            item: with_no_trimmed_paths!(tcx.def_path_str(def_id)),
+            location: format!("{}:{}:{}", loc.file.name.prefer_local(), loc.line, loc.col.0 + 1),
+        })
+    });
+
+    Rebuild {
+        node: describe(tcx, cause.node, cgu_names),
+        changed: cause.changed,
+        codegen_unit: cgu_names.get(&cause.node).map(|name| name.to_string()),
+        because: chain.iter().map(|&node| describe(tcx, node, cgu_names)).collect(),
+        edit,
+    }
+}
+
+fn describe(tcx: TyCtxt<'_>, node: DepNode, cgu_names: &FxHashMap<DepNode, Symbol>) -> String {
+    if let Some(def_id) = node.extract_def_id(tcx) {
+        format!("{:?}({})", node.kind, with_no_trimmed_paths!(tcx.def_path_str(def_id)))
+    } else if let Some(name) = cgu_names.get(&node) {
+        format!("{:?}({name})", node.kind)
+    } else {
+        format!("{node:?}")
+    }
+}
+
+fn print_summary(rebuilds: &[Rebuild]) {
+    let mut by_edit: FxIndexMap<Option<&Edit>, Vec<&Rebuild>> = FxIndexMap::default();
+    for rebuild in rebuilds {
+        by_edit.entry(rebuild.edit.as_ref()).or_default().push(rebuild);
+    }
+    by_edit.sort_by(|_, a, _, b| b.len().cmp(&a.len()));
+
+    let cgus = rebuilds.iter().filter(|r| r.codegen_unit.is_some()).count();
+    eprintln!(
+        "[incremental] {} nodes could not be reused, including {cgus} codegen units",
+        rebuilds.len()
+    );
+    for (edit, rebuilds) in by_edit {
+        match edit {
+            Some(Edit { node, location, .. }) => {
+                eprintln!("[incremental] `{node}` changed due to an edit at {location}")
+            }
+            None => eprintln!("[incremental] inputs without a source location changed"),
+        }
+        let changed = rebuilds.iter().filter(|r| r.changed).count();
+        eprintln!("[incremental]     {} nodes re-executed, {changed} changed", rebuilds.len());
+        let (cgus, queries): (Vec<_>, Vec<_>) =
+            rebuilds.iter().partition(|r| r.codegen_unit.is_some());
+        for rebuild in cgus {
+            eprintln!(
+                "[incremental]     codegen unit `{}` rebuilt because `{}` changed",
+                rebuild.codegen_unit.as_ref().unwrap(),
+                rebuild.because[0],
+            );
+        }
+        for rebuild in queries.iter().take(QUERIES_PER_EDIT) {
+            eprintln!(
+                "[incremental]     `{}` re-executed because `{}` changed",
+                rebuild.node, rebuild.because[0],
+            );
+        }
+        if queries.len() > QUERIES_PER_EDIT {
+            eprintln!("[incremental]     ... and {} more", queries.len() - QUERIES_PER_EDIT);
+        }
+    }
+}
@@ -11,8 +11,10 @@
 
 mod assert_dep_graph;
 mod errors;
+mod explain;
 mod persist;
 
+pub use explain::explain_rebuilds;
 pub use persist::{
     copy_cgu_workproduct_to_incr_comp_cache_dir, finalize_session_directory, in_incr_comp_dir,
     in_incr_comp_dir_sess, load_query_result_cache, save_dep_graph, save_work_product_index,
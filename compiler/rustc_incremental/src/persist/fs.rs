@@ -50,6 +50,17 @@
 //! any more and will delete those. It will also delete any finalized session
 //! directories for a given crate except for the most recent one.
 //!
+//! This only ever looks at the directory of the crate being compiled, so the
+//! session directories of crates that are not compiled any more (e.g. old
+//! versions of dependencies) stay around forever. With `-Z incremental-gc`,
+//! the compiler additionally gives the whole incremental compilation directory
+//! a size and/or age budget. After finalizing its session directory, it evicts
+//! the least recently used finalized session directories of all crates, along
+//! with the work products in them, until the budget is met. Since a finalized
+//! session directory is replaced by a new one whenever it is used, the
+//! timestamp in its name is the time it was last used. Evicting a directory
+//! follows the same locking protocol as the regular garbage collection.
+//!
 //! ## Synchronization
 //!
 //! There is some synchronization needed in order for the compiler to be able to
@@ -117,7 +128,7 @@
 use rustc_errors::ErrorGuaranteed;
 use rustc_fs_util::{link_or_copy, try_canonicalize, LinkOrCopy};
 use rustc_middle::bug;
-use rustc_session::config::CrateType;
+use rustc_session::config::{CrateType, IncrementalGcBudget};
 use rustc_session::output::{collect_crate_types, find_crate_name};
 use rustc_session::{Session, StableCrateId};
 use tracing::debug;
@@ -373,6 +384,10 @@ pub fn finalize_session_directory(sess: &Session, svh: Option<Svh>) {
     }
 
     let _ = garbage_collect_session_directories(sess);
+
+    if let Some(budget) = sess.opts.unstable_opts.incremental_gc {
+        let _ = evict_to_budget(sess, budget);
+    }
 }
 
 pub(crate) fn delete_all_session_dir_contents(sess: &Session) -> io::Result<()> {
@@ -847,6 +862,119 @@ pub(crate) fn garbage_collect_session_directories(sess: &Session) -> io::Result<
     Ok(())
 }
 
+/// Evicts the least recently used finalized session directories of all crates
+/// in the incremental compilation directory until it fits `budget`. The
+/// current session directory is never evicted.
+fn evict_to_budget(sess: &Session, budget: IncrementalGcBudget) -> io::Result<()> {
+    debug!("evict_to_budget() - begin");
+
+    // The current session directory is canonicalized, so canonicalize the
+    // incremental directory too to be able to recognize it.
+    let incr_dir = try_canonicalize(sess.opts.incremental.as_ref().unwrap())?;
+    let current_session_dir = sess.incr_comp_session_dir().clone();
+
+    let mut total_size = 0;
+    let mut candidates = Vec::new();
+    for crate_dir in incr_dir.read_dir()? {
+        let Ok(crate_dir) = crate_dir else {
+            // Ignore any errors
+            continue;
+        };
+        let Ok(session_dirs) = crate_dir.path().read_dir() else {
+            // This is not a crate directory, leave it alone
+            continue;
+        };
+
+        for session_dir in session_dirs {
+            let Ok(session_dir) = session_dir else {
+                continue;
+            };
+            let path = session_dir.path();
+            let Some(directory_name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            if !is_session_directory(directory_name) {
+                continue;
+            }
+
+            // Directories that are still being worked on count towards the
+            // budget, but are left to the regular garbage collection.
+            let size = dir_size(&path);
+            total_size += size;
+            if !is_finalized(directory_name) || path == current_session_dir {
+                continue;
+            }
+            let Ok(timestamp) = extract_timestamp_from_session_dir(directory_name) else {
+                debug!("found session-dir with malformed timestamp: {}", path.display());
+                continue;
+            };
+            candidates.push((timestamp, size, path));
+        }
+    }
+    debug!("evict_to_budget() - total size: {}", total_size);
+
+    // Evict the least recently used directories first.
+    candidates.sort();
+    let now = SystemTime::now();
+    for (timestamp, size, path) in candidates {
+        if !exceeds_budget(budget, timestamp, total_size, now) {
+            break;
+        }
+
+        let lock_file_path = lock_file_path(&path);
+        let Ok(lock) = flock::Lock::new(
+            &lock_file_path,
+            false, // don't wait
+            false, // don't create the lock-file
+            true,  // get an exclusive lock
+        ) else {
+            debug!("evict_to_budget() - not evicting `{}`, still in use", path.display());
+            continue;
+        };
+
+        debug!("evict_to_budget() - evicting `{}`", path.display());
+        if let Err(err) = safe_remove_dir_all(&path) {
+            sess.dcx().emit_warn(errors::FinalizedGcFailed { path: &path, err });
+        } else {
+            delete_session_dir_lock_file(sess, &lock_file_path);
+            total_size -= size;
+        }
+
+        // Let's make it explicit that the file lock is released at this point,
+        // or rather, that we held on to it until here
+        drop(lock);
+    }
+
+    Ok(())
+}
+
+/// Returns whether the least recently used session directory, last used at
+/// `last_used`, has to be evicted to meet the budget.
+fn exceeds_budget(
+    budget: IncrementalGcBudget,
+    last_used: SystemTime,
+    total_size: u64,
+    now: SystemTime,
+) -> bool {
+    budget.max_size.is_some_and(|max_size| total_size > max_size)
+        || budget
+            .max_age
+            .is_some_and(|max_age| now.duration_since(last_used).is_ok_and(|age| age > max_age))
+}
+
+/// Returns the total size of the files in a directory and its subdirectories.
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = path.read_dir() else { return 0 };
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| match entry.metadata() {
+            Ok(metadata) if metadata.is_dir() => dir_size(&entry.path()),
+            Ok(metadata) => metadata.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
 fn delete_old(sess: &Session, path: &Path) {
     debug!("garbage_collect_session_directories() - deleting `{}`", path.display());
 
@@ -75,3 +75,29 @@ fn test_find_source_directory_in_iter() {
         None
     );
 }
+
+#[test]
+fn test_exceeds_budget() {
+    let now = UNIX_EPOCH + Duration::from_secs(100_000);
+    let day_ago = now - Duration::from_secs(24 * 60 * 60);
+    let week_ago = now - Duration::from_secs(7 * 24 * 60 * 60);
+
+    let unlimited = IncrementalGcBudget::default();
+    assert!(!exceeds_budget(unlimited, week_ago, u64::MAX, now));
+
+    let size = IncrementalGcBudget { max_size: Some(1000), max_age: None };
+    assert!(exceeds_budget(size, now, 1001, now));
+    assert!(!exceeds_budget(size, week_ago, 1000, now));
+
+    let age = IncrementalGcBudget { max_size: None, max_age: Some(Duration::from_secs(2 * 86400)) };
+    assert!(exceeds_budget(age, week_ago, 0, now));
+    assert!(!exceeds_budget(age, day_ago, u64::MAX, now));
+    // Directories from the future (e.g. because of clock skew) are not too old.
+    assert!(!exceeds_budget(age, now + Duration::from_secs(60), 0, now));
+
+    let both =
+        IncrementalGcBudget { max_size: Some(1000), max_age: Some(Duration::from_secs(2 * 86400)) };
+    assert!(exceeds_budget(both, day_ago, 1001, now));
+    assert!(exceeds_budget(both, week_ago, 0, now));
+    assert!(!exceeds_budget(both, day_ago, 1000, now));
+}
@@ -179,5 +179,6 @@ pub(crate) fn build_dep_graph(
         encoder,
         sess.opts.unstable_opts.query_dep_graph,
         sess.opts.unstable_opts.incremental_info,
+        sess.opts.unstable_opts.incremental_explain.enabled(),
     ))
 }
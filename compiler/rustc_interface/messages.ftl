@@ -1,6 +1,9 @@
 interface_cant_emit_mir =
     could not emit MIR: {$error}
 
+interface_duplicate_codegen_backend =
+    codegen backend `{$name}` is loaded more than once
+
 interface_emoji_identifier =
     identifiers cannot contain emoji: `{$ident}`
 
@@ -46,5 +49,12 @@ interface_rustc_error_fatal =
 interface_rustc_error_unexpected_annotation =
     unexpected annotation used with `#[rustc_error(...)]`!
 
+interface_secondary_codegen_backend_unwinding =
+    codegen backend `{$name}` does not support unwinding, so it cannot be used as a secondary codegen backend with `-C panic=unwind`
+    .help = compile this crate with `-C panic=abort`
+
 interface_temps_dir_error =
     failed to find or create the directory specified by `--temps-dir`
+
+interface_unsupported_secondary_codegen_backend =
+    codegen backend `{$name}` cannot be used as a secondary codegen backend
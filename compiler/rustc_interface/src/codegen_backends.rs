@@ -0,0 +1,165 @@
+//! Support for `-Z secondary-codegen-backends`, which loads codegen backends that generate the
+//! functions marked `#[codegen_backend = "name"]`, while the main codegen backend generates the
+//! rest of the crate and links it.
+
+use std::any::Any;
+
+use rustc_codegen_ssa::traits::CodegenBackend;
+use rustc_codegen_ssa::CodegenResults;
+use rustc_data_structures::fx::FxIndexMap;
+use rustc_errors::ErrorGuaranteed;
+use rustc_metadata::creader::MetadataLoaderDyn;
+use rustc_metadata::EncodedMetadata;
+use rustc_middle::dep_graph::{WorkProduct, WorkProductId};
+use rustc_middle::ty::TyCtxt;
+use rustc_middle::util::Providers;
+use rustc_session::config::{OutputFilenames, PrintRequest};
+use rustc_session::Session;
+use rustc_span::Symbol;
+use rustc_target::spec::PanicStrategy;
+
+use crate::errors;
+
+/// A codegen backend that generates the codegen units of the functions marked
+/// `#[codegen_backend]` with the secondary codegen backends, and everything else with the main
+/// codegen backend.
+pub(crate) struct CodegenBackends {
+    main: Box<dyn CodegenBackend>,
+    secondary: Vec<(Symbol, Box<dyn CodegenBackend>)>,
+}
+
+struct OngoingCodegen {
+    main: Box<dyn Any>,
+    /// The ongoing codegen of the secondary backends that have codegen units, with their index in
+    /// `CodegenBackends::secondary`.
+    secondary: Vec<(usize, Box<dyn Any>)>,
+}
+
+impl CodegenBackends {
+    pub(crate) fn new(
+        sess: &Session,
+        main: Box<dyn CodegenBackend>,
+        secondary: Vec<(String, Box<dyn CodegenBackend>)>,
+    ) -> CodegenBackends {
+        let main_name = sess.codegen_backend_name();
+        for (i, (name, _)) in secondary.iter().enumerate() {
+            if name == main_name || secondary[..i].iter().any(|(other, _)| other == name) {
+                sess.dcx().emit_fatal(errors::DuplicateCodegenBackend { name });
+            }
+        }
+        let secondary =
+            secondary.into_iter().map(|(name, backend)| (Symbol::intern(&name), backend)).collect();
+        CodegenBackends { main, secondary }
+    }
+}
+
+impl CodegenBackend for CodegenBackends {
+    fn locale_resource(&self) -> &'static str {
+        self.main.locale_resource()
+    }
+
+    fn init(&self, sess: &Session) {
+        self.main.init(sess);
+        for (_, backend) in &self.secondary {
+            backend.init(sess);
+        }
+    }
+
+    fn print(&self, req: &PrintRequest, out: &mut String, sess: &Session) {
+        self.main.print(req, out, sess)
+    }
+
+    fn target_features(&self, sess: &Session, allow_unstable: bool) -> Vec<Symbol> {
+        self.main.target_features(sess, allow_unstable)
+    }
+
+    fn print_passes(&self) {
+        self.main.print_passes()
+    }
+
+    fn print_version(&self) {
+        self.main.print_version()
+    }
+
+    fn metadata_loader(&self) -> Box<MetadataLoaderDyn> {
+        self.main.metadata_loader()
+    }
+
+    fn provide(&self, providers: &mut Providers) {
+        // The queries of the main backend take precedence.
+        for (_, backend) in &self.secondary {
+            backend.provide(providers);
+        }
+        self.main.provide(providers);
+    }
+
+    fn codegen_crate<'tcx>(
+        &self,
+        tcx: TyCtxt<'tcx>,
+        metadata: EncodedMetadata,
+        need_metadata_module: bool,
+    ) -> Box<dyn Any> {
+        let main = self.main.codegen_crate(tcx, metadata, need_metadata_module);
+
+        let mut secondary = vec![];
+        if tcx.sess.opts.output_types.should_codegen() && !tcx.sess.opts.unstable_opts.no_codegen {
+            let codegen_units = tcx.collect_and_partition_mono_items(()).1;
+            for (index, &(name, ref backend)) in self.secondary.iter().enumerate() {
+                if !codegen_units.iter().any(|cgu| cgu.codegen_backend() == Some(name)) {
+                    continue;
+                }
+                // The main backend expects the functions of other backends to run the cleanups of
+                // the panics that unwind through them.
+                if tcx.sess.panic_strategy() == PanicStrategy::Unwind
+                    && !backend.supports_unwinding()
+                {
+                    tcx.dcx().emit_err(errors::SecondaryCodegenBackendUnwinding { name });
+                    continue;
+                }
+                match backend.codegen_secondary_units(tcx, name) {
+                    Some(ongoing_codegen) => secondary.push((index, ongoing_codegen)),
+                    None => {
+                        tcx.dcx().emit_err(errors::UnsupportedSecondaryCodegenBackend { name });
+                    }
+                }
+            }
+        }
+
+        Box::new(OngoingCodegen { main, secondary })
+    }
+
+    fn join_codegen(
+        &self,
+        ongoing_codegen: Box<dyn Any>,
+        sess: &Session,
+        outputs: &OutputFilenames,
+    ) -> (CodegenResults, FxIndexMap<WorkProductId, WorkProduct>) {
+        let ongoing_codegen = ongoing_codegen
+            .downcast::<OngoingCodegen>()
+            .expect("Expected CodegenBackends's OngoingCodegen, found Box<Any>");
+
+        let (mut codegen_results, mut work_products) =
+            self.main.join_codegen(ongoing_codegen.main, sess, outputs);
+        for (index, ongoing_codegen) in ongoing_codegen.secondary {
+            let (secondary_results, secondary_work_products) =
+                self.secondary[index].1.join_codegen(ongoing_codegen, sess, outputs);
+            codegen_results.modules.extend(secondary_results.modules);
+            work_products.extend(secondary_work_products);
+        }
+        (codegen_results, work_products)
+    }
+
+    fn link(
+        &self,
+        sess: &Session,
+        codegen_results: CodegenResults,
+        outputs: &OutputFilenames,
+    ) -> Result<(), ErrorGuaranteed> {
+        self.main.link(sess, codegen_results, outputs)
+    }
+
+    fn supports_parallel(&self) -> bool {
+        self.main.supports_parallel()
+            && self.secondary.iter().all(|(_, backend)| backend.supports_parallel())
+    }
+}
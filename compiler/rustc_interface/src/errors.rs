@@ -103,3 +103,22 @@ pub struct FailedWritingFile<'a> {
 #[derive(Diagnostic)]
 #[diag(interface_multiple_output_types_to_stdout)]
 pub struct MultipleOutputTypesToStdout;
+
+#[derive(Diagnostic)]
+#[diag(interface_duplicate_codegen_backend)]
+pub struct DuplicateCodegenBackend<'a> {
+    pub name: &'a str,
+}
+
+#[derive(Diagnostic)]
+#[diag(interface_secondary_codegen_backend_unwinding)]
+#[help]
+pub struct SecondaryCodegenBackendUnwinding {
+    pub name: Symbol,
+}
+
+#[derive(Diagnostic)]
+#[diag(interface_unsupported_secondary_codegen_backend)]
+pub struct UnsupportedSecondaryCodegenBackend {
+    pub name: Symbol,
+}
@@ -27,6 +27,7 @@
 use rustc_span::FileName;
 use tracing::trace;
 
+use crate::codegen_backends::CodegenBackends;
 use crate::util;
 
 pub type Result<T> = result::Result<T, ErrorGuaranteed>;
@@ -398,7 +399,7 @@ pub fn run_compiler<R: Send>(config: Config, f: impl FnOnce(&Compiler) -> R + Se
             // impl `Send`. Creating a new one is fine.
             let early_dcx = EarlyDiagCtxt::new(config.opts.error_format);
 
-            let codegen_backend = match config.make_codegen_backend {
+            let mut codegen_backend = match config.make_codegen_backend {
                 None => util::get_codegen_backend(
                     &early_dcx,
                     &sysroot,
@@ -411,6 +412,11 @@ pub fn run_compiler<R: Send>(config: Config, f: impl FnOnce(&Compiler) -> R + Se
                     make_codegen_backend(&config.opts)
                 }
             };
+            let secondary_codegen_backends = util::get_secondary_codegen_backends(
+                &early_dcx,
+                &sysroot,
+                &config.opts.unstable_opts.secondary_codegen_backends,
+            );
 
             let temps_dir = config.opts.unstable_opts.temps_dir.as_deref().map(PathBuf::from);
 
@@ -427,6 +433,9 @@ pub fn run_compiler<R: Send>(config: Config, f: impl FnOnce(&Compiler) -> R + Se
 
             let mut locale_resources = Vec::from(config.locale_resources);
             locale_resources.push(codegen_backend.locale_resource());
+            for (_, backend) in &secondary_codegen_backends {
+                locale_resources.push(backend.locale_resource());
+            }
 
             let mut sess = rustc_session::build_session(
                 early_dcx,
@@ -449,6 +458,13 @@ pub fn run_compiler<R: Send>(config: Config, f: impl FnOnce(&Compiler) -> R + Se
                 config.expanded_args,
             );
 
+            if !secondary_codegen_backends.is_empty() {
+                codegen_backend = Box::new(CodegenBackends::new(
+                    &sess,
+                    codegen_backend,
+                    secondary_codegen_backends,
+                ));
+            }
             codegen_backend.init(&sess);
 
             let cfg = parse_cfg(sess.dcx(), config.crate_cfg);
@@ -478,6 +494,7 @@ pub fn run_compiler<R: Send>(config: Config, f: impl FnOnce(&Compiler) -> R + Se
                 register_lints(&sess, &mut lint_store);
                 sess.registered_lints = true;
             }
+            lint_store.load_lint_config(&sess);
             sess.lint_store = Some(Lrc::new(lint_store));
 
             let compiler = Compiler {
@@ -6,6 +6,7 @@
 // tidy-alphabetical-end
 
 mod callbacks;
+mod codegen_backends;
 mod errors;
 pub mod interface;
 pub mod passes;
@@ -11,6 +11,7 @@
 use rustc_hir::def_id::LOCAL_CRATE;
 use rustc_middle::arena::Arena;
 use rustc_middle::dep_graph::DepGraph;
+use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_middle::ty::{GlobalCtxt, TyCtxt};
 use rustc_serialize::opaque::FileEncodeResult;
 use rustc_session::config::{self, OutputFilenames, OutputType};
@@ -130,14 +131,15 @@ pub fn codegen_and_build_linker(
 
         // This must run after monomorphization so that all generic types
         // have been instantiated.
+        let format = tcx.sess.opts.unstable_opts.print_sizes_format;
         if tcx.sess.opts.unstable_opts.print_type_sizes {
-            tcx.sess.code_stats.print_type_sizes();
+            tcx.sess.code_stats.print_type_sizes(format);
         }
 
         if tcx.sess.opts.unstable_opts.print_vtable_sizes {
             let crate_name = tcx.crate_name(LOCAL_CRATE);
 
-            tcx.sess.code_stats.print_vtable_sizes(crate_name);
+            tcx.sess.code_stats.print_vtable_sizes(crate_name, format);
         }
 
         Ok(Linker {
@@ -213,6 +215,20 @@ pub fn enter<F, T>(&self, f: F) -> T
         // since that likely means there was a parse error.
         if let Some(Ok(gcx)) = &mut *queries.gcx.result.borrow_mut() {
             let gcx = gcx.get_mut();
+
+            gcx.enter(rustc_incremental::explain_rebuilds);
+
+            // This runs for `cargo check` too, and after codegen if there is any.
+            if let (Some(item_times), Some(limit)) =
+                (&self.sess.item_times, self.sess.opts.unstable_opts.item_time_report)
+            {
+                gcx.enter(|tcx| {
+                    item_times.print_report(limit, |def_id| {
+                        with_no_trimmed_paths!(tcx.def_path_str(def_id))
+                    })
+                });
+            }
+
             // We assume that no queries are run past here. If there are new queries
             // after this point, they'll show up as "<unknown>" in self-profiling data.
             {
@@ -1,7 +1,7 @@
 use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::OnceLock;
+use std::sync::{Mutex, OnceLock};
 use std::{env, iter, thread};
 
 use rustc_ast as ast;
@@ -250,10 +250,7 @@ pub fn get_codegen_backend(
     static LOAD: OnceLock<unsafe fn() -> Box<dyn CodegenBackend>> = OnceLock::new();
 
     let load = LOAD.get_or_init(|| {
-        let backend = backend_name
-            .or(target.default_codegen_backend.as_deref())
-            .or(option_env!("CFG_DEFAULT_CODEGEN_BACKEND"))
-            .unwrap_or("llvm");
+        let backend = rustc_session::codegen_backend_name(backend_name, target);
 
         match backend {
             filename if filename.contains('.') => {
@@ -271,6 +268,39 @@ pub fn get_codegen_backend(
     unsafe { load() }
 }
 
+/// Get the secondary codegen backends of `-Z secondary-codegen-backends`, which generate the code
+/// of the functions marked `#[codegen_backend = "name"]`, along with their names.
+pub fn get_secondary_codegen_backends(
+    early_dcx: &EarlyDiagCtxt,
+    sysroot: &Path,
+    backend_names: &[String],
+) -> Vec<(String, Box<dyn CodegenBackend>)> {
+    static LOADED: Mutex<Vec<(String, MakeBackendFn)>> = Mutex::new(Vec::new());
+
+    let mut loaded = LOADED.lock().unwrap();
+    backend_names
+        .iter()
+        .map(|backend_name| {
+            let load = match loaded.iter().find(|(name, _)| name == backend_name) {
+                Some(&(_, load)) => load,
+                None => {
+                    let load = match backend_name.as_str() {
+                        filename if filename.contains('.') => {
+                            load_backend_from_dylib(early_dcx, filename.as_ref())
+                        }
+                        #[cfg(feature = "llvm")]
+                        "llvm" => rustc_codegen_llvm::LlvmCodegenBackend::new,
+                        backend_name => find_codegen_backend(early_dcx, sysroot, backend_name),
+                    };
+                    loaded.push((backend_name.clone(), load));
+                    load
+                }
+            };
+            (backend_name.clone(), load())
+        })
+        .collect()
+}
+
 // This is used for rustdoc, but it uses similar machinery to codegen backend
 // loading, so we leave the code here. It is potentially useful for other tools
 // that want to invoke the rustc binary while linking to rustc as well.
@@ -293,7 +323,6 @@ fn get_rustc_path_inner(bin_path: &str) -> Option<PathBuf> {
     })
 }
 
-#[allow(rustc::untranslatable_diagnostic)] // FIXME: make this translatable
 fn get_codegen_sysroot(
     early_dcx: &EarlyDiagCtxt,
     sysroot: &Path,
@@ -310,6 +339,16 @@ fn get_codegen_sysroot(
         "cannot load the default codegen backend twice"
     );
 
+    find_codegen_backend(early_dcx, sysroot, backend_name)
+}
+
+/// Loads the codegen backend `backend_name` from the `codegen-backends` directory of the sysroot.
+#[allow(rustc::untranslatable_diagnostic)] // FIXME: make this translatable
+fn find_codegen_backend(
+    early_dcx: &EarlyDiagCtxt,
+    sysroot: &Path,
+    backend_name: &str,
+) -> MakeBackendFn {
     let target = host_triple();
     let sysroot_candidates = sysroot_candidates();
 
@@ -468,6 +468,11 @@ lint_invalid_reference_casting_note_ty_has_interior_mutability = even for types
 lint_legacy_derive_helpers = derive helper attribute is used before it is introduced
     .label = the attribute is introduced here
 
+lint_lint_config_invalid_level = invalid lint level `{$level}` in lint config
+    .note = expected one of `allow`, `warn`, `deny` or `forbid`
+
+lint_lint_config_malformed = malformed lint config: {$message}
+
 lint_lintpass_by_hand = implementing `LintPass` by hand
     .help = try using `declare_lint_pass!` or `impl_lint_pass!` instead
 
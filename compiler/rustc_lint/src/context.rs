@@ -43,6 +43,7 @@
 
 use self::TargetLint::*;
 use crate::levels::LintLevelsBuilder;
+use crate::lint_config::LintConfig;
 use crate::passes::{EarlyLintPassObject, LateLintPassObject};
 
 mod diagnostics;
@@ -76,6 +77,9 @@ pub struct LintStore {
 
     /// Map of registered lint groups to what lints they expand to.
     lint_groups: FxIndexMap<&'static str, LintGroup>,
+
+    /// Lint levels read from `-Z lint-config`.
+    lint_config: Option<LintConfig>,
 }
 
 impl LintStoreMarker for LintStore {}
@@ -150,9 +154,22 @@ pub fn new() -> LintStore {
             late_module_passes: vec![],
             by_name: Default::default(),
             lint_groups: Default::default(),
+            lint_config: None,
         }
     }
 
+    /// Reads the file passed with `-Z lint-config`, if any. Errors in the file are reported
+    /// immediately; the lint names are only checked once lint levels are computed, so this can
+    /// be called before all tool lints have been registered.
+    pub fn load_lint_config(&mut self, sess: &Session) {
+        self.lint_config =
+            sess.opts.lint_config.as_ref().and_then(|file| LintConfig::load(sess, file));
+    }
+
+    pub(crate) fn lint_config(&self) -> Option<&LintConfig> {
+        self.lint_config.as_ref()
+    }
+
     pub fn get_lints<'t>(&'t self) -> &'t [&'static Lint] {
         &self.lints
     }
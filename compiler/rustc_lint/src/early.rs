@@ -86,11 +86,22 @@ fn visit_param(&mut self, param: &'a ast::Param) {
     }
 
     fn visit_item(&mut self, it: &'a ast::Item) {
+        // Overrides from `-Z lint-config` for out-of-line modules sit between the parent's
+        // levels and the attributes on the module.
+        let config_push = match it.kind {
+            ast::ItemKind::Mod(_, ast::ModKind::Loaded(_, ast::Inline::No, spans)) => {
+                Some(self.context.builder.push_lint_config_overrides(spans.inner_span))
+            }
+            _ => None,
+        };
         self.with_lint_attrs(it.id, &it.attrs, |cx| {
             lint_callback!(cx, check_item, it);
             ast_visit::walk_item(cx, it);
             lint_callback!(cx, check_item_post, it);
-        })
+        });
+        if let Some(push) = config_push {
+            self.context.builder.pop(push);
+        }
     }
 
     fn visit_foreign_item(&mut self, it: &'a ast::ForeignItem) {
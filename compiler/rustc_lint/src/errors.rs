@@ -103,6 +103,23 @@ pub(crate) struct UnsupportedGroup {
     pub lint_group: String,
 }
 
+#[derive(Diagnostic)]
+#[diag(lint_lint_config_malformed)]
+pub(crate) struct LintConfigMalformed {
+    #[primary_span]
+    pub span: Span,
+    pub message: String,
+}
+
+#[derive(Diagnostic)]
+#[diag(lint_lint_config_invalid_level)]
+#[note]
+pub(crate) struct LintConfigInvalidLevel {
+    #[primary_span]
+    pub span: Span,
+    pub level: String,
+}
+
 #[derive(Diagnostic)]
 #[diag(lint_check_name_unknown_tool, code = E0602)]
 pub(crate) struct CheckNameUnknownTool<'a> {
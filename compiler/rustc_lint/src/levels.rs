@@ -32,11 +32,12 @@
 };
 use crate::fluent_generated as fluent;
 use crate::late::unerased_lint_store;
+use crate::lint_config::LintConfigEntry;
 use crate::lints::{
-    DeprecatedLintName, DeprecatedLintNameFromCommandLine, IgnoredUnlessCrateSpecified,
-    OverruledAttributeLint, RemovedLint, RemovedLintFromCommandLine, RenamedLint,
-    RenamedLintFromCommandLine, RenamedLintSuggestion, UnknownLint, UnknownLintFromCommandLine,
-    UnknownLintSuggestion,
+    DeprecatedLintName, DeprecatedLintNameFromCommandLine, DeprecatedLintNameFromLintConfig,
+    IgnoredUnlessCrateSpecified, OverruledAttributeLint, RemovedLint, RemovedLintFromCommandLine,
+    RenamedLint, RenamedLintFromCommandLine, RenamedLintSuggestion, UnknownLint,
+    UnknownLintFromCommandLine, UnknownLintSuggestion,
 };
 
 /// Collection of lint levels for the whole crate.
@@ -137,6 +138,18 @@ fn shallow_lint_levels_on(tcx: TyCtxt<'_>, owner: hir::OwnerId) -> ShallowLintLe
 
     if owner == hir::CRATE_OWNER_ID {
         levels.add_command_line();
+    } else if let hir::OwnerNode::Item(item) = tcx.hir_owner_node(owner)
+        && let hir::ItemKind::Mod(module) = item.kind
+        && !module.spans.inner_span.is_dummy()
+        && !tcx
+            .sess
+            .source_map()
+            .lookup_source_file(module.spans.inner_span.lo())
+            .contains(item.span.lo())
+    {
+        // Overrides from `-Z lint-config` for out-of-line modules sit between the parent's
+        // levels and the attributes on the module.
+        levels.add_lint_config_overrides(module.spans.inner_span);
     }
 
     match attrs.map.range(..) {
@@ -380,6 +393,25 @@ pub(crate) fn push(
         BuilderPush { prev }
     }
 
+    /// Pushes the `-Z lint-config` overrides for an out-of-line module whose contents are at
+    /// `inner_span`. This must be called before `push`ing the attributes on the module.
+    ///
+    /// Don't forget to call `pop`!
+    pub(crate) fn push_lint_config_overrides(&mut self, inner_span: Span) -> BuilderPush {
+        let prev = self.provider.cur;
+        self.provider.cur =
+            self.provider.sets.list.push(LintSet { specs: FxIndexMap::default(), parent: prev });
+
+        self.add_lint_config_overrides(inner_span);
+
+        if self.provider.current_specs().is_empty() {
+            self.provider.sets.list.pop();
+            self.provider.cur = prev;
+        }
+
+        BuilderPush { prev }
+    }
+
     /// Called after `push` when the scope of a set of attributes are exited.
     pub(crate) fn pop(&mut self, push: BuilderPush) {
         self.provider.cur = push.prev;
@@ -412,6 +444,11 @@ fn insert(&mut self, id: LintId, lvl: LevelAndSource) {
     }
 
     fn add_command_line(&mut self) {
+        let lint_config = self.store.lint_config();
+        if let Some(config) = lint_config {
+            self.add_lint_config_entries(&config.lints);
+        }
+
         for &(ref lint_name, level) in &self.sess.opts.lint_opts {
             // Checks the validity of lint names derived from the command line.
             let (tool_name, lint_name_only) = parse_lint_and_tool_name(lint_name);
@@ -481,6 +518,99 @@ fn add_command_line(&mut self) {
                 }
             }
         }
+
+        // Overrides are more specific than both the config's top-level levels and the
+        // command-line flags, as they are for out-of-line modules.
+        if let Some(config) = lint_config
+            && let Some(path) = self.sess.io.input.opt_path()
+        {
+            for o in config.overrides_for(path) {
+                self.add_lint_config_entries(&o.lints);
+            }
+        }
+    }
+
+    /// Applies the `-Z lint-config` overrides for the file containing `span`.
+    fn add_lint_config_overrides(&mut self, span: Span) {
+        let Some(config) = self.store.lint_config() else { return };
+        let FileName::Real(name) = self.sess.source_map().span_to_filename(span) else { return };
+        let Some(path) = name.local_path() else { return };
+        for o in config.overrides_for(path) {
+            self.add_lint_config_entries(&o.lints);
+        }
+    }
+
+    /// Applies levels from the `-Z lint-config` file. These are checked like lint attributes,
+    /// with diagnostics pointing into the config file.
+    fn add_lint_config_entries(&mut self, entries: &[LintConfigEntry]) {
+        for entry in entries {
+            let sp = entry.span;
+            let (tool_name, lint_name) = parse_lint_and_tool_name(&entry.name);
+            let ids = match self.store.check_lint_name(lint_name, tool_name, self.registered_tools)
+            {
+                CheckLintNameResult::Ok(ids) | CheckLintNameResult::Tool(ids, None) => ids,
+                CheckLintNameResult::Tool(ids, Some(ref replace)) => {
+                    if self.lint_added_lints {
+                        let name = entry.name.clone();
+                        let lint = DeprecatedLintNameFromLintConfig { name, replace };
+                        self.emit_span_lint(RENAMED_AND_REMOVED_LINTS, sp.into(), lint);
+                    }
+                    ids
+                }
+                CheckLintNameResult::MissingTool => continue,
+                CheckLintNameResult::NoTool => {
+                    if self.lint_added_lints {
+                        self.sess.dcx().emit_err(UnknownToolInScopedLint {
+                            span: Some(sp),
+                            tool_name: tool_name.unwrap(),
+                            lint_name: lint_name.to_string(),
+                            is_nightly_build: self.sess.is_nightly_build(),
+                        });
+                    }
+                    continue;
+                }
+                CheckLintNameResult::Renamed(ref replace) => {
+                    if self.lint_added_lints {
+                        let suggestion = RenamedLintSuggestion::WithoutSpan { replace };
+                        let lint = RenamedLint { name: &entry.name, suggestion };
+                        self.emit_span_lint(RENAMED_AND_REMOVED_LINTS, sp.into(), lint);
+                    }
+                    // As with attributes, apply the new lint instead of ignoring the entry.
+                    let CheckLintNameResult::Ok(ids) =
+                        self.store.check_lint_name(replace, None, self.registered_tools)
+                    else {
+                        panic!("renamed lint does not exist: {replace}");
+                    };
+                    ids
+                }
+                CheckLintNameResult::Removed(ref reason) => {
+                    if self.lint_added_lints {
+                        let lint = RemovedLint { name: &entry.name, reason };
+                        self.emit_span_lint(RENAMED_AND_REMOVED_LINTS, sp.into(), lint);
+                    }
+                    continue;
+                }
+                CheckLintNameResult::NoLint(suggestion) => {
+                    if self.lint_added_lints {
+                        let name = entry.name.clone();
+                        let suggestion = suggestion.map(|(replace, from_rustc)| {
+                            UnknownLintSuggestion::WithoutSpan { replace, from_rustc }
+                        });
+                        let lint = UnknownLint { name, suggestion };
+                        self.emit_span_lint(UNKNOWN_LINTS, sp.into(), lint);
+                    }
+                    continue;
+                }
+            };
+
+            let src =
+                LintLevelSource::Node { name: Symbol::intern(&entry.name), span: sp, reason: None };
+            for &id in ids {
+                if self.check_gated_lint(id, sp, false) {
+                    self.insert_spec(id, (entry.level, src));
+                }
+            }
+        }
     }
 
     /// Attempts to insert the `id` to `level_src` map entry. If unsuccessful
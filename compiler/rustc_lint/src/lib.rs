@@ -61,6 +61,7 @@
 mod late;
 mod let_underscore;
 mod levels;
+mod lint_config;
 mod lints;
 mod macro_expr_fragment_specifier_2024_migration;
 mod map_unit_fn;
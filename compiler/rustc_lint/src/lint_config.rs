@@ -0,0 +1,196 @@
+//! Support for `-Z lint-config`, which reads lint levels from a TOML file.
+//!
+//! The format follows Cargo's `[lints]` table, with an extra `[[overrides]]`
+//! array for changing levels in parts of the source tree:
+//!
+//! ```toml
+//! [lints.rust]
+//! unsafe_code = "forbid"
+//! unused = { level = "deny", priority = -1 }
+//!
+//! [lints.clippy]
+//! pedantic = "warn"
+//!
+//! [[overrides]]
+//! path = "src/legacy"
+//! lints.rust.dead_code = "allow"
+//! ```
+//!
+//! The top-level levels are applied at the crate root just before the `-A`/`-W`/`-D`/`-F` flags,
+//! so command-line flags and lint attributes take precedence over them. Overrides apply to the
+//! crate root and to out-of-line modules whose file lives under `path`, which is relative to the
+//! directory containing the config file. Like a lint attribute on the module, an override takes
+//! precedence over the levels of the enclosing module, including command-line flags. Within a
+//! table, entries are applied by ascending `priority` and then by name, as Cargo does.
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use rustc_session::config::LintConfigFile;
+use rustc_session::lint::Level;
+use rustc_session::Session;
+use rustc_span::{BytePos, FileName, SourceFile, Span};
+use serde::Deserialize;
+use toml::Spanned;
+
+use crate::errors::{LintConfigInvalidLevel, LintConfigMalformed};
+
+/// A parsed `-Z lint-config` file.
+#[derive(Debug)]
+pub(crate) struct LintConfig {
+    /// Levels applied to the whole crate.
+    pub lints: Vec<LintConfigEntry>,
+    pub overrides: Vec<LintConfigOverride>,
+}
+
+/// Levels applied to the files under a directory.
+#[derive(Debug)]
+pub(crate) struct LintConfigOverride {
+    /// The absolute path of the directory or file this override applies to.
+    pub path: PathBuf,
+    pub lints: Vec<LintConfigEntry>,
+}
+
+#[derive(Debug)]
+pub(crate) struct LintConfigEntry {
+    /// The lint or group name, prefixed with the tool name for tool lints (`clippy::pedantic`).
+    pub name: String,
+    pub level: Level,
+    /// The span of the entry's key in the config file.
+    pub span: Span,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawConfig {
+    #[serde(default)]
+    lints: RawLints,
+    #[serde(default)]
+    overrides: Vec<RawOverride>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawOverride {
+    path: PathBuf,
+    #[serde(default)]
+    lints: RawLints,
+}
+
+/// Lint levels keyed by tool name and then by lint name.
+type RawLints = BTreeMap<String, BTreeMap<String, Spanned<RawLevel>>>;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawLevel {
+    Level(String),
+    Table {
+        level: String,
+        #[serde(default)]
+        priority: i8,
+    },
+}
+
+impl LintConfig {
+    /// Parses the lint config file, reporting any errors with spans into the file.
+    pub(crate) fn load(sess: &Session, file: &LintConfigFile) -> Option<LintConfig> {
+        let source_file =
+            sess.source_map().new_source_file(FileName::from(file.path.clone()), file.src.clone());
+        let raw: RawConfig = match toml::from_str(&file.src) {
+            Ok(raw) => raw,
+            Err(err) => {
+                let pos = err
+                    .line_col()
+                    .map_or(0, |(line, col)| line_col_to_offset(&file.src, line, col));
+                sess.dcx().emit_err(LintConfigMalformed {
+                    span: span_in(&source_file, pos..pos),
+                    message: err.to_string(),
+                });
+                return None;
+            }
+        };
+
+        let base = std::path::absolute(&file.path)
+            .ok()
+            .and_then(|path| path.parent().map(Path::to_path_buf))
+            .unwrap_or_default();
+        let cx = LoadCx { sess, src: &file.src, source_file: &source_file };
+        let lints = cx.entries(raw.lints);
+        let overrides = raw
+            .overrides
+            .into_iter()
+            .map(|o| LintConfigOverride { path: base.join(o.path), lints: cx.entries(o.lints) })
+            .collect();
+        Some(LintConfig { lints, overrides })
+    }
+
+    /// Returns the overrides that apply to the source file `path`.
+    pub(crate) fn overrides_for(&self, path: &Path) -> impl Iterator<Item = &LintConfigOverride> {
+        let path = std::path::absolute(path).ok();
+        self.overrides.iter().filter(move |o| path.as_ref().is_some_and(|p| p.starts_with(&o.path)))
+    }
+}
+
+struct LoadCx<'a> {
+    sess: &'a Session,
+    src: &'a str,
+    source_file: &'a SourceFile,
+}
+
+impl LoadCx<'_> {
+    fn entries(&self, lints: RawLints) -> Vec<LintConfigEntry> {
+        let mut entries = vec![];
+        for (tool, lints) in lints {
+            for (name, value) in lints {
+                let value_range = value.start()..value.end();
+                let (level, priority) = match value.into_inner() {
+                    RawLevel::Level(level) => (level, 0),
+                    RawLevel::Table { level, priority } => (level, priority),
+                };
+                let Some(level) = Level::from_str(&level) else {
+                    self.sess.dcx().emit_err(LintConfigInvalidLevel {
+                        span: span_in(self.source_file, value_range),
+                        level,
+                    });
+                    continue;
+                };
+                let name = if tool == "rust" { name } else { format!("{tool}::{name}") };
+                let span = span_in(self.source_file, key_range(self.src, value_range));
+                entries.push((priority, LintConfigEntry { name, level, span }));
+            }
+        }
+        // Lower priorities are applied first so that higher priorities override them. The sort
+        // is stable, so entries with the same priority stay in name order.
+        entries.sort_by_key(|&(priority, _)| priority);
+        entries.into_iter().map(|(_, entry)| entry).collect()
+    }
+}
+
+/// Returns the range of the key for the value at `value`, which is the text between the start
+/// of the line and the `=` sign. Falls back to `value` if the key is not on the same line.
+fn key_range(src: &str, value: Range<usize>) -> Range<usize> {
+    let line_start = src[..value.start].rfind('\n').map_or(0, |i| i + 1);
+    let Some(eq) = src[line_start..value.start].rfind('=') else { return value };
+    let key = &src[line_start..line_start + eq];
+    let trimmed = key.trim();
+    if trimmed.is_empty() {
+        return value;
+    }
+    let start = line_start + (key.len() - key.trim_start().len());
+    start..start + trimmed.len()
+}
+
+fn line_col_to_offset(src: &str, line: usize, col: usize) -> usize {
+    let line_start = src.split_inclusive('\n').take(line).map(str::len).sum::<usize>();
+    (line_start + col).min(src.len())
+}
+
+fn span_in(source_file: &SourceFile, range: Range<usize>) -> Span {
+    let lo = source_file.start_pos + BytePos::from_usize(range.start);
+    let hi = source_file.start_pos + BytePos::from_usize(range.end);
+    Span::with_root_ctxt(lo, hi)
+}
+
+#[cfg(test)]
+mod tests;
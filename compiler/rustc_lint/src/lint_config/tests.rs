@@ -0,0 +1,34 @@
+use super::{key_range, line_col_to_offset};
+
+fn key_of<'a>(src: &'a str, value: &str) -> &'a str {
+    let start = src.find(value).unwrap();
+    &src[key_range(src, start..start + value.len())]
+}
+
+#[test]
+fn key_range_plain_and_inline_table() {
+    let src =
+        "[lints.rust]\nunsafe_code = \"forbid\"\n  unused={ level = \"deny\", priority = -1 }\n";
+    assert_eq!(key_of(src, "\"forbid\""), "unsafe_code");
+    assert_eq!(key_of(src, "{ level"), "unused");
+}
+
+#[test]
+fn key_range_dotted_key() {
+    let src = "[[overrides]]\npath = \"src\"\nlints.rust.dead_code = \"allow\"\n";
+    assert_eq!(key_of(src, "\"allow\""), "lints.rust.dead_code");
+}
+
+#[test]
+fn key_range_falls_back_to_value() {
+    let src = "[lints.rust.unused]\nlevel =\n\"deny\"\n";
+    assert_eq!(key_of(src, "\"deny\""), "\"deny\"");
+}
+
+#[test]
+fn line_col() {
+    let src = "a = 1\nb = \nc = 3";
+    assert_eq!(line_col_to_offset(src, 0, 2), 2);
+    assert_eq!(line_col_to_offset(src, 1, 4), 10);
+    assert_eq!(line_col_to_offset(src, 5, 0), src.len());
+}
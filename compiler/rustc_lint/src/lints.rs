@@ -1040,6 +1040,14 @@ pub(crate) struct DeprecatedLintNameFromCommandLine<'a> {
     pub requested_level: RequestedLevel<'a>,
 }
 
+#[derive(LintDiagnostic)]
+#[diag(lint_deprecated_lint_name)]
+#[help]
+pub(crate) struct DeprecatedLintNameFromLintConfig<'a> {
+    pub name: String,
+    pub replace: &'a str,
+}
+
 #[derive(LintDiagnostic)]
 #[diag(lint_renamed_lint)]
 pub(crate) struct RenamedLint<'a> {
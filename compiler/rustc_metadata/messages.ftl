@@ -106,6 +106,10 @@ metadata_import_name_type_raw =
 metadata_import_name_type_x86 =
     import name type is only supported on x86
 
+metadata_incompatible_codegen_backend_lto =
+    the crate `{$crate_name}` is compiled with the `{$found_backend}` codegen backend, which is incompatible with `-C lto` in this crate
+    .note = all crates optimized with `-C lto` must be compiled with the `{$desired_backend}` codegen backend
+
 metadata_incompatible_panic_in_drop_strategy =
     the crate `{$crate_name}` is compiled with the panic-in-drop strategy `{$found_strategy}` which is incompatible with this crate's strategy of `{$desired_strategy}`
 
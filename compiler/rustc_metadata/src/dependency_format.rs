@@ -56,7 +56,7 @@
 use rustc_middle::bug;
 use rustc_middle::middle::dependency_format::{Dependencies, DependencyList, Linkage};
 use rustc_middle::ty::TyCtxt;
-use rustc_session::config::CrateType;
+use rustc_session::config::{CrateType, Lto};
 use rustc_session::cstore::CrateDepKind;
 use rustc_session::cstore::LinkagePreference::{self, RequireDynamic, RequireStatic};
 use rustc_span::sym;
@@ -64,9 +64,9 @@
 
 use crate::creader::CStore;
 use crate::errors::{
-    BadPanicStrategy, CrateDepMultiple, IncompatiblePanicInDropStrategy, LibRequired,
-    NonStaticCrateDep, RequiredPanicStrategy, RlibRequired, RustcDriverHelp, RustcLibRequired,
-    TwoPanicRuntimes,
+    BadPanicStrategy, CrateDepMultiple, IncompatibleCodegenBackendLto,
+    IncompatiblePanicInDropStrategy, LibRequired, NonStaticCrateDep, RequiredPanicStrategy,
+    RlibRequired, RustcDriverHelp, RustcLibRequired, TwoPanicRuntimes,
 };
 
 pub(crate) fn calculate(tcx: TyCtxt<'_>) -> Dependencies {
@@ -460,4 +460,29 @@ fn verify_ok(tcx: TyCtxt<'_>, list: &[Linkage]) {
             }
         }
     }
+
+    // Cross-crate LTO optimizes the code of the statically linked crates with the main codegen
+    // backend of this crate, which cannot read the code generated by other codegen backends.
+    if matches!(sess.lto(), Lto::Fat | Lto::Thin) {
+        let desired_backend = sess.codegen_backend_name();
+        for (i, linkage) in list.iter().enumerate() {
+            if *linkage != Linkage::Static {
+                continue;
+            }
+            let cnum = CrateNum::new(i + 1);
+            if tcx.is_compiler_builtins(cnum) || tcx.is_no_builtins(cnum) {
+                continue;
+            }
+            let backends = tcx.codegen_backends(cnum);
+            if let Some(&found_backend) =
+                backends.iter().find(|backend| backend.as_str() != desired_backend)
+            {
+                sess.dcx().emit_err(IncompatibleCodegenBackendLto {
+                    crate_name: tcx.crate_name(cnum),
+                    found_backend,
+                    desired_backend,
+                });
+            }
+        }
+    }
 }
@@ -74,6 +74,15 @@ pub struct RequiredPanicStrategy {
     pub desired_strategy: PanicStrategy,
 }
 
+#[derive(Diagnostic)]
+#[diag(metadata_incompatible_codegen_backend_lto)]
+#[note]
+pub struct IncompatibleCodegenBackendLto<'a> {
+    pub crate_name: Symbol,
+    pub found_backend: Symbol,
+    pub desired_backend: &'a str,
+}
+
 #[derive(Diagnostic)]
 #[diag(metadata_incompatible_panic_in_drop_strategy)]
 pub struct IncompatiblePanicInDropStrategy {
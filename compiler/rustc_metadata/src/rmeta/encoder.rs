@@ -610,6 +610,7 @@ macro_rules! stat {
             stat!("dep", || (self.encode_crate_deps(), self.encode_dylib_dependency_formats()));
 
         let lib_features = stat!("lib-features", || self.encode_lib_features());
+        let codegen_backends = stat!("codegen-backends", || self.encode_codegen_backends());
 
         let stability_implications =
             stat!("stability-implications", || self.encode_stability_implications());
@@ -705,6 +706,7 @@ macro_rules! stat {
                 stable_crate_id: tcx.def_path_hash(LOCAL_CRATE.as_def_id()).stable_crate_id(),
                 required_panic_strategy: tcx.required_panic_strategy(LOCAL_CRATE),
                 panic_in_drop_strategy: tcx.sess.opts.unstable_opts.panic_in_drop,
+                codegen_backends,
                 edition: tcx.sess.edition(),
                 has_global_allocator: tcx.has_global_allocator(LOCAL_CRATE),
                 has_alloc_error_handler: tcx.has_alloc_error_handler(LOCAL_CRATE),
@@ -1960,6 +1962,13 @@ fn encode_crate_deps(&mut self) -> LazyArray<CrateDep> {
         self.lazy_array(deps.iter().map(|(_, dep)| dep))
     }
 
+    fn encode_codegen_backends(&mut self) -> LazyArray<Symbol> {
+        let sess = self.tcx.sess;
+        let main = Symbol::intern(sess.codegen_backend_name());
+        let secondary = sess.opts.unstable_opts.secondary_codegen_backends.iter();
+        self.lazy_array(std::iter::once(main).chain(secondary.map(|name| Symbol::intern(name))))
+    }
+
     fn encode_lib_features(&mut self) -> LazyArray<(Symbol, FeatureStability)> {
         empty_proc_macro!(self);
         let tcx = self.tcx;
@@ -2186,6 +2195,11 @@ pub struct EncodedMetadata {
 }
 
 impl EncodedMetadata {
+    #[inline]
+    pub fn empty() -> Self {
+        Self { mmap: None, _temp_dir: None }
+    }
+
     #[inline]
     pub fn from_path(path: PathBuf, temp_dir: Option<MaybeTempDir>) -> std::io::Result<Self> {
         let file = std::fs::File::open(&path)?;
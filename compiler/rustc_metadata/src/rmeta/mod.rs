@@ -249,6 +249,8 @@ pub(crate) struct CrateRoot {
     stable_crate_id: StableCrateId,
     required_panic_strategy: Option<PanicStrategy>,
     panic_in_drop_strategy: PanicStrategy,
+    /// The codegen backends that may have generated code of the crate, the main one first.
+    codegen_backends: LazyArray<Symbol>,
     edition: Edition,
     has_global_allocator: bool,
     has_alloc_error_handler: bool,
@@ -97,7 +97,10 @@ fn clone(&self) -> Self { *self }
     /// session, if it still exists. This is used during incremental compilation to
     /// turn a deserialized `DefPathHash` into its current `DefId`.
     /// Will fetch a DefId from a DefPathHash for a foreign crate.
-    hook def_path_hash_to_def_id_extern(hash: DefPathHash, stable_crate_id: StableCrateId) -> DefId;
+    hook def_path_hash_to_def_id_extern(
+        hash: DefPathHash,
+        stable_crate_id: StableCrateId
+    ) -> Option<DefId>;
 
     /// Create a THIR tree for debugging.
     hook thir_tree(key: LocalDefId) -> String;
@@ -49,6 +49,9 @@ pub struct CodegenFnAttrs {
     /// The `#[patchable_function_entry(...)]` attribute. Indicates how many nops should be around
     /// the function entry.
     pub patchable_function_entry: Option<PatchableFunctionEntry>,
+    /// The `#[codegen_backend = "..."]` attribute. Indicates the codegen backend that should
+    /// generate the code of the function, if it is not the main one of the compilation session.
+    pub codegen_backend: Option<Symbol>,
 }
 
 #[derive(Copy, Clone, Debug, TyEncodable, TyDecodable, HashStable)]
@@ -158,6 +161,7 @@ pub const fn new() -> CodegenFnAttrs {
             instruction_set: None,
             alignment: None,
             patchable_function_entry: None,
+            codegen_backend: None,
         }
     }
 
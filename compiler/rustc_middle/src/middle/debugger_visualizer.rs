@@ -8,6 +8,7 @@
 pub enum DebuggerVisualizerType {
     Natvis,
     GdbPrettyPrinter,
+    LldbFormatter,
 }
 
 /// A single debugger visualizer file.
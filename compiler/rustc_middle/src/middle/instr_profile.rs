@@ -0,0 +1,38 @@
+//! The instrumentation profile passed with `-C profile-use`, as used by the MIR inliner.
+
+use rustc_data_structures::fx::FxHashMap;
+
+/// How often a function was executed in the profiled runs.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Hotness {
+    Hot,
+    Normal,
+    Cold,
+}
+
+/// The counts of the functions in an instrumentation profile.
+///
+/// Only the largest count of each function is kept: like LLVM's `isFunctionHotInCallGraph`, a
+/// function is hot if one of its counts is hot, and cold if all of them are cold.
+#[derive(Debug)]
+pub struct InstrProfile {
+    /// The largest count of each function, by symbol name.
+    pub max_counts: FxHashMap<String, u64>,
+    /// The smallest count that is hot, from the summary of the profile.
+    pub hot_count_threshold: u64,
+    /// The largest count that is cold, from the summary of the profile.
+    pub cold_count_threshold: u64,
+}
+
+impl InstrProfile {
+    /// Returns the hotness of the function with the given symbol name, or `None` if the profile
+    /// has no counts for it, e.g. because it was not instantiated in the profiled build.
+    pub fn function_hotness(&self, symbol_name: &str) -> Option<Hotness> {
+        let count = *self.max_counts.get(symbol_name)?;
+        Some(match count {
+            _ if count >= self.hot_count_threshold => Hotness::Hot,
+            _ if count <= self.cold_count_threshold => Hotness::Cold,
+            _ => Hotness::Normal,
+        })
+    }
+}
@@ -2,6 +2,7 @@
 pub mod debugger_visualizer;
 pub mod dependency_format;
 pub mod exported_symbols;
+pub mod instr_profile;
 pub mod lang_items;
 pub mod lib_features {
     use rustc_data_structures::unord::UnordMap;
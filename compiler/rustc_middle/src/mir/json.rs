@@ -0,0 +1,567 @@
+//! A structured JSON representation of MIR bodies, written by `-Z dump-mir` when
+//! `-Z dump-mir-format=json` is passed.
+//!
+//! Unlike the textual output, every basic block, statement, terminator, local, scope and span is
+//! a separate JSON value, so that tools can compare the MIR between passes without having to
+//! parse the pretty-printed form. Statements and terminators are described by their kind and their
+//! places, operands and rvalues, which are structured values too. Types, constants and the kinds
+//! of operations, such as `Add` or `IntToInt`, are rendered as text, using the same syntax as the
+//! textual output. Each statement and terminator also comes with its textual form.
+
+use std::fmt::Display;
+use std::io;
+
+use rustc_middle::mir::*;
+use rustc_span::source_map::Spanned;
+use rustc_span::{FileNameDisplayPreference, Span};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct JsonBody {
+    def_path: String,
+    promoted: Option<usize>,
+    pass_name: String,
+    disambiguator: String,
+    phase: &'static str,
+    pass_count: usize,
+    arg_count: usize,
+    span: JsonSpan,
+    locals: Vec<JsonLocal>,
+    var_debug_info: Vec<JsonVarDebugInfo>,
+    scopes: Vec<JsonScope>,
+    basic_blocks: Vec<JsonBasicBlock>,
+}
+
+#[derive(Serialize)]
+struct JsonSpan {
+    file: Option<String>,
+    start_line: usize,
+    start_col: usize,
+    end_line: usize,
+    end_col: usize,
+}
+
+#[derive(Serialize)]
+struct JsonLocal {
+    local: usize,
+    ty: String,
+    mutable: bool,
+    user_ty: Vec<String>,
+    scope: usize,
+    span: JsonSpan,
+}
+
+#[derive(Serialize)]
+struct JsonVarDebugInfo {
+    name: String,
+    /// The debuginfo as printed by the textual output, e.g. `x => copy _1`.
+    text: String,
+    argument_index: Option<u16>,
+    scope: usize,
+    span: JsonSpan,
+}
+
+#[derive(Serialize)]
+struct JsonScope {
+    scope: usize,
+    parent: Option<usize>,
+    /// The function the scope was inlined from, if any.
+    inlined: Option<JsonInlined>,
+    inlined_parent: Option<usize>,
+    span: JsonSpan,
+}
+
+#[derive(Serialize)]
+struct JsonInlined {
+    callee: String,
+    call_site: JsonSpan,
+}
+
+#[derive(Serialize)]
+struct JsonBasicBlock {
+    block: usize,
+    is_cleanup: bool,
+    statements: Vec<JsonStatement>,
+    terminator: JsonTerminator,
+}
+
+#[derive(Serialize)]
+struct JsonStatement {
+    #[serde(flatten)]
+    kind: JsonStatementKind,
+    text: String,
+    scope: usize,
+    span: JsonSpan,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind")]
+enum JsonStatementKind {
+    Assign { place: JsonPlace, rvalue: JsonRvalue },
+    FakeRead { cause: String, place: JsonPlace },
+    SetDiscriminant { place: JsonPlace, variant_index: usize },
+    Deinit { place: JsonPlace },
+    StorageLive { local: usize },
+    StorageDead { local: usize },
+    Retag { retag_kind: String, place: JsonPlace },
+    PlaceMention { place: JsonPlace },
+    AscribeUserType { place: JsonPlace, variance: String },
+    Coverage,
+    Assume { operand: JsonOperand },
+    CopyNonOverlapping { src: JsonOperand, dst: JsonOperand, count: JsonOperand },
+    ConstEvalCounter,
+    Nop,
+}
+
+#[derive(Serialize)]
+struct JsonPlace {
+    local: usize,
+    projection: Vec<JsonProjection>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind")]
+enum JsonProjection {
+    Deref,
+    Field { field: usize, ty: String },
+    Index { local: usize },
+    ConstantIndex { offset: u64, min_length: u64, from_end: bool },
+    Subslice { from: u64, to: u64, from_end: bool },
+    Downcast { variant_index: usize, name: Option<String> },
+    OpaqueCast { ty: String },
+    Subtype { ty: String },
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind")]
+enum JsonOperand {
+    Copy { place: JsonPlace },
+    Move { place: JsonPlace },
+    Constant { ty: String, text: String },
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind")]
+enum JsonRvalue {
+    Use { operand: JsonOperand },
+    Repeat { operand: JsonOperand, count: String },
+    Ref { borrow_kind: String, place: JsonPlace },
+    ThreadLocalRef { def_path: String },
+    RawPtr { mutable: bool, place: JsonPlace },
+    Len { place: JsonPlace },
+    Cast { cast_kind: String, operand: JsonOperand, ty: String },
+    BinaryOp { op: String, lhs: JsonOperand, rhs: JsonOperand },
+    NullaryOp { op: String, ty: String },
+    UnaryOp { op: String, operand: JsonOperand },
+    Discriminant { place: JsonPlace },
+    Aggregate { aggregate: JsonAggregateKind, operands: Vec<JsonOperand> },
+    ShallowInitBox { operand: JsonOperand, ty: String },
+    CopyForDeref { place: JsonPlace },
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind")]
+enum JsonAggregateKind {
+    Array { ty: String },
+    Tuple,
+    Adt { def_path: String, variant_index: usize, active_field: Option<usize> },
+    Closure { def_path: String },
+    Coroutine { def_path: String },
+    CoroutineClosure { def_path: String },
+    RawPtr { ty: String, mutable: bool },
+}
+
+#[derive(Serialize)]
+struct JsonTerminator {
+    #[serde(flatten)]
+    kind: JsonTerminatorKind,
+    /// The terminator without its successors, which are listed separately.
+    text: String,
+    successors: Vec<JsonSuccessor>,
+    /// What happens when unwinding out of this terminator, if it can unwind. A cleanup block is
+    /// also listed in the successors, with the `unwind` label.
+    unwind: Option<String>,
+    scope: usize,
+    span: JsonSpan,
+}
+
+/// The targets of the terminators are not repeated here, as they are listed in the successors.
+#[derive(Serialize)]
+#[serde(tag = "kind")]
+enum JsonTerminatorKind {
+    Goto,
+    SwitchInt { discr: JsonOperand, values: Vec<String> },
+    UnwindResume,
+    UnwindTerminate,
+    Return,
+    Unreachable,
+    Drop { place: JsonPlace, replace: bool },
+    Call { func: JsonOperand, args: Vec<JsonOperand>, destination: JsonPlace },
+    TailCall { func: JsonOperand, args: Vec<JsonOperand> },
+    Assert { cond: JsonOperand, expected: bool, msg: String },
+    Yield { value: JsonOperand, resume_arg: JsonPlace },
+    CoroutineDrop,
+    FalseEdge,
+    FalseUnwind,
+    InlineAsm,
+}
+
+#[derive(Serialize)]
+struct JsonSuccessor {
+    label: String,
+    target: usize,
+}
+
+/// Writes a JSON object describing the given MIR body, as it is after the pass `pass_name`.
+pub fn write_mir_fn_json<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    pass_name: &str,
+    disambiguator: &dyn Display,
+    w: &mut dyn io::Write,
+) -> io::Result<()> {
+    let json =
+        ty::print::with_no_trimmed_paths!(mir_fn_to_json(tcx, body, pass_name, disambiguator));
+    serde_json::to_writer_pretty(&mut *w, &json)?;
+    writeln!(w)
+}
+
+fn mir_fn_to_json<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    pass_name: &str,
+    disambiguator: &dyn Display,
+) -> JsonBody {
+    let span = |span| json_span(tcx, span);
+
+    let locals = body
+        .local_decls
+        .iter_enumerated()
+        .map(|(local, decl)| JsonLocal {
+            local: local.index(),
+            ty: decl.ty.to_string(),
+            mutable: decl.mutability.is_mut(),
+            user_ty: decl
+                .user_ty
+                .iter()
+                .flat_map(|user_ty| user_ty.projections())
+                .map(|user_ty| format!("{user_ty:?}"))
+                .collect(),
+            scope: decl.source_info.scope.index(),
+            span: span(decl.source_info.span),
+        })
+        .collect();
+
+    let var_debug_info = body
+        .var_debug_info
+        .iter()
+        .map(|info| JsonVarDebugInfo {
+            name: info.name.to_string(),
+            text: format!("{info:?}"),
+            argument_index: info.argument_index,
+            scope: info.source_info.scope.index(),
+            span: span(info.source_info.span),
+        })
+        .collect();
+
+    let scopes = body
+        .source_scopes
+        .iter_enumerated()
+        .map(|(scope, data)| JsonScope {
+            scope: scope.index(),
+            parent: data.parent_scope.map(SourceScope::index),
+            inlined: data.inlined.map(|(callee, call_site)| JsonInlined {
+                callee: callee.to_string(),
+                call_site: span(call_site),
+            }),
+            inlined_parent: data.inlined_parent_scope.map(SourceScope::index),
+            span: span(data.span),
+        })
+        .collect();
+
+    let basic_blocks = body
+        .basic_blocks
+        .iter_enumerated()
+        .map(|(block, data)| {
+            let statements = data
+                .statements
+                .iter()
+                .map(|statement| JsonStatement {
+                    kind: statement_kind_to_json(tcx, &statement.kind),
+                    text: format!("{statement:?}"),
+                    scope: statement.source_info.scope.index(),
+                    span: span(statement.source_info.span),
+                })
+                .collect();
+            JsonBasicBlock {
+                block: block.index(),
+                is_cleanup: data.is_cleanup,
+                statements,
+                terminator: terminator_to_json(tcx, data.terminator()),
+            }
+        })
+        .collect();
+
+    // See the notes on #41697 in `pretty.rs`.
+    let def_path =
+        ty::print::with_forced_impl_filename_line!(tcx.def_path_str(body.source.def_id()));
+
+    JsonBody {
+        def_path,
+        promoted: body.source.promoted.map(|promoted| promoted.index()),
+        pass_name: pass_name.to_string(),
+        disambiguator: disambiguator.to_string(),
+        phase: body.phase.name(),
+        pass_count: body.pass_count,
+        arg_count: body.arg_count,
+        span: span(body.span),
+        locals,
+        var_debug_info,
+        scopes,
+        basic_blocks,
+    }
+}
+
+fn terminator_to_json<'tcx>(tcx: TyCtxt<'tcx>, terminator: &Terminator<'tcx>) -> JsonTerminator {
+    let mut text = String::new();
+    terminator.kind.fmt_head(&mut text).unwrap();
+
+    let successors = terminator
+        .kind
+        .fmt_successor_labels()
+        .into_iter()
+        .zip(terminator.kind.successors())
+        .map(|(label, target)| JsonSuccessor { label: label.into_owned(), target: target.index() })
+        .collect();
+
+    let unwind = terminator.kind.unwind().map(|unwind| match unwind {
+        UnwindAction::Continue => "continue".to_string(),
+        UnwindAction::Unreachable => "unreachable".to_string(),
+        UnwindAction::Terminate(reason) => format!("terminate({})", reason.as_short_str()),
+        UnwindAction::Cleanup(_) => "cleanup".to_string(),
+    });
+
+    JsonTerminator {
+        kind: terminator_kind_to_json(&terminator.kind),
+        text,
+        successors,
+        unwind,
+        scope: terminator.source_info.scope.index(),
+        span: json_span(tcx, terminator.source_info.span),
+    }
+}
+
+fn statement_kind_to_json<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    kind: &StatementKind<'tcx>,
+) -> JsonStatementKind {
+    match *kind {
+        StatementKind::Assign(box (place, ref rvalue)) => JsonStatementKind::Assign {
+            place: place_to_json(place),
+            rvalue: rvalue_to_json(tcx, rvalue),
+        },
+        StatementKind::FakeRead(box (cause, place)) => {
+            JsonStatementKind::FakeRead { cause: format!("{cause:?}"), place: place_to_json(place) }
+        }
+        StatementKind::SetDiscriminant { box place, variant_index } => {
+            JsonStatementKind::SetDiscriminant {
+                place: place_to_json(place),
+                variant_index: variant_index.index(),
+            }
+        }
+        StatementKind::Deinit(box place) => {
+            JsonStatementKind::Deinit { place: place_to_json(place) }
+        }
+        StatementKind::StorageLive(local) => {
+            JsonStatementKind::StorageLive { local: local.index() }
+        }
+        StatementKind::StorageDead(local) => {
+            JsonStatementKind::StorageDead { local: local.index() }
+        }
+        StatementKind::Retag(retag_kind, box place) => JsonStatementKind::Retag {
+            retag_kind: format!("{retag_kind:?}"),
+            place: place_to_json(place),
+        },
+        StatementKind::PlaceMention(box place) => {
+            JsonStatementKind::PlaceMention { place: place_to_json(place) }
+        }
+        StatementKind::AscribeUserType(box (place, _), variance) => {
+            JsonStatementKind::AscribeUserType {
+                place: place_to_json(place),
+                variance: format!("{variance:?}"),
+            }
+        }
+        StatementKind::Coverage(_) => JsonStatementKind::Coverage,
+        StatementKind::Intrinsic(box NonDivergingIntrinsic::Assume(ref operand)) => {
+            JsonStatementKind::Assume { operand: operand_to_json(operand) }
+        }
+        StatementKind::Intrinsic(box NonDivergingIntrinsic::CopyNonOverlapping(
+            CopyNonOverlapping { ref src, ref dst, ref count },
+        )) => JsonStatementKind::CopyNonOverlapping {
+            src: operand_to_json(src),
+            dst: operand_to_json(dst),
+            count: operand_to_json(count),
+        },
+        StatementKind::ConstEvalCounter => JsonStatementKind::ConstEvalCounter,
+        StatementKind::Nop => JsonStatementKind::Nop,
+    }
+}
+
+fn terminator_kind_to_json<'tcx>(kind: &TerminatorKind<'tcx>) -> JsonTerminatorKind {
+    let operands = |args: &[Spanned<Operand<'tcx>>]| {
+        args.iter().map(|arg| operand_to_json(&arg.node)).collect()
+    };
+    match *kind {
+        TerminatorKind::Goto { .. } => JsonTerminatorKind::Goto,
+        TerminatorKind::SwitchInt { ref discr, ref targets } => JsonTerminatorKind::SwitchInt {
+            discr: operand_to_json(discr),
+            values: targets.iter().map(|(value, _)| value.to_string()).collect(),
+        },
+        TerminatorKind::UnwindResume => JsonTerminatorKind::UnwindResume,
+        TerminatorKind::UnwindTerminate(_) => JsonTerminatorKind::UnwindTerminate,
+        TerminatorKind::Return => JsonTerminatorKind::Return,
+        TerminatorKind::Unreachable => JsonTerminatorKind::Unreachable,
+        TerminatorKind::Drop { place, replace, .. } => {
+            JsonTerminatorKind::Drop { place: place_to_json(place), replace }
+        }
+        TerminatorKind::Call { ref func, ref args, destination, .. } => JsonTerminatorKind::Call {
+            func: operand_to_json(func),
+            args: operands(args),
+            destination: place_to_json(destination),
+        },
+        TerminatorKind::TailCall { ref func, ref args, .. } => {
+            JsonTerminatorKind::TailCall { func: operand_to_json(func), args: operands(args) }
+        }
+        TerminatorKind::Assert { ref cond, expected, ref msg, .. } => {
+            let mut text = String::new();
+            msg.fmt_assert_args(&mut text).unwrap();
+            JsonTerminatorKind::Assert { cond: operand_to_json(cond), expected, msg: text }
+        }
+        TerminatorKind::Yield { ref value, resume_arg, .. } => JsonTerminatorKind::Yield {
+            value: operand_to_json(value),
+            resume_arg: place_to_json(resume_arg),
+        },
+        TerminatorKind::CoroutineDrop => JsonTerminatorKind::CoroutineDrop,
+        TerminatorKind::FalseEdge { .. } => JsonTerminatorKind::FalseEdge,
+        TerminatorKind::FalseUnwind { .. } => JsonTerminatorKind::FalseUnwind,
+        TerminatorKind::InlineAsm { .. } => JsonTerminatorKind::InlineAsm,
+    }
+}
+
+fn rvalue_to_json<'tcx>(tcx: TyCtxt<'tcx>, rvalue: &Rvalue<'tcx>) -> JsonRvalue {
+    match *rvalue {
+        Rvalue::Use(ref operand) => JsonRvalue::Use { operand: operand_to_json(operand) },
+        Rvalue::Repeat(ref operand, count) => {
+            JsonRvalue::Repeat { operand: operand_to_json(operand), count: count.to_string() }
+        }
+        Rvalue::Ref(_, borrow_kind, place) => {
+            JsonRvalue::Ref { borrow_kind: format!("{borrow_kind:?}"), place: place_to_json(place) }
+        }
+        Rvalue::ThreadLocalRef(def_id) => {
+            JsonRvalue::ThreadLocalRef { def_path: tcx.def_path_str(def_id) }
+        }
+        Rvalue::RawPtr(mutability, place) => {
+            JsonRvalue::RawPtr { mutable: mutability.is_mut(), place: place_to_json(place) }
+        }
+        Rvalue::Len(place) => JsonRvalue::Len { place: place_to_json(place) },
+        Rvalue::Cast(cast_kind, ref operand, ty) => JsonRvalue::Cast {
+            cast_kind: format!("{cast_kind:?}"),
+            operand: operand_to_json(operand),
+            ty: ty.to_string(),
+        },
+        Rvalue::BinaryOp(op, box (ref lhs, ref rhs)) => JsonRvalue::BinaryOp {
+            op: format!("{op:?}"),
+            lhs: operand_to_json(lhs),
+            rhs: operand_to_json(rhs),
+        },
+        Rvalue::NullaryOp(ref op, ty) => {
+            JsonRvalue::NullaryOp { op: format!("{op:?}"), ty: ty.to_string() }
+        }
+        Rvalue::UnaryOp(op, ref operand) => {
+            JsonRvalue::UnaryOp { op: format!("{op:?}"), operand: operand_to_json(operand) }
+        }
+        Rvalue::Discriminant(place) => JsonRvalue::Discriminant { place: place_to_json(place) },
+        Rvalue::Aggregate(box ref kind, ref operands) => {
+            let aggregate = match *kind {
+                AggregateKind::Array(ty) => JsonAggregateKind::Array { ty: ty.to_string() },
+                AggregateKind::Tuple => JsonAggregateKind::Tuple,
+                AggregateKind::Adt(def_id, variant_index, _, _, active_field) => {
+                    JsonAggregateKind::Adt {
+                        def_path: tcx.def_path_str(def_id),
+                        variant_index: variant_index.index(),
+                        active_field: active_field.map(|field| field.index()),
+                    }
+                }
+                AggregateKind::Closure(def_id, _) => {
+                    JsonAggregateKind::Closure { def_path: tcx.def_path_str(def_id) }
+                }
+                AggregateKind::Coroutine(def_id, _) => {
+                    JsonAggregateKind::Coroutine { def_path: tcx.def_path_str(def_id) }
+                }
+                AggregateKind::CoroutineClosure(def_id, _) => {
+                    JsonAggregateKind::CoroutineClosure { def_path: tcx.def_path_str(def_id) }
+                }
+                AggregateKind::RawPtr(ty, mutability) => {
+                    JsonAggregateKind::RawPtr { ty: ty.to_string(), mutable: mutability.is_mut() }
+                }
+            };
+            JsonRvalue::Aggregate {
+                aggregate,
+                operands: operands.iter().map(operand_to_json).collect(),
+            }
+        }
+        Rvalue::ShallowInitBox(ref operand, ty) => {
+            JsonRvalue::ShallowInitBox { operand: operand_to_json(operand), ty: ty.to_string() }
+        }
+        Rvalue::CopyForDeref(place) => JsonRvalue::CopyForDeref { place: place_to_json(place) },
+    }
+}
+
+fn operand_to_json(operand: &Operand<'_>) -> JsonOperand {
+    match *operand {
+        Operand::Copy(place) => JsonOperand::Copy { place: place_to_json(place) },
+        Operand::Move(place) => JsonOperand::Move { place: place_to_json(place) },
+        Operand::Constant(ref constant) => {
+            JsonOperand::Constant { ty: constant.ty().to_string(), text: format!("{constant:?}") }
+        }
+    }
+}
+
+fn place_to_json(place: Place<'_>) -> JsonPlace {
+    let projection = place
+        .projection
+        .iter()
+        .map(|elem| match elem {
+            ProjectionElem::Deref => JsonProjection::Deref,
+            ProjectionElem::Field(field, ty) => {
+                JsonProjection::Field { field: field.index(), ty: ty.to_string() }
+            }
+            ProjectionElem::Index(local) => JsonProjection::Index { local: local.index() },
+            ProjectionElem::ConstantIndex { offset, min_length, from_end } => {
+                JsonProjection::ConstantIndex { offset, min_length, from_end }
+            }
+            ProjectionElem::Subslice { from, to, from_end } => {
+                JsonProjection::Subslice { from, to, from_end }
+            }
+            ProjectionElem::Downcast(name, variant_index) => JsonProjection::Downcast {
+                variant_index: variant_index.index(),
+                name: name.map(|name| name.to_string()),
+            },
+            ProjectionElem::OpaqueCast(ty) => JsonProjection::OpaqueCast { ty: ty.to_string() },
+            ProjectionElem::Subtype(ty) => JsonProjection::Subtype { ty: ty.to_string() },
+        })
+        .collect();
+    JsonPlace { local: place.local.index(), projection }
+}
+
+fn json_span(tcx: TyCtxt<'_>, span: Span) -> JsonSpan {
+    let (file, start_line, start_col, end_line, end_col) =
+        tcx.sess.source_map().span_to_location_info(span);
+    JsonSpan {
+        file: file.map(|file| file.name.display(FileNameDisplayPreference::Remapped).to_string()),
+        start_line,
+        start_col,
+        end_line,
+        end_col,
+    }
+}
@@ -53,6 +53,7 @@
 pub mod generic_graphviz;
 pub mod graphviz;
 pub mod interpret;
+pub mod json;
 pub mod mono;
 pub mod patch;
 pub mod pretty;
@@ -264,6 +264,12 @@ pub struct CodegenUnit<'tcx> {
     /// True if this is CGU is used to hold code coverage information for dead code,
     /// false otherwise.
     is_code_coverage_dead_code_cgu: bool,
+    /// Function items of this CGU whose code is identical to that of an earlier item, which the
+    /// backend may emit as aliases of it, see `-Z merge-identical-instances`.
+    aliases: FxIndexMap<Instance<'tcx>, Instance<'tcx>>,
+    /// The secondary codegen backend that generates the code of this CGU, for the items marked
+    /// `#[codegen_backend]`, or `None` for the main codegen backend.
+    codegen_backend: Option<Symbol>,
 }
 
 /// Auxiliary info about a `MonoItem`.
@@ -314,6 +320,8 @@ pub fn new(name: Symbol) -> CodegenUnit<'tcx> {
             size_estimate: 0,
             primary: false,
             is_code_coverage_dead_code_cgu: false,
+            aliases: Default::default(),
+            codegen_backend: None,
         }
     }
 
@@ -341,6 +349,22 @@ pub fn items_mut(&mut self) -> &mut FxIndexMap<MonoItem<'tcx>, MonoItemData> {
         &mut self.items
     }
 
+    pub fn aliases(&self) -> &FxIndexMap<Instance<'tcx>, Instance<'tcx>> {
+        &self.aliases
+    }
+
+    pub fn aliases_mut(&mut self) -> &mut FxIndexMap<Instance<'tcx>, Instance<'tcx>> {
+        &mut self.aliases
+    }
+
+    pub fn codegen_backend(&self) -> Option<Symbol> {
+        self.codegen_backend
+    }
+
+    pub fn set_codegen_backend(&mut self, codegen_backend: Option<Symbol>) {
+        self.codegen_backend = codegen_backend;
+    }
+
     pub fn is_code_coverage_dead_code_cgu(&self) -> bool {
         self.is_code_coverage_dead_code_cgu
     }
@@ -11,10 +11,12 @@
 };
 use rustc_middle::mir::visit::Visitor;
 use rustc_middle::mir::*;
+use rustc_session::config::DumpMirFormat;
 use rustc_target::abi::Size;
 use tracing::trace;
 
 use super::graphviz::write_mir_fn_graphviz;
+use super::json::write_mir_fn_json;
 use crate::mir::interpret::ConstAllocation;
 
 const INDENT: &str = "    ";
@@ -159,27 +161,43 @@ fn dump_matched_mir_node<'tcx, F>(
 ) where
     F: FnMut(PassWhere, &mut dyn io::Write) -> io::Result<()>,
 {
-    let _: io::Result<()> = try {
-        let mut file = create_dump_file(tcx, "mir", pass_num, pass_name, disambiguator, body)?;
-        // see notes on #41697 above
-        let def_path =
-            ty::print::with_forced_impl_filename_line!(tcx.def_path_str(body.source.def_id()));
-        // ignore-tidy-odd-backticks the literal below is fine
-        write!(file, "// MIR for `{def_path}")?;
-        match body.source.promoted {
-            None => write!(file, "`")?,
-            Some(promoted) => write!(file, "::{promoted:?}`")?,
+    let format = tcx.sess.opts.unstable_opts.dump_mir_format;
+    let extension = format.extension();
+    match format {
+        DumpMirFormat::Text => {
+            let _: io::Result<()> = try {
+                let mut file =
+                    create_dump_file(tcx, extension, pass_num, pass_name, disambiguator, body)?;
+                // see notes on #41697 above
+                let def_path = ty::print::with_forced_impl_filename_line!(
+                    tcx.def_path_str(body.source.def_id())
+                );
+                // ignore-tidy-odd-backticks the literal below is fine
+                write!(file, "// MIR for `{def_path}")?;
+                match body.source.promoted {
+                    None => write!(file, "`")?,
+                    Some(promoted) => write!(file, "::{promoted:?}`")?,
+                }
+                writeln!(file, " {disambiguator} {pass_name}")?;
+                if let Some(ref layout) = body.coroutine_layout_raw() {
+                    writeln!(file, "/* coroutine_layout = {layout:#?} */")?;
+                }
+                writeln!(file)?;
+                extra_data(PassWhere::BeforeCFG, &mut file)?;
+                write_user_type_annotations(tcx, body, &mut file)?;
+                write_mir_fn(tcx, body, &mut extra_data, &mut file, options)?;
+                extra_data(PassWhere::AfterCFG, &mut file)?;
+            };
         }
-        writeln!(file, " {disambiguator} {pass_name}")?;
-        if let Some(ref layout) = body.coroutine_layout_raw() {
-            writeln!(file, "/* coroutine_layout = {layout:#?} */")?;
+        // The extra data of the passes is only written in the textual format.
+        DumpMirFormat::Json => {
+            let _: io::Result<()> = try {
+                let mut file =
+                    create_dump_file(tcx, extension, pass_num, pass_name, disambiguator, body)?;
+                write_mir_fn_json(tcx, body, pass_name, disambiguator, &mut file)?;
+            };
         }
-        writeln!(file)?;
-        extra_data(PassWhere::BeforeCFG, &mut file)?;
-        write_user_type_annotations(tcx, body, &mut file)?;
-        write_mir_fn(tcx, body, &mut extra_data, &mut file, options)?;
-        extra_data(PassWhere::AfterCFG, &mut file)?;
-    };
+    }
 
     if tcx.sess.opts.unstable_opts.dump_mir_graphviz {
         let _: io::Result<()> = try {
@@ -248,6 +248,7 @@ impl EraseType for $ty {
     Option<rustc_span::def_id::LocalDefId>,
     Option<rustc_span::Span>,
     Option<rustc_target::abi::FieldIdx>,
+    Option<rustc_target::abi::WrappingRange>,
     Option<rustc_target::spec::PanicStrategy>,
     Option<usize>,
     Option<rustc_middle::ty::IntrinsicDef>,
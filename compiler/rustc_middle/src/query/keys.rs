@@ -40,6 +40,12 @@ fn key_as_def_id(&self) -> Option<DefId> {
         None
     }
 
+    /// The item that the query is computed for, which `-Z item-time-report` charges the
+    /// execution time of the query to. This is also the `DefId` of an instance.
+    fn key_as_item_def_id(&self) -> Option<DefId> {
+        self.key_as_def_id()
+    }
+
     fn ty_def_id(&self) -> Option<DefId> {
         None
     }
@@ -67,6 +73,10 @@ impl<'tcx> Key for ty::InstanceKind<'tcx> {
     fn default_span(&self, tcx: TyCtxt<'_>) -> Span {
         tcx.def_span(self.def_id())
     }
+
+    fn key_as_item_def_id(&self) -> Option<DefId> {
+        Some(self.def_id())
+    }
 }
 
 impl<'tcx> AsLocalKey for ty::InstanceKind<'tcx> {
@@ -84,6 +94,10 @@ impl<'tcx> Key for ty::Instance<'tcx> {
     fn default_span(&self, tcx: TyCtxt<'_>) -> Span {
         tcx.def_span(self.def_id())
     }
+
+    fn key_as_item_def_id(&self) -> Option<DefId> {
+        Some(self.def_id())
+    }
 }
 
 impl<'tcx> Key for mir::interpret::GlobalId<'tcx> {
@@ -92,6 +106,10 @@ impl<'tcx> Key for mir::interpret::GlobalId<'tcx> {
     fn default_span(&self, tcx: TyCtxt<'_>) -> Span {
         self.instance.default_span(tcx)
     }
+
+    fn key_as_item_def_id(&self) -> Option<DefId> {
+        self.instance.key_as_item_def_id()
+    }
 }
 
 impl<'tcx> Key for (Ty<'tcx>, Option<ty::PolyExistentialTraitRef<'tcx>>) {
@@ -462,6 +480,10 @@ fn default_span(&self, tcx: TyCtxt<'_>) -> Span {
         self.value.default_span(tcx)
     }
 
+    fn key_as_item_def_id(&self) -> Option<DefId> {
+        self.value.key_as_item_def_id()
+    }
+
     fn ty_def_id(&self) -> Option<DefId> {
         self.value.ty_def_id()
     }
@@ -1509,6 +1509,13 @@
         desc { "getting a crate's configured panic-in-drop strategy" }
         separate_provide_extern
     }
+    /// The codegen backends that may have generated code of a crate, its main codegen backend
+    /// first.
+    query codegen_backends(_: CrateNum) -> &'tcx [Symbol] {
+        fatal_cycle
+        desc { "getting the codegen backends of a crate" }
+        separate_provide_extern
+    }
     query is_no_builtins(_: CrateNum) -> bool {
         fatal_cycle
         desc { "getting whether a crate has `#![no_builtins]`" }
@@ -2241,6 +2248,22 @@
         desc { |tcx| "checking assoc const `{}` has the same type as trait item", tcx.def_path_str(key.0) }
     }
 
+    /// Computes a range that contains all the values returned by a local function that returns an
+    /// unsigned integer. This is used to remove bounds checks in its callers.
+    query return_value_range(def_id: LocalDefId) -> Option<rustc_target::abi::WrappingRange> {
+        desc { |tcx| "computing the range of the values returned by `{}`", tcx.def_path_str(def_id) }
+    }
+
+    /// Reads the instrumentation profile passed with `-C profile-use`, so that the MIR inliner
+    /// can tell hot and cold call sites apart.
+    query instr_profile(_: ()) -> &'tcx Option<rustc_middle::middle::instr_profile::InstrProfile> {
+        arena_cache
+        // Reads the profile, which is not tracked.
+        eval_always
+        no_hash
+        desc { "reading the instrumentation profile" }
+    }
+
     query deduced_param_attrs(def_id: DefId) -> &'tcx [ty::DeducedParamAttrs] {
         desc { |tcx| "deducing parameter attributes for {}", tcx.def_path_str(def_id) }
         separate_provide_extern
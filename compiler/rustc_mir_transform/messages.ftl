@@ -1,4 +1,13 @@
 mir_transform_arithmetic_overflow = this arithmetic operation will overflow
+mir_transform_bounds_check_survived = bounds check could not be removed
+    .note = {$index ->
+        [unknown] nothing is known about the value of the index
+        *[other] {$length ->
+            [unknown] the index is in `{$index}`, but nothing is known about the length
+            *[other] the index is in `{$index}`, but the length may be as low as {$length}
+        }
+    }
+
 mir_transform_const_defined_here = `const` item defined here
 
 mir_transform_const_modify = attempting to modify a `const` item
@@ -27,3 +36,5 @@ mir_transform_unaligned_packed_ref = reference to packed field is unaligned
     .note = packed structs are only aligned by one byte, and many modern architectures penalize unaligned field accesses
     .note_ub = creating a misaligned reference is undefined behavior (even if that reference is never dereferenced)
     .help = copy the field contents to a local variable, or replace the reference with a raw pointer and use `read_unaligned`/`write_unaligned` (loads and stores via `*p` must be properly aligned even when using raw pointers)
+
+mir_transform_unreadable_profile = could not read the profile `{$path}` for MIR inlining: {$error}
@@ -0,0 +1,797 @@
+//! Bounds-check elimination.
+//!
+//! This pass removes the bounds checks that can be proven to always succeed. LLVM is usually able
+//! to remove these checks too, but not when the proof needs facts about the return value of a
+//! function that is not inlined, and not at all when using another backend.
+//!
+//! We run a forward dataflow analysis that computes, for each unsigned integer local:
+//! - a range of the values it may hold. The ranges come from constants, arithmetic, array
+//!   lengths, the comparisons that have been checked by a `switchInt` or an `assert`, and the
+//!   return value of the callees (see below);
+//! - a symbolic upper bound `local < bound`, where `bound` is either an SSA local or the length of
+//!   the slice pointed to by an SSA local. These facts come from the checked comparisons, and from
+//!   operations like `a % b` or `a & b` that cannot produce a value larger than their operands.
+//!
+//! A bounds check `assert(Lt(index, len))` is redundant if the range of `index` is below the
+//! range of `len`, or if `index < len` is a known fact.
+//!
+//! Function calls to local functions are handled using a summary of the callee: the
+//! `return_value_range` query runs the same analysis on the optimized MIR of the callee to find
+//! the range of its return value. The callee must not call back into the caller, to avoid query
+//! cycles. The analysis of a callee does not use the summaries of its own callees.
+//!
+//! The pass is opt-in: it only runs at `-Zmir-opt-level=3` and above, or with
+//! `-Zmir-enable-passes=+BoundsCheckElimination`. With `-Zmir-bounds-check-remarks`, a note is
+//! emitted for each bounds check that remains, which explains what is known about the index.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::LocalDefId;
+use rustc_index::bit_set::BitSet;
+use rustc_index::IndexVec;
+use rustc_middle::bug;
+use rustc_middle::mir::*;
+use rustc_middle::ty::{self, Instance, InstanceKind, ParamEnv, Ty, TyCtxt};
+use rustc_mir_dataflow::fmt::DebugWithContext;
+use rustc_mir_dataflow::impls::borrowed_locals;
+use rustc_mir_dataflow::{Analysis, AnalysisDomain, JoinSemiLattice, SwitchIntEdgeEffects};
+use rustc_target::abi::WrappingRange;
+use tracing::{debug, instrument};
+
+use crate::errors::BoundsCheckSurvived;
+use crate::ssa::SsaLocals;
+
+pub struct BoundsCheckElimination;
+
+impl<'tcx> MirPass<'tcx> for BoundsCheckElimination {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 3
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+
+        let has_bounds_checks = body.basic_blocks.iter().any(|data| {
+            matches!(
+                data.terminator().kind,
+                TerminatorKind::Assert { ref msg, .. } if matches!(**msg, AssertKind::BoundsCheck { .. })
+            )
+        });
+        if !has_bounds_checks {
+            return;
+        }
+
+        let param_env = tcx.param_env_reveal_all_normalized(body.source.def_id());
+        let ssa = SsaLocals::new(tcx, body, param_env);
+        let analysis = RangeAnalysis::new(tcx, body, param_env, &ssa, true);
+        let mut cursor =
+            analysis.into_engine(tcx, body).iterate_to_fixpoint().into_results_cursor(body);
+
+        let remarks = tcx.sess.opts.unstable_opts.mir_bounds_check_remarks;
+        let mut redundant = Vec::new();
+        for (block, data) in body.basic_blocks.iter_enumerated() {
+            // The analysis assumes that the condition of an assertion holds on its unwind edge
+            // too, so cleanup blocks must be left alone.
+            if data.is_cleanup {
+                continue;
+            }
+            let terminator = data.terminator();
+            let TerminatorKind::Assert { ref cond, expected, ref msg, .. } = terminator.kind else {
+                continue;
+            };
+            let AssertKind::BoundsCheck { ref len, ref index } = **msg else {
+                continue;
+            };
+
+            cursor.seek_before_primary_effect(body.terminator_loc(block));
+            let RangeState::Reachable(facts) = cursor.get() else {
+                // Unreachable code is removed by other passes.
+                continue;
+            };
+            let analysis = cursor.analysis();
+            let proven = cond
+                .place()
+                .and_then(|cond| cond.as_local())
+                .and_then(|cond| analysis.find_comparison(block, cond))
+                .is_some_and(|(op, lhs, rhs)| analysis.proves(facts, op, expected, lhs, rhs));
+            debug!(?block, ?proven);
+
+            if proven {
+                redundant.push(block);
+            } else if remarks {
+                let index = match analysis.operand_range(facts, index) {
+                    Some(range) => range.to_string(),
+                    None => "unknown".to_owned(),
+                };
+                let length = match analysis.operand_range(facts, len) {
+                    Some(range) => range.start.to_string(),
+                    None => "unknown".to_owned(),
+                };
+                tcx.dcx().emit_note(BoundsCheckSurvived {
+                    span: terminator.source_info.span,
+                    index,
+                    length,
+                });
+            }
+        }
+
+        for block in redundant {
+            let terminator = body.basic_blocks_mut()[block].terminator_mut();
+            let TerminatorKind::Assert { target, .. } = terminator.kind else { bug!() };
+            terminator.kind = TerminatorKind::Goto { target };
+        }
+    }
+}
+
+/// Computes the range of the values returned by a local function returning an unsigned integer.
+pub(crate) fn return_value_range(tcx: TyCtxt<'_>, def_id: LocalDefId) -> Option<WrappingRange> {
+    let body = tcx.optimized_mir(def_id);
+    if !matches!(body.return_ty().kind(), ty::Uint(_)) {
+        return None;
+    }
+
+    let param_env = tcx.param_env_reveal_all_normalized(def_id);
+    let ssa = SsaLocals::new(tcx, body, param_env);
+    let analysis = RangeAnalysis::new(tcx, body, param_env, &ssa, false);
+    let mut cursor =
+        analysis.into_engine(tcx, body).iterate_to_fixpoint().into_results_cursor(body);
+
+    let mut range: Option<Range> = None;
+    for (block, data) in body.basic_blocks.iter_enumerated() {
+        if let TerminatorKind::Return = data.terminator().kind {
+            cursor.seek_before_primary_effect(body.terminator_loc(block));
+            let RangeState::Reachable(facts) = cursor.get() else { continue };
+            let returned = facts.ranges.get(&RETURN_PLACE)?;
+            range = Some(match range {
+                Some(range) => range.hull(*returned),
+                None => Range::new(returned.start, returned.end),
+            });
+        }
+    }
+    range.map(|range| WrappingRange { start: range.start, end: range.end })
+}
+
+/// An inclusive range of unsigned integers.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct Range {
+    start: u128,
+    end: u128,
+    /// Number of times this range has been widened by a join. After `MAX_JOIN_CHANGES` joins,
+    /// the bounds that still move are set to their extreme value, so that loops converge quickly.
+    changes: u8,
+}
+
+const MAX_JOIN_CHANGES: u8 = 2;
+
+impl Range {
+    fn new(start: u128, end: u128) -> Range {
+        Range { start, end, changes: 0 }
+    }
+
+    fn exact(value: u128) -> Range {
+        Range::new(value, value)
+    }
+
+    fn hull(self, other: Range) -> Range {
+        Range::new(self.start.min(other.start), self.end.max(other.end))
+    }
+
+    fn is_full(&self) -> bool {
+        self.start == 0 && self.end == u128::MAX
+    }
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..={}", self.start, self.end)
+    }
+}
+
+/// A symbolic value that does not change while the facts that mention it hold.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Bound {
+    /// The value of an SSA local.
+    Local(Local),
+    /// The length of the slice or array pointed to by an SSA local.
+    Len(Local),
+}
+
+impl Bound {
+    fn mentions(self, local: Local) -> bool {
+        match self {
+            Bound::Local(l) | Bound::Len(l) => l == local,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Facts {
+    /// Ranges of the unsigned integer locals. Locals without an entry may have any value.
+    ranges: BTreeMap<Local, Range>,
+    /// `local < bound` for each entry.
+    less_than: BTreeMap<Local, Bound>,
+}
+
+impl Facts {
+    /// Forget everything about `local`, as it has been assigned to.
+    fn kill(&mut self, local: Local) {
+        self.ranges.remove(&local);
+        self.less_than.remove(&local);
+        self.less_than.retain(|_, bound| !bound.mentions(local));
+    }
+
+    fn set_range(&mut self, local: Local, range: Range) {
+        if range.is_full() {
+            self.ranges.remove(&local);
+        } else {
+            self.ranges.insert(local, range);
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum RangeState {
+    Unreachable,
+    Reachable(Facts),
+}
+
+impl JoinSemiLattice for RangeState {
+    fn join(&mut self, other: &Self) -> bool {
+        match (&mut *self, other) {
+            (_, RangeState::Unreachable) => false,
+            (RangeState::Unreachable, _) => {
+                *self = other.clone();
+                true
+            }
+            (RangeState::Reachable(this), RangeState::Reachable(other)) => {
+                let mut changed = false;
+                this.ranges.retain(|local, range| {
+                    let Some(other) = other.ranges.get(local) else {
+                        changed = true;
+                        return false;
+                    };
+                    let hull = range.hull(*other);
+                    if hull.start == range.start && hull.end == range.end {
+                        return true;
+                    }
+                    changed = true;
+                    if range.changes < MAX_JOIN_CHANGES {
+                        *range = Range { changes: range.changes.max(other.changes) + 1, ..hull };
+                    } else {
+                        // Widen the bounds that still move.
+                        if hull.start < range.start {
+                            range.start = 0;
+                        }
+                        if hull.end > range.end {
+                            range.end = u128::MAX;
+                        }
+                    }
+                    !range.is_full()
+                });
+                this.less_than.retain(|local, bound| {
+                    let keep = other.less_than.get(local) == Some(bound);
+                    changed |= !keep;
+                    keep
+                });
+                changed
+            }
+        }
+    }
+}
+
+impl<C> DebugWithContext<C> for RangeState {}
+
+/// A comparison `lhs op rhs`, normalized to `lhs < rhs`, `lhs <= rhs` or `lhs == rhs`.
+#[derive(Copy, Clone, Debug)]
+enum Relation {
+    Lt,
+    Le,
+    Eq,
+}
+
+/// Returns the relation that holds when `op` evaluates to `holds`, and whether the operands must
+/// be swapped.
+fn relation(op: BinOp, holds: bool) -> Option<(Relation, bool)> {
+    Some(match (op, holds) {
+        (BinOp::Lt, true) | (BinOp::Ge, false) => (Relation::Lt, false),
+        (BinOp::Le, true) | (BinOp::Gt, false) => (Relation::Le, false),
+        (BinOp::Gt, true) | (BinOp::Le, false) => (Relation::Lt, true),
+        (BinOp::Ge, true) | (BinOp::Lt, false) => (Relation::Le, true),
+        (BinOp::Eq, true) | (BinOp::Ne, false) => (Relation::Eq, false),
+        _ => return None,
+    })
+}
+
+struct RangeAnalysis<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    body: &'a Body<'tcx>,
+    param_env: ParamEnv<'tcx>,
+    /// Unsigned integer locals that cannot be modified through a pointer.
+    tracked: BitSet<Local>,
+    /// Symbolic value of each SSA local.
+    bounds: IndexVec<Local, Option<Bound>>,
+    /// Comparisons assigned to SSA locals whose operands are SSA locals or constants, so that
+    /// their value is the same wherever the result is used.
+    comparisons: IndexVec<Local, Option<(BinOp, Operand<'tcx>, Operand<'tcx>)>>,
+    /// Whether to use the summaries of the callees.
+    use_summaries: bool,
+}
+
+impl<'a, 'tcx> RangeAnalysis<'a, 'tcx> {
+    fn new(
+        tcx: TyCtxt<'tcx>,
+        body: &'a Body<'tcx>,
+        param_env: ParamEnv<'tcx>,
+        ssa: &SsaLocals,
+        use_summaries: bool,
+    ) -> Self {
+        let borrowed = borrowed_locals(body);
+        let mut tracked = BitSet::new_empty(body.local_decls.len());
+        for (local, decl) in body.local_decls.iter_enumerated() {
+            if matches!(decl.ty.kind(), ty::Uint(_)) && !borrowed.contains(local) {
+                tracked.insert(local);
+            }
+        }
+
+        // For each SSA local that holds a pointer, the SSA local it was copied or reborrowed from.
+        let mut pointer_roots: IndexVec<Local, Local> = body.local_decls.indices().collect();
+        let mut bounds = IndexVec::from_elem(None, &body.local_decls);
+        for local in body.args_iter() {
+            if ssa.is_ssa(local) {
+                bounds[local] = Some(Bound::Local(local));
+            }
+        }
+        let mut comparisons = IndexVec::from_elem(None, &body.local_decls);
+        let is_ssa_operand = |operand: &Operand<'tcx>| match operand.place() {
+            Some(place) => place.as_local().is_some_and(|local| ssa.is_ssa(local)),
+            None => true,
+        };
+
+        let ssa_local = |place: &Place<'tcx>| place.as_local().filter(|&l| ssa.is_ssa(l));
+        let ssa_deref = |place: &Place<'tcx>| match place.as_ref() {
+            PlaceRef { local, projection: [PlaceElem::Deref] } if ssa.is_ssa(local) => Some(local),
+            _ => None,
+        };
+
+        // The assignments are visited in an order where each local is assigned before its uses.
+        for (local, rvalue, _) in ssa.assignments(body) {
+            bounds[local] = Some(Bound::Local(local));
+            match *rvalue {
+                Rvalue::Use(Operand::Copy(ref place) | Operand::Move(ref place)) => {
+                    if let Some(source) = ssa_local(place) {
+                        pointer_roots[local] = pointer_roots[source];
+                        if let Some(bound) = bounds[source] {
+                            bounds[local] = Some(bound);
+                        }
+                    }
+                }
+                Rvalue::Ref(_, _, ref place) | Rvalue::RawPtr(_, ref place) => {
+                    if let Some(source) = ssa_deref(place) {
+                        pointer_roots[local] = pointer_roots[source];
+                    }
+                }
+                Rvalue::UnaryOp(
+                    UnOp::PtrMetadata,
+                    Operand::Copy(ref place) | Operand::Move(ref place),
+                ) => {
+                    if let Some(pointer) = ssa_local(place)
+                        && matches!(body.local_decls[local].ty.kind(), ty::Uint(_))
+                    {
+                        bounds[local] = Some(Bound::Len(pointer_roots[pointer]));
+                    }
+                }
+                Rvalue::Len(ref place) => {
+                    if let Some(pointer) = ssa_deref(place) {
+                        bounds[local] = Some(Bound::Len(pointer_roots[pointer]));
+                    }
+                }
+                Rvalue::BinaryOp(
+                    op @ (BinOp::Lt | BinOp::Le | BinOp::Gt | BinOp::Ge | BinOp::Eq | BinOp::Ne),
+                    box (ref lhs, ref rhs),
+                ) if is_ssa_operand(lhs) && is_ssa_operand(rhs) => {
+                    comparisons[local] = Some((op, lhs.clone(), rhs.clone()));
+                }
+                _ => {}
+            }
+        }
+
+        RangeAnalysis { tcx, body, param_env, tracked, bounds, comparisons, use_summaries }
+    }
+
+    fn type_max(&self, ty: Ty<'tcx>) -> Option<u128> {
+        match ty.kind() {
+            ty::Uint(_) => Some(ty.primitive_size(self.tcx).unsigned_int_max()),
+            _ => None,
+        }
+    }
+
+    fn tracked_local(&self, operand: &Operand<'tcx>) -> Option<Local> {
+        operand.place()?.as_local().filter(|&local| self.tracked.contains(local))
+    }
+
+    /// The range of an unsigned integer operand, or `None` if it may have any value.
+    fn operand_range(&self, facts: &Facts, operand: &Operand<'tcx>) -> Option<Range> {
+        match operand {
+            Operand::Constant(constant) => {
+                self.type_max(constant.ty())?;
+                let value = constant.const_.try_eval_bits(self.tcx, self.param_env)?;
+                Some(Range::exact(value))
+            }
+            Operand::Copy(_) | Operand::Move(_) => facts
+                .ranges
+                .get(&self.tracked_local(operand)?)
+                .map(|range| Range::new(range.start, range.end)),
+        }
+    }
+
+    /// The range of an operand, or the full range of its type.
+    fn operand_range_or_full(&self, facts: &Facts, operand: &Operand<'tcx>) -> Option<Range> {
+        let max = self.type_max(operand.ty(self.body, self.tcx))?;
+        Some(self.operand_range(facts, operand).unwrap_or(Range::new(0, max)))
+    }
+
+    /// The symbolic value of an operand, if it is an SSA local.
+    fn operand_bound(&self, operand: &Operand<'tcx>) -> Option<Bound> {
+        self.bounds[operand.place()?.as_local()?]
+    }
+
+    /// A known bound `operand < bound`.
+    fn operand_less_than(&self, facts: &Facts, operand: &Operand<'tcx>) -> Option<Bound> {
+        facts.less_than.get(&self.tracked_local(operand)?).copied()
+    }
+
+    /// Computes the range of `rvalue`, and a bound that it is known to be less than.
+    fn eval_rvalue(
+        &self,
+        facts: &Facts,
+        rvalue: &Rvalue<'tcx>,
+        max: u128,
+    ) -> (Option<Range>, Option<Bound>) {
+        match *rvalue {
+            Rvalue::Use(ref operand) => {
+                (self.operand_range(facts, operand), self.operand_less_than(facts, operand))
+            }
+            Rvalue::Cast(CastKind::IntToInt, ref operand, _) => {
+                let range = self.operand_range(facts, operand).filter(|range| range.end <= max);
+                (range, None)
+            }
+            Rvalue::Len(place) => {
+                let len = match *place.ty(self.body, self.tcx).ty.kind() {
+                    ty::Array(_, len) => len.try_eval_target_usize(self.tcx, self.param_env),
+                    _ => None,
+                };
+                (len.map(|len| Range::exact(len.into())), None)
+            }
+            Rvalue::BinaryOp(op, box (ref lhs, ref rhs)) => {
+                let (Some(a), Some(b)) = (
+                    self.operand_range_or_full(facts, lhs),
+                    self.operand_range_or_full(facts, rhs),
+                ) else {
+                    return (None, None);
+                };
+                match op {
+                    BinOp::Add | BinOp::AddUnchecked => {
+                        let end = a.end.checked_add(b.end).filter(|&end| end <= max);
+                        (end.map(|end| Range::new(a.start + b.start, end)), None)
+                    }
+                    BinOp::Sub | BinOp::SubUnchecked if a.start >= b.end => {
+                        let range = Range::new(a.start - b.end, a.end - b.start);
+                        // The difference is at most `lhs`, and less than it if `rhs` is not zero.
+                        let bound = self
+                            .operand_less_than(facts, lhs)
+                            .or_else(|| self.operand_bound(lhs).filter(|_| b.start > 0));
+                        (Some(range), bound)
+                    }
+                    BinOp::Mul | BinOp::MulUnchecked => {
+                        let end = a.end.checked_mul(b.end).filter(|&end| end <= max);
+                        (end.map(|end| Range::new(a.start * b.start, end)), None)
+                    }
+                    BinOp::Div if b.start > 0 => (
+                        Some(Range::new(a.start / b.end, a.end / b.start)),
+                        self.operand_less_than(facts, lhs),
+                    ),
+                    BinOp::Rem if b.start > 0 => (
+                        Some(Range::new(0, a.end.min(b.end - 1))),
+                        self.operand_bound(rhs).or_else(|| self.operand_less_than(facts, lhs)),
+                    ),
+                    BinOp::BitAnd => (
+                        Some(Range::new(0, a.end.min(b.end))),
+                        self.operand_less_than(facts, lhs)
+                            .or_else(|| self.operand_less_than(facts, rhs)),
+                    ),
+                    BinOp::Shr | BinOp::ShrUnchecked
+                        if b.start == b.end && b.end < u128::from(max.count_ones()) =>
+                    {
+                        (
+                            Some(Range::new(a.start >> b.end, a.end >> b.end)),
+                            self.operand_less_than(facts, lhs),
+                        )
+                    }
+                    _ => (None, None),
+                }
+            }
+            _ => (None, None),
+        }
+    }
+
+    /// Finds the comparison whose result is held by `cond` at the end of `block`.
+    fn find_comparison(
+        &self,
+        block: BasicBlock,
+        cond: Local,
+    ) -> Option<(BinOp, &Operand<'tcx>, &Operand<'tcx>)> {
+        // Look for the comparison in the block itself, checking that its operands are not
+        // modified before the terminator.
+        let mut assigned: Vec<Local> = Vec::new();
+        for statement in self.body.basic_blocks[block].statements.iter().rev() {
+            let StatementKind::Assign(box (place, ref rvalue)) = statement.kind else { continue };
+            if place.local == cond {
+                let Rvalue::BinaryOp(op, box (ref lhs, ref rhs)) = *rvalue else { return None };
+                let unchanged = |operand: &Operand<'tcx>| {
+                    operand.place().is_none_or(|place| !assigned.contains(&place.local))
+                };
+                return (place.as_local().is_some() && unchanged(lhs) && unchanged(rhs))
+                    .then_some((op, lhs, rhs));
+            }
+            assigned.push(place.local);
+        }
+
+        let (op, ref lhs, ref rhs) = *self.comparisons[cond].as_ref()?;
+        Some((op, lhs, rhs))
+    }
+
+    /// Whether the facts prove that `lhs op rhs` evaluates to `expected`.
+    fn proves(
+        &self,
+        facts: &Facts,
+        op: BinOp,
+        expected: bool,
+        lhs: &Operand<'tcx>,
+        rhs: &Operand<'tcx>,
+    ) -> bool {
+        let Some((relation, swap)) = relation(op, expected) else { return false };
+        let (lhs, rhs) = if swap { (rhs, lhs) } else { (lhs, rhs) };
+        let (Some(a), Some(b)) =
+            (self.operand_range_or_full(facts, lhs), self.operand_range_or_full(facts, rhs))
+        else {
+            return false;
+        };
+        match relation {
+            Relation::Lt => {
+                a.end < b.start
+                    || self
+                        .operand_less_than(facts, lhs)
+                        .is_some_and(|bound| self.operand_bound(rhs) == Some(bound))
+            }
+            Relation::Le => a.end <= b.start,
+            Relation::Eq => a.start == a.end && b.start == b.end && a.start == b.start,
+        }
+    }
+
+    /// Records that `lhs op rhs` evaluated to `holds`.
+    fn refine(
+        &self,
+        facts: &mut Facts,
+        op: BinOp,
+        holds: bool,
+        lhs: &Operand<'tcx>,
+        rhs: &Operand<'tcx>,
+    ) {
+        let Some((relation, swap)) = relation(op, holds) else { return };
+        let (lhs, rhs) = if swap { (rhs, lhs) } else { (lhs, rhs) };
+        let (Some(a), Some(b)) =
+            (self.operand_range_or_full(facts, lhs), self.operand_range_or_full(facts, rhs))
+        else {
+            return;
+        };
+        let (a, b) = match relation {
+            Relation::Lt => {
+                if let Some(local) = self.tracked_local(lhs)
+                    && let Some(bound) =
+                        self.operand_bound(rhs).or_else(|| self.operand_less_than(facts, rhs))
+                    && !bound.mentions(local)
+                {
+                    facts.less_than.insert(local, bound);
+                }
+                let Some(a_end) = b.end.checked_sub(1) else { return };
+                let Some(b_start) = a.start.checked_add(1) else { return };
+                (Range::new(a.start, a.end.min(a_end)), Range::new(b.start.max(b_start), b.end))
+            }
+            Relation::Le => {
+                if let Some(local) = self.tracked_local(lhs)
+                    && let Some(bound) = self.operand_less_than(facts, rhs)
+                    && !bound.mentions(local)
+                {
+                    facts.less_than.insert(local, bound);
+                }
+                (Range::new(a.start, a.end.min(b.end)), Range::new(b.start.max(a.start), b.end))
+            }
+            Relation::Eq => {
+                let range = Range::new(a.start.max(b.start), a.end.min(b.end));
+                (range, range)
+            }
+        };
+        // An empty range means that this edge cannot be taken, leave that to other passes.
+        if a.start > a.end || b.start > b.end {
+            return;
+        }
+        if let Some(local) = self.tracked_local(lhs) {
+            facts.set_range(local, a);
+        }
+        if let Some(local) = self.tracked_local(rhs) {
+            facts.set_range(local, b);
+        }
+    }
+
+    /// The range of the value returned by the function called by `func`, from its summary.
+    fn callee_return_range(&self, func: &Operand<'tcx>) -> Option<Range> {
+        if !self.use_summaries {
+            return None;
+        }
+        let (def_id, args) = func.const_fn_def()?;
+        let instance = Instance::try_resolve(self.tcx, self.param_env, def_id, args).ok()??;
+        let InstanceKind::Item(callee) = instance.def else { return None };
+        if !matches!(self.tcx.def_kind(callee), DefKind::Fn | DefKind::AssocFn)
+            || self.tcx.intrinsic(callee).is_some()
+            || !self.tcx.is_mir_available(callee)
+        {
+            return None;
+        }
+
+        // Summaries are not encoded in the crate metadata, so only local callees have one.
+        let callee = callee.as_local()?;
+        let caller = self.body.source.def_id().as_local()?;
+        if callee == caller {
+            return None;
+        }
+        // Avoid query cycles the same way the inliner does: only look at the MIR of the callee if
+        // it cannot look at ours.
+        if self.tcx.def_path_hash(caller.to_def_id()).local_hash()
+            >= self.tcx.def_path_hash(callee.to_def_id()).local_hash()
+            && self.tcx.mir_callgraph_reachable((instance, caller))
+        {
+            return None;
+        }
+
+        let range = self.tcx.return_value_range(callee)?;
+        Some(Range::new(range.start, range.end))
+    }
+}
+
+impl<'tcx> AnalysisDomain<'tcx> for RangeAnalysis<'_, 'tcx> {
+    type Domain = RangeState;
+
+    const NAME: &'static str = "RangeAnalysis";
+
+    fn bottom_value(&self, _: &Body<'tcx>) -> Self::Domain {
+        RangeState::Unreachable
+    }
+
+    fn initialize_start_block(&self, _: &Body<'tcx>, state: &mut Self::Domain) {
+        *state = RangeState::Reachable(Facts::default());
+    }
+}
+
+impl<'tcx> Analysis<'tcx> for RangeAnalysis<'_, 'tcx> {
+    fn apply_statement_effect(
+        &mut self,
+        state: &mut Self::Domain,
+        statement: &Statement<'tcx>,
+        _: Location,
+    ) {
+        let RangeState::Reachable(facts) = state else { return };
+        match statement.kind {
+            StatementKind::Assign(box (place, ref rvalue)) => {
+                let Some(local) = place.as_local() else {
+                    facts.kill(place.local);
+                    return;
+                };
+                let result = self
+                    .type_max(self.body.local_decls[local].ty)
+                    .filter(|_| self.tracked.contains(local))
+                    .map(|max| self.eval_rvalue(facts, rvalue, max));
+                facts.kill(local);
+                if let Some((range, bound)) = result {
+                    if let Some(range) = range {
+                        facts.set_range(local, range);
+                    }
+                    if let Some(bound) = bound
+                        && !bound.mentions(local)
+                    {
+                        facts.less_than.insert(local, bound);
+                    }
+                }
+            }
+            StatementKind::SetDiscriminant { box place, .. } | StatementKind::Deinit(box place) => {
+                facts.kill(place.local)
+            }
+            StatementKind::StorageLive(local) | StatementKind::StorageDead(local) => {
+                facts.kill(local)
+            }
+            _ => {}
+        }
+    }
+
+    fn apply_terminator_effect<'mir>(
+        &mut self,
+        state: &mut Self::Domain,
+        terminator: &'mir Terminator<'tcx>,
+        location: Location,
+    ) -> TerminatorEdges<'mir, 'tcx> {
+        if let RangeState::Reachable(facts) = state {
+            match terminator.kind {
+                // The condition holds after the assertion. This is not the case on the unwind
+                // edge, which is why bounds checks in cleanup blocks are never removed.
+                TerminatorKind::Assert { ref cond, expected, .. } => {
+                    if let Some(cond) = cond.place().and_then(|cond| cond.as_local())
+                        && let Some((op, lhs, rhs)) = self.find_comparison(location.block, cond)
+                    {
+                        self.refine(facts, op, expected, lhs, rhs);
+                    }
+                }
+                // The destination is only written on the return edge, but it must not keep its
+                // facts on the unwind edge.
+                TerminatorKind::Call { destination: place, .. }
+                | TerminatorKind::Drop { place, .. }
+                | TerminatorKind::Yield { resume_arg: place, .. } => facts.kill(place.local),
+                TerminatorKind::InlineAsm { ref operands, .. } => {
+                    CallReturnPlaces::InlineAsm(operands).for_each(|place| facts.kill(place.local));
+                }
+                _ => {}
+            }
+        }
+        terminator.edges()
+    }
+
+    fn apply_call_return_effect(
+        &mut self,
+        state: &mut Self::Domain,
+        block: BasicBlock,
+        return_places: CallReturnPlaces<'_, 'tcx>,
+    ) {
+        let RangeState::Reachable(facts) = state else { return };
+        return_places.for_each(|place| facts.kill(place.local));
+
+        if let CallReturnPlaces::Call(destination) = return_places
+            && let Some(local) = destination.as_local()
+            && self.tracked.contains(local)
+            && let TerminatorKind::Call { ref func, .. } =
+                self.body.basic_blocks[block].terminator().kind
+            && let Some(range) = self.callee_return_range(func)
+        {
+            facts.set_range(local, range);
+        }
+    }
+
+    fn apply_switch_int_edge_effects(
+        &mut self,
+        block: BasicBlock,
+        discr: &Operand<'tcx>,
+        apply_edge_effects: &mut impl SwitchIntEdgeEffects<Self::Domain>,
+    ) {
+        let Some(cond) = discr.place().and_then(|discr| discr.as_local()) else { return };
+        if !self.body.local_decls[cond].ty.is_bool() {
+            return;
+        }
+        let Some((op, lhs, rhs)) = self.find_comparison(block, cond) else { return };
+        let TerminatorKind::SwitchInt { ref targets, .. } =
+            self.body.basic_blocks[block].terminator().kind
+        else {
+            bug!()
+        };
+
+        apply_edge_effects.apply(|state, edge| {
+            let RangeState::Reachable(facts) = state else { return };
+            // The `otherwise` edge is taken when the condition is true iff the only other
+            // value is `false`.
+            let holds = match edge.value {
+                Some(value) => value != 0,
+                None => targets.iter().all(|(value, _)| value == 0),
+            };
+            self.refine(facts, op, holds, lhs, rhs);
+        });
+    }
+}
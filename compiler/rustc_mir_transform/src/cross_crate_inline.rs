@@ -29,6 +29,12 @@ fn cross_crate_inlinable(tcx: TyCtxt<'_>, def_id: LocalDefId) -> bool {
         _ => return false,
     }
 
+    // Functions generated by another codegen backend must not be copied into the codegen units
+    // of their callers.
+    if codegen_fn_attrs.codegen_backend.is_some() {
+        return false;
+    }
+
     // From this point on, it is valid to return true or false.
     if tcx.sess.opts.unstable_opts.cross_crate_inline_threshold == InliningThreshold::Always {
         return true;
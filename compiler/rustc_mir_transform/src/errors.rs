@@ -1,3 +1,5 @@
+use std::path::Path;
+
 use rustc_errors::codes::*;
 use rustc_errors::{Diag, LintDiagnostic};
 use rustc_macros::{Diagnostic, LintDiagnostic, Subdiagnostic};
@@ -38,6 +40,23 @@ pub(crate) struct UnalignedPackedRef {
     pub span: Span,
 }
 
+#[derive(Diagnostic)]
+#[diag(mir_transform_bounds_check_survived)]
+#[note]
+pub(crate) struct BoundsCheckSurvived {
+    #[primary_span]
+    pub span: Span,
+    pub index: String,
+    pub length: String,
+}
+
+#[derive(Diagnostic)]
+#[diag(mir_transform_unreadable_profile)]
+pub(crate) struct UnreadableProfile<'a> {
+    pub path: &'a Path,
+    pub error: String,
+}
+
 pub(crate) struct AssertLint<P> {
     pub span: Span,
     pub assert_kind: AssertKind<P>,
@@ -10,6 +10,7 @@
 use rustc_index::Idx;
 use rustc_middle::bug;
 use rustc_middle::middle::codegen_fn_attrs::{CodegenFnAttrFlags, CodegenFnAttrs};
+use rustc_middle::middle::instr_profile::{Hotness, InstrProfile};
 use rustc_middle::mir::visit::*;
 use rustc_middle::mir::*;
 use rustc_middle::ty::{
@@ -29,6 +30,7 @@
 use crate::validate::validate_types;
 
 pub(crate) mod cycle;
+pub(crate) mod profile;
 
 const TOP_DOWN_DEPTH_LIMIT: usize = 5;
 
@@ -40,6 +42,9 @@ struct CallSite<'tcx> {
     fn_sig: ty::PolyFnSig<'tcx>,
     block: BasicBlock,
     source_info: SourceInfo,
+    /// How often the call site was executed, according to the profile passed with
+    /// `-C profile-use`.
+    hotness: Hotness,
 }
 
 impl<'tcx> MirPass<'tcx> for Inline {
@@ -96,10 +101,21 @@ fn inline<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) -> bool {
     let param_env = tcx.param_env_reveal_all_normalized(def_id);
     let codegen_fn_attrs = tcx.codegen_fn_attrs(def_id);
 
+    // Generic functions don't have a symbol name of their own, so the profile is only used when
+    // inlining into functions that are not generic.
+    let profile = tcx.instr_profile(()).as_ref().and_then(|profile| {
+        if tcx.generics_of(def_id).requires_monomorphization(tcx) {
+            return None;
+        }
+        let symbol_name = tcx.symbol_name(Instance::mono(tcx, def_id.to_def_id()));
+        Some((profile, profile.function_hotness(symbol_name.name)?))
+    });
+
     let mut this = Inliner {
         tcx,
         param_env,
         codegen_fn_attrs,
+        profile,
         history: Vec::new(),
         changed: false,
         caller_is_inline_forwarder: matches!(
@@ -117,6 +133,9 @@ struct Inliner<'tcx> {
     param_env: ParamEnv<'tcx>,
     /// Caller codegen attributes.
     codegen_fn_attrs: &'tcx CodegenFnAttrs,
+    /// The profile passed with `-C profile-use` and the hotness of the caller in it, if it has
+    /// any counts for the caller.
+    profile: Option<(&'tcx InstrProfile, Hotness)>,
     /// Stack of inlined instances.
     /// We only check the `DefId` and not the args because we want to
     /// avoid inlining cases of polymorphic recursion.
@@ -190,6 +209,11 @@ fn try_inlining(
         let cross_crate_inlinable = self.tcx.cross_crate_inlinable(callsite.callee.def_id());
         self.check_codegen_attributes(callsite, callee_attrs, cross_crate_inlinable)?;
 
+        // The profile only overrides the heuristics, not what the user asked for.
+        if callsite.hotness == Hotness::Cold && callee_attrs.inline != InlineAttr::Always {
+            return Err("call site is cold in the profile");
+        }
+
         // Intrinsic fallback bodies are automatically made cross-crate inlineable,
         // but at this stage we don't know whether codegen knows the intrinsic,
         // so just conservatively don't inline it.
@@ -422,14 +446,36 @@ fn resolve_callsite(
                 }
 
                 let source_info = SourceInfo { span: fn_span, ..terminator.source_info };
+                let hotness = self.callsite_hotness(callee);
 
-                return Some(CallSite { callee, fn_sig, block: bb, source_info });
+                return Some(CallSite { callee, fn_sig, block: bb, source_info, hotness });
             }
         }
 
         None
     }
 
+    /// Returns the hotness of a call to `callee` from the caller, according to the profile passed
+    /// with `-C profile-use`. The profile doesn't count the calls of each call site, so a call
+    /// site is cold if the caller or the callee is, and hot if the caller is hot and the callee
+    /// is not known to be executed less often.
+    fn callsite_hotness(&self, callee: Instance<'tcx>) -> Hotness {
+        let Some((profile, caller_hotness)) = self.profile else {
+            return Hotness::Normal;
+        };
+        if caller_hotness == Hotness::Cold {
+            return Hotness::Cold;
+        }
+        // The caller is not generic, so the arguments of the callee are known and it has a symbol
+        // name, even if it is an instance of a generic function.
+        let symbol_name = self.tcx.symbol_name(callee);
+        match (caller_hotness, profile.function_hotness(symbol_name.name)) {
+            (_, Some(Hotness::Cold)) => Hotness::Cold,
+            (Hotness::Hot, Some(Hotness::Hot) | None) => Hotness::Hot,
+            _ => Hotness::Normal,
+        }
+    }
+
     /// Returns an error if inlining is not possible based on codegen attributes alone. A success
     /// indicates that inlining decision should be based on other criteria.
     fn check_codegen_attributes(
@@ -479,6 +525,14 @@ fn check_codegen_attributes(
             return Err("incompatible instruction set");
         }
 
+        // The code of the callee must stay with the codegen backend that `#[codegen_backend]`
+        // selects for it.
+        if callee_attrs.codegen_backend.is_some()
+            && callee_attrs.codegen_backend != self.codegen_fn_attrs.codegen_backend
+        {
+            return Err("incompatible codegen backend");
+        }
+
         let callee_feature_names = callee_attrs.target_features.iter().map(|f| f.name);
         let this_feature_names = self.codegen_fn_attrs.target_features.iter().map(|f| f.name);
         if callee_feature_names.ne(this_feature_names) {
@@ -517,6 +571,12 @@ fn check_mir_body(
             self.tcx.sess.opts.unstable_opts.inline_mir_threshold.unwrap_or(50)
         };
 
+        // Hot call sites are worth inlining larger callees into.
+        if callsite.hotness == Hotness::Hot {
+            threshold =
+                threshold.max(tcx.sess.opts.unstable_opts.inline_mir_hot_threshold.unwrap_or(200));
+        }
+
         // Give a bonus functions with a small number of blocks,
         // We normally have two or three blocks for even
         // very small functions.
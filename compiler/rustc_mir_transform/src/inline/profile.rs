@@ -0,0 +1,203 @@
+//! Reads the instrumentation profile passed with `-C profile-use`, so that the inliner can favour
+//! the call sites that are hot in the profiled runs and skip the ones that never ran.
+//!
+//! The profile is in LLVM's indexed format, as written by `llvm-profdata merge`. It is read here
+//! rather than by the codegen backend so that backends without PGO support benefit from it too.
+//! The counters of IR-level instrumentation are placed on the edges of the LLVM control-flow
+//! graph, which cannot be mapped back to MIR call sites, so only the largest count of each
+//! function is kept, see [`InstrProfile`].
+
+use std::fs;
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_middle::middle::instr_profile::InstrProfile;
+use rustc_middle::ty::TyCtxt;
+
+use crate::errors;
+
+/// `IndexedInstrProf::Magic`, the string `"\xfflprofi\x81"`.
+const MAGIC: u64 = 0x8169666f72706cff;
+
+/// The variant flag of profiles with context-sensitive counts, which have a second summary.
+const VARIANT_MASK_CSIR_PROF: u64 = 1 << 57;
+
+/// The percentiles of the counts that LLVM uses to tell hot and cold counts apart, see
+/// `ProfileSummaryCutoffHot` and `ProfileSummaryCutoffCold`.
+const HOT_CUTOFF: u64 = 990_000;
+const COLD_CUTOFF: u64 = 999_999;
+
+pub(crate) fn instr_profile(tcx: TyCtxt<'_>, (): ()) -> Option<InstrProfile> {
+    let path = tcx.sess.opts.cg.profile_use.as_deref()?;
+    let profile = fs::read(path).map_err(|err| err.to_string()).and_then(|data| {
+        let mut reader = Reader { data: &data, pos: 0 };
+        read_indexed_profile(&mut reader)
+    });
+    match profile {
+        Ok(profile) => Some(profile),
+        Err(error) => {
+            tcx.dcx().emit_warn(errors::UnreadableProfile { path, error });
+            None
+        }
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: u64) -> Result<&'a [u8], String> {
+        let end = usize::try_from(len)
+            .ok()
+            .and_then(|len| self.pos.checked_add(len))
+            .filter(|&end| end <= self.data.len())
+            .ok_or("unexpected end of file")?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u16(&mut self) -> Result<u16, String> {
+        Ok(u16::from_le_bytes(self.bytes(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_le_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, String> {
+        Ok(u64::from_le_bytes(self.bytes(8)?.try_into().unwrap()))
+    }
+
+    fn seek(&mut self, pos: u64) -> Result<(), String> {
+        self.pos = 0;
+        self.bytes(pos)?;
+        Ok(())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos == self.data.len()
+    }
+}
+
+fn read_indexed_profile(reader: &mut Reader<'_>) -> Result<InstrProfile, String> {
+    if reader.u64()? != MAGIC {
+        return Err(
+            "not an indexed profile, it should be converted with `llvm-profdata merge`".to_string()
+        );
+    }
+    let variant = reader.u64()?;
+    let version = variant & 0xffff_ffff;
+    if !(5..=12).contains(&version) {
+        return Err(format!("unsupported version {version}"));
+    }
+    // The field that became unused in version 4, and the type of the hash of the names.
+    reader.u64()?;
+    reader.u64()?;
+    let hash_offset = reader.u64()?;
+    // The offsets of the sections that were added after version 7, which are not needed here.
+    let later_fields = match version {
+        8 => 1,
+        9 => 2,
+        10 | 11 => 3,
+        12 => 4,
+        _ => 0,
+    };
+    reader.bytes(later_fields * 8)?;
+
+    let (hot_count_threshold, cold_count_threshold) = read_summary(reader)?;
+    if variant & VARIANT_MASK_CSIR_PROF != 0 {
+        read_summary(reader)?;
+    }
+
+    // The functions are stored in an `OnDiskIterableChainedHashTable`, keyed by their names.
+    // The items of all buckets come first, and are followed by the buckets, which start with
+    // the total number of items.
+    let items = reader.pos;
+    reader.seek(hash_offset)?;
+    let _buckets = reader.u64()?;
+    let mut items_left = reader.u64()?;
+    reader.pos = items;
+
+    let mut max_counts = FxHashMap::default();
+    while items_left > 0 {
+        let items_in_bucket = reader.u16()?;
+        for _ in 0..items_in_bucket {
+            items_left = items_left.checked_sub(1).ok_or("too many functions")?;
+            // The hash of the name.
+            reader.u64()?;
+            let name_len = reader.u64()?;
+            let data_len = reader.u64()?;
+            let name = reader.bytes(name_len)?;
+            let mut data = Reader { data: reader.bytes(data_len)?, pos: 0 };
+            let max_count = read_max_count(&mut data, version)?;
+
+            // The names of functions with internal linkage are prefixed with the name of their
+            // source file.
+            let name = String::from_utf8_lossy(name);
+            let name = name.rsplit_once(';').map_or(&*name, |(_, name)| name);
+            let count = max_counts.entry(name.to_string()).or_insert(0);
+            *count = max_count.max(*count);
+        }
+    }
+
+    Ok(InstrProfile {
+        max_counts,
+        hot_count_threshold: hot_count_threshold.max(1),
+        cold_count_threshold,
+    })
+}
+
+/// Reads an `IndexedInstrProf::Summary`, and returns the hot and cold count thresholds it
+/// describes.
+fn read_summary(reader: &mut Reader<'_>) -> Result<(u64, u64), String> {
+    let fields = reader.u64()?;
+    let entries = reader.u64()?;
+    reader.bytes(fields.checked_mul(8).ok_or("invalid summary")?)?;
+
+    let mut hot = None;
+    let mut cold = None;
+    // The entries are sorted by cutoff, and give the smallest count of the hottest counters
+    // that make up the given percentile of the total count.
+    for _ in 0..entries {
+        let cutoff = reader.u64()?;
+        let min_count = reader.u64()?;
+        let _counters = reader.u64()?;
+        if cutoff >= HOT_CUTOFF && hot.is_none() {
+            hot = Some(min_count);
+        }
+        if cutoff >= COLD_CUTOFF && cold.is_none() {
+            cold = Some(min_count);
+        }
+    }
+    Ok((hot.unwrap_or(u64::MAX), cold.unwrap_or(0)))
+}
+
+/// Reads the records of a function, one for each hash of its control-flow graph, and returns
+/// its largest count.
+fn read_max_count(data: &mut Reader<'_>, version: u64) -> Result<u64, String> {
+    let mut max_count = 0;
+    while !data.is_empty() {
+        // The hash of the control-flow graph.
+        data.u64()?;
+        let counters = data.u64()?;
+        for _ in 0..counters {
+            max_count = data.u64()?.max(max_count);
+        }
+        if version > 10 {
+            // The bitmap of MC/DC coverage, each byte being stored in a `u64`.
+            let bitmap_bytes = data.u64()?;
+            data.bytes(bitmap_bytes.checked_mul(8).ok_or("invalid bitmap")?)?;
+        }
+        // The value profile, which starts with its total size.
+        let start = data.pos;
+        let size = data.u32()?;
+        if size < 8 {
+            return Err("invalid value profile".to_string());
+        }
+        data.pos = start;
+        data.bytes(size.into())?;
+    }
+    Ok(max_count)
+}
@@ -48,6 +48,7 @@
 mod add_moves_for_packed_drops;
 mod add_retag;
 mod add_subtyping_projections;
+mod bounds_check_elimination;
 mod check_alignment;
 mod check_const_item_mutation;
 mod check_packed_ref;
@@ -79,6 +80,7 @@
 mod known_panics_lint;
 mod large_enums;
 mod lint;
+mod loop_invariant_code_motion;
 mod lower_intrinsics;
 mod lower_slice_len;
 mod match_branches;
@@ -110,6 +112,7 @@
 
 use rustc_const_eval::check_consts::{self, ConstCx};
 use rustc_mir_dataflow::rustc_peek;
+pub use validate::validate_body;
 
 rustc_fluent_macro::fluent_messages! { "../messages.ftl" }
 
@@ -131,8 +134,10 @@ pub fn provide(providers: &mut Providers) {
         is_ctfe_mir_available: |tcx, did| is_mir_available(tcx, did),
         mir_callgraph_reachable: inline::cycle::mir_callgraph_reachable,
         mir_inliner_callees: inline::cycle::mir_inliner_callees,
+        instr_profile: inline::profile::instr_profile,
         promoted_mir,
         deduced_param_attrs: deduce_param_attrs::deduced_param_attrs,
+        return_value_range: bounds_check_elimination::return_value_range,
         coroutine_by_move_body_def_id: coroutine::coroutine_by_move_body_def_id,
         ..providers.queries
     };
@@ -599,8 +604,11 @@ fn o1<T>(x: T) -> WithMinOptLevel<T> {
             &single_use_consts::SingleUseConsts,
             &o1(simplify_branches::SimplifyConstCondition::AfterConstProp),
             &jump_threading::JumpThreading,
+            &bounds_check_elimination::BoundsCheckElimination,
             &early_otherwise_branch::EarlyOtherwiseBranch,
             &simplify_comparison_integral::SimplifyComparisonIntegral,
+            // After GVN and const propagation, so that we hoist the simplified computations.
+            &loop_invariant_code_motion::LoopInvariantCodeMotion,
             &dest_prop::DestinationPropagation,
             &o1(simplify_branches::SimplifyConstCondition::Final),
             &o1(remove_noop_landing_pads::RemoveNoopLandingPads),
@@ -0,0 +1,451 @@
+//! Loop-invariant code motion.
+//!
+//! This pass moves computations whose value does not change between iterations of a loop to a
+//! new block that is executed once, before entering the loop:
+//!
+//! ```ignore (MIR)
+//! // Before:
+//! bb1: {
+//!     switchInt(copy _3) -> [0: bb3, otherwise: bb2];
+//! }
+//! bb2: {
+//!     _5 = Mul(copy _1, copy _2);
+//!     _4 = Add(copy _4, copy _5);
+//!     goto -> bb1;
+//! }
+//!
+//! // After:
+//! bb4: {
+//!     _5 = Mul(copy _1, copy _2);
+//!     goto -> bb1;
+//! }
+//! bb1: {
+//!     switchInt(copy _3) -> [0: bb3, otherwise: bb2];
+//! }
+//! bb2: {
+//!     _4 = Add(copy _4, copy _5);
+//!     goto -> bb1;
+//! }
+//! ```
+//!
+//! LLVM already does this, so this mostly helps the code generated by backends that do not, and
+//! reduces the work done by the MIR interpreter.
+//!
+//! The pass is opt-in: it only runs with `-Zmir-opt-level=3` or higher, and is not part of the
+//! default pipeline in debug nor in release builds.
+//!
+//! Loops are found using the dominator tree: an edge `latch -> header` such that `header`
+//! dominates `latch` is a back edge, and the loop is made of `header` and all the blocks that can
+//! reach `latch` without going through `header`. Loops sharing a header are merged. Loops are
+//! processed from the innermost to the outermost, so that a statement can be moved out of several
+//! nested loops.
+//!
+//! The hoisted statements may not have been executed at all in the original program, for instance
+//! if they were on a branch inside the loop, or if the loop does not run a single iteration. We
+//! thus only hoist statements that cannot have any side effect nor cause UB:
+//! - the statement assigns to an SSA local which is never moved from nor borrowed, so that the
+//!   value it holds is the same at each of its uses, wherever it is computed;
+//! - the rvalue is an arithmetic operation that cannot be UB, such as a wrapping addition
+//!   but not a division, a cast that cannot fail, or a read;
+//! - each read place is either an invariant local or one of its fields, or a field of the
+//!   pointee of an SSA argument of type `&T` with `T: Freeze`, which is dereferenceable and
+//!   immutable for the whole execution of the function;
+//! - a local is invariant if it is not assigned to in the loop, its SSA assignment dominates the
+//!   loop header, and it cannot be storage-dead when entering the loop, according to the
+//!   `MaybeStorageDead` dataflow analysis. Locals that have already been hoisted out of the same
+//!   loop are invariant too.
+//!
+//! Storage liveness is used rather than the liveness of `MaybeLiveLocals`. Liveness tells whether
+//! a value may still be read later, while we need to know whether the value of a local is still
+//! available when entering the loop. An SSA local holds the value of its single assignment for as
+//! long as its storage is live, so `MaybeStorageDead` answers exactly that question.
+//!
+//! As the hoisted locals are now assigned to before the loop, their storage markers are removed.
+
+use std::borrow::Cow;
+
+use rustc_data_structures::fx::{FxIndexMap, FxIndexSet};
+use rustc_index::bit_set::BitSet;
+use rustc_middle::mir::visit::*;
+use rustc_middle::mir::*;
+use rustc_middle::ty::{self, ParamEnv, TyCtxt};
+use rustc_mir_dataflow::impls::MaybeStorageDead;
+use rustc_mir_dataflow::storage::always_storage_live_locals;
+use rustc_mir_dataflow::Analysis;
+use tracing::{debug, instrument};
+
+use crate::ssa::{SsaLocals, StorageLiveLocals};
+
+pub struct LoopInvariantCodeMotion;
+
+impl<'tcx> MirPass<'tcx> for LoopInvariantCodeMotion {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        // Opt-in for now, until the pass has seen more testing.
+        sess.mir_opt_level() >= 3
+    }
+
+    #[instrument(level = "trace", skip(self, tcx, body))]
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        debug!(def_id = ?body.source.def_id());
+
+        let loops = find_loops(body);
+        if loops.is_empty() {
+            return;
+        }
+        debug!(?loops);
+
+        let hoisted = find_invariant_statements(tcx, body, &loops);
+        if hoisted.is_empty() {
+            return;
+        }
+        debug!(?hoisted);
+
+        apply_hoisting(body, &loops, hoisted);
+    }
+}
+
+#[derive(Debug)]
+struct Loop {
+    header: BasicBlock,
+    blocks: BitSet<BasicBlock>,
+}
+
+/// Find the natural loops of `body`, innermost loops first.
+fn find_loops(body: &Body<'_>) -> Vec<Loop> {
+    let dominators = body.basic_blocks.dominators();
+    let predecessors = body.basic_blocks.predecessors();
+
+    let mut loops: FxIndexMap<BasicBlock, BitSet<BasicBlock>> = FxIndexMap::default();
+    for &latch in body.basic_blocks.reverse_postorder() {
+        for header in body.basic_blocks[latch].terminator().successors() {
+            if !dominators.dominates(header, latch) {
+                continue;
+            }
+
+            let blocks = loops.entry(header).or_insert_with(|| {
+                let mut blocks = BitSet::new_empty(body.basic_blocks.len());
+                blocks.insert(header);
+                blocks
+            });
+            let mut worklist = vec![latch];
+            while let Some(block) = worklist.pop() {
+                if blocks.insert(block) {
+                    worklist.extend(
+                        predecessors[block].iter().filter(|&&pred| dominators.is_reachable(pred)),
+                    );
+                }
+            }
+        }
+    }
+
+    let mut loops: Vec<Loop> = loops
+        .into_iter()
+        // We cannot insert a block before the start block, and cleanup blocks cannot jump to
+        // a normal block.
+        .filter(|&(header, _)| header != START_BLOCK && !body.basic_blocks[header].is_cleanup)
+        .map(|(header, blocks)| Loop { header, blocks })
+        .collect();
+    // An inner loop is a strict subset of the loops around it.
+    loops.sort_by_cached_key(|l| l.blocks.count());
+    loops
+}
+
+/// Compute the statements to hoist, in the order they must be executed, along with the index of
+/// the outermost loop they can be hoisted out of.
+fn find_invariant_statements<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    loops: &[Loop],
+) -> FxIndexMap<Location, usize> {
+    let param_env = tcx.param_env_reveal_all_normalized(body.source.def_id());
+    let ssa = SsaLocals::new(tcx, body, param_env);
+    let always_live_locals = always_storage_live_locals(body);
+    let storage_live = StorageLiveLocals::new(body, &always_live_locals);
+    let mut maybe_dead = MaybeStorageDead::new(Cow::Owned(always_live_locals))
+        .into_engine(tcx, body)
+        .iterate_to_fixpoint()
+        .into_results_cursor(body);
+
+    let mut moved_locals = MovedLocals(BitSet::new_empty(body.local_decls.len()));
+    moved_locals.visit_body(body);
+
+    let finder = InvariantFinder {
+        tcx,
+        body,
+        param_env,
+        ssa: &ssa,
+        storage_live: &storage_live,
+        moved_locals: &moved_locals.0,
+    };
+
+    let mut hoisted: FxIndexMap<Location, usize> = FxIndexMap::default();
+    for (index, lp) in loops.iter().enumerate() {
+        maybe_dead.seek_to_block_start(lp.header);
+
+        let mut assigned = AssignedLocals {
+            blocks: &lp.blocks,
+            locals: BitSet::new_empty(body.local_decls.len()),
+        };
+        assigned.visit_body(body);
+
+        let mut state = LoopState {
+            header: lp.header,
+            assigned: assigned.locals,
+            storage_dead: maybe_dead.get().clone(),
+            hoisted: BitSet::new_empty(body.local_decls.len()),
+        };
+
+        // Statements already hoisted out of inner loops come first, as they may be needed by the
+        // statements of this loop.
+        let mut candidates: FxIndexSet<Location> = hoisted
+            .iter()
+            .filter(|&(_, &inner)| lp.blocks.contains(loops[inner].header))
+            .map(|(&location, _)| location)
+            .collect();
+        for &bb in body.basic_blocks.reverse_postorder() {
+            if lp.blocks.contains(bb) && !body.basic_blocks[bb].is_cleanup {
+                for statement_index in 0..body.basic_blocks[bb].statements.len() {
+                    candidates.insert(Location { block: bb, statement_index });
+                }
+            }
+        }
+
+        // Hoisting a statement may make other statements invariant, so iterate to fixpoint.
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &location in &candidates {
+                if hoisted.get(&location) == Some(&index) {
+                    continue;
+                }
+                let StatementKind::Assign(box (dest, ref rvalue)) =
+                    body.basic_blocks[location.block].statements[location.statement_index].kind
+                else {
+                    continue;
+                };
+                if finder.can_hoist(&state, dest, rvalue) {
+                    // Re-insert the statement, so it comes after the statements it depends on.
+                    hoisted.shift_remove(&location);
+                    hoisted.insert(location, index);
+                    state.hoisted.insert(dest.local);
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    hoisted
+}
+
+struct LoopState {
+    header: BasicBlock,
+    /// Locals that are assigned to inside the loop.
+    assigned: BitSet<Local>,
+    /// Locals that may be storage-dead when entering the loop header.
+    storage_dead: BitSet<Local>,
+    /// Locals whose assignment is hoisted out of this loop.
+    hoisted: BitSet<Local>,
+}
+
+struct InvariantFinder<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    body: &'a Body<'tcx>,
+    param_env: ParamEnv<'tcx>,
+    ssa: &'a SsaLocals,
+    storage_live: &'a StorageLiveLocals,
+    moved_locals: &'a BitSet<Local>,
+}
+
+impl<'tcx> InvariantFinder<'_, 'tcx> {
+    fn can_hoist(&self, state: &LoopState, dest: Place<'tcx>, rvalue: &Rvalue<'tcx>) -> bool {
+        let Some(dest) = dest.as_local() else { return false };
+        if dest == RETURN_PLACE
+            || !self.ssa.is_ssa(dest)
+            || self.ssa.borrowed_locals().contains(dest)
+            || self.moved_locals.contains(dest)
+        {
+            return false;
+        }
+
+        match *rvalue {
+            Rvalue::Use(ref operand)
+            | Rvalue::UnaryOp(UnOp::Not | UnOp::Neg | UnOp::PtrMetadata, ref operand)
+            | Rvalue::Cast(
+                CastKind::IntToInt
+                | CastKind::IntToFloat
+                | CastKind::FloatToInt
+                | CastKind::FloatToFloat
+                | CastKind::PtrToPtr
+                | CastKind::FnPtrToPtr,
+                ref operand,
+                _,
+            ) => self.is_invariant_operand(state, operand),
+            Rvalue::BinaryOp(op, box (ref lhs, ref rhs)) => {
+                // These may be UB depending on the value of their operands.
+                let may_be_ub = matches!(
+                    op,
+                    BinOp::Div
+                        | BinOp::Rem
+                        | BinOp::AddUnchecked
+                        | BinOp::SubUnchecked
+                        | BinOp::MulUnchecked
+                        | BinOp::ShlUnchecked
+                        | BinOp::ShrUnchecked
+                        | BinOp::Offset
+                );
+                !may_be_ub
+                    && self.is_invariant_operand(state, lhs)
+                    && self.is_invariant_operand(state, rhs)
+            }
+            Rvalue::Len(place) | Rvalue::Discriminant(place) => {
+                self.is_invariant_place(state, place)
+            }
+            Rvalue::NullaryOp(..) => true,
+            _ => false,
+        }
+    }
+
+    fn is_invariant_operand(&self, state: &LoopState, operand: &Operand<'tcx>) -> bool {
+        match *operand {
+            Operand::Constant(_) => true,
+            Operand::Copy(place) => self.is_invariant_place(state, place),
+            // Hoisting a move would invalidate the local in the following iterations.
+            Operand::Move(_) => false,
+        }
+    }
+
+    fn is_invariant_place(&self, state: &LoopState, place: Place<'tcx>) -> bool {
+        let fields = match place.projection[..] {
+            [ProjectionElem::Deref, ref fields @ ..] => {
+                let is_arg = (1..=self.body.arg_count).contains(&place.local.as_usize());
+                let ty = self.body.local_decls[place.local].ty;
+                let ty::Ref(_, pointee, Mutability::Not) = *ty.kind() else { return false };
+                if !is_arg || !pointee.is_freeze(self.tcx, self.param_env) {
+                    return false;
+                }
+                fields
+            }
+            ref fields => fields,
+        };
+        if !fields.iter().all(|elem| matches!(elem, ProjectionElem::Field(..))) {
+            return false;
+        }
+        // Reading the field of a union may produce an invalid value if that field is not the
+        // active one.
+        if place.iter_projections().any(|(base, _)| base.ty(self.body, self.tcx).ty.is_union()) {
+            return false;
+        }
+        self.is_invariant_local(state, place.local)
+    }
+
+    fn is_invariant_local(&self, state: &LoopState, local: Local) -> bool {
+        if state.hoisted.contains(local) {
+            return true;
+        }
+        let header_start = Location { block: state.header, statement_index: 0 };
+        !state.assigned.contains(local)
+            && self.ssa.assignment_dominates(
+                self.body.basic_blocks.dominators(),
+                local,
+                header_start,
+            )
+            // With a single `StorageLive`, the local cannot be storage-dead between its
+            // assignment and the loop if it is not storage-dead when entering the loop.
+            && self.storage_live.has_single_storage(local)
+            && !state.storage_dead.contains(local)
+    }
+}
+
+/// Move the statements in `hoisted` to a new preheader block for each loop.
+fn apply_hoisting<'tcx>(
+    body: &mut Body<'tcx>,
+    loops: &[Loop],
+    hoisted: FxIndexMap<Location, usize>,
+) {
+    let predecessors = body.basic_blocks.predecessors().clone();
+
+    let mut hoisted_locals = BitSet::new_empty(body.local_decls.len());
+    let mut preheader_statements = vec![vec![]; loops.len()];
+    for (location, index) in hoisted {
+        let statement = body.basic_blocks.as_mut_preserves_cfg()[location.block].statements
+            [location.statement_index]
+            .replace_nop();
+        if let StatementKind::Assign(box (dest, _)) = statement.kind {
+            hoisted_locals.insert(dest.local);
+        }
+        preheader_statements[index].push(statement);
+    }
+
+    for (lp, statements) in loops.iter().zip(preheader_statements) {
+        if statements.is_empty() {
+            continue;
+        }
+        let source_info = body.basic_blocks[lp.header].terminator().source_info;
+        let preheader = body.basic_blocks_mut().push(BasicBlockData {
+            statements,
+            terminator: Some(Terminator {
+                source_info,
+                kind: TerminatorKind::Goto { target: lp.header },
+            }),
+            is_cleanup: false,
+        });
+        debug!(?lp.header, ?preheader);
+
+        // Redirect the edges that enter the loop. The other predecessors of the header are the
+        // latches of back edges, which are part of the loop.
+        for &pred in &predecessors[lp.header] {
+            if lp.blocks.contains(pred) {
+                continue;
+            }
+            for target in body.basic_blocks_mut()[pred].terminator_mut().successors_mut() {
+                if *target == lp.header {
+                    *target = preheader;
+                }
+            }
+        }
+    }
+
+    // The hoisted locals are now assigned before the loop, so they must not be storage-dead
+    // inside it.
+    for data in body.basic_blocks.as_mut_preserves_cfg() {
+        for statement in data.statements.iter_mut() {
+            if let StatementKind::StorageLive(local) | StatementKind::StorageDead(local) =
+                statement.kind
+                && hoisted_locals.contains(local)
+            {
+                statement.make_nop();
+            }
+        }
+    }
+}
+
+/// Collect the locals that are moved from anywhere in the body.
+struct MovedLocals(BitSet<Local>);
+
+impl<'tcx> Visitor<'tcx> for MovedLocals {
+    fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, _: Location) {
+        if let PlaceContext::NonMutatingUse(NonMutatingUseContext::Move) = context {
+            self.0.insert(place.local);
+        }
+    }
+}
+
+/// Collect the locals that are assigned to in a set of blocks.
+struct AssignedLocals<'a> {
+    blocks: &'a BitSet<BasicBlock>,
+    locals: BitSet<Local>,
+}
+
+impl<'tcx> Visitor<'tcx> for AssignedLocals<'_> {
+    fn visit_basic_block_data(&mut self, block: BasicBlock, data: &BasicBlockData<'tcx>) {
+        if self.blocks.contains(block) {
+            self.super_basic_block_data(block, data);
+        }
+    }
+
+    fn visit_local(&mut self, local: Local, context: PlaceContext, _: Location) {
+        if context.is_mutating_use() {
+            self.locals.insert(local);
+        }
+    }
+}
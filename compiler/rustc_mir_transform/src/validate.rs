@@ -1,5 +1,7 @@
 //! Validates the MIR to ensure that invariants are upheld.
 
+use std::cell::RefCell;
+
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_hir::LangItem;
 use rustc_index::bit_set::BitSet;
@@ -38,6 +40,33 @@ pub struct Validator {
 
 impl<'tcx> MirPass<'tcx> for Validator {
     fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        self.check(tcx, body, None);
+    }
+}
+
+/// Checks that `body` upholds the invariants of `mir_phase`, returning all violations found.
+///
+/// This is what the [`Validator`] pass runs. It is exposed for bodies that were not produced by
+/// the MIR pass pipeline, e.g. bodies that were edited through StableMIR.
+pub fn validate_body<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    mir_phase: MirPhase,
+    body: &Body<'tcx>,
+) -> Vec<(Location, String)> {
+    let failures = RefCell::new(Vec::new());
+    Validator { when: String::from("validate_body"), mir_phase }.check(tcx, body, Some(&failures));
+    failures.into_inner()
+}
+
+impl Validator {
+    /// Validates `body`. Violations are collected into `failures` if it is given, and are a bug
+    /// otherwise.
+    fn check<'tcx>(
+        &self,
+        tcx: TyCtxt<'tcx>,
+        body: &Body<'tcx>,
+        failures: Option<&RefCell<Vec<(Location, String)>>>,
+    ) {
         // FIXME(JakobDegen): These bodies never instantiated in codegend anyway, so it's not
         // terribly important that they pass the validator. However, I think other passes might
         // still see them, in which case they might be surprised. It would probably be better if we
@@ -83,6 +112,7 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
             reachable_blocks: traversal::reachable_as_bitset(body),
             value_cache: FxHashSet::default(),
             can_unwind,
+            failures,
         };
         cfg_checker.visit_body(body);
         cfg_checker.check_cleanup_control_flow();
@@ -116,11 +146,16 @@ struct CfgChecker<'a, 'tcx> {
     // If `false`, then the MIR must not contain `UnwindAction::Continue` or
     // `TerminatorKind::Resume`.
     can_unwind: bool,
+    failures: Option<&'a RefCell<Vec<(Location, String)>>>,
 }
 
 impl<'a, 'tcx> CfgChecker<'a, 'tcx> {
     #[track_caller]
     fn fail(&self, location: Location, msg: impl AsRef<str>) {
+        if let Some(failures) = self.failures {
+            failures.borrow_mut().push((location, msg.as_ref().to_owned()));
+            return;
+        }
         // We might see broken MIR when other errors have already occurred.
         assert!(
             self.tcx.dcx().has_errors().is_some(),
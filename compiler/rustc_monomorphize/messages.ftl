@@ -17,6 +17,10 @@ monomorphize_recursion_limit =
     reached the recursion limit while instantiating `{$shrunk}`
     .note = `{$def_path_str}` defined here
 
+monomorphize_secondary_codegen_backend_lto =
+    functions generated by the `{$backend}` codegen backend cannot be optimized with `-C lto`
+    .note = each codegen backend only optimizes its own functions, so `-C lto=off` is the only LTO setting that is supported
+
 monomorphize_start_not_found = using `fn main` requires the standard library
     .help = use `#![no_main]` to bypass the Rust generated entrypoint and declare a platform specific entrypoint yourself, usually with `#[no_mangle]`
 
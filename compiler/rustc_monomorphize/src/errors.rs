@@ -82,6 +82,13 @@ pub(crate) struct EncounteredErrorWhileInstantiating {
     pub formatted_item: String,
 }
 
+#[derive(Diagnostic)]
+#[diag(monomorphize_secondary_codegen_backend_lto)]
+#[note]
+pub(crate) struct SecondaryCodegenBackendLto {
+    pub backend: Symbol,
+}
+
 #[derive(Diagnostic)]
 #[diag(monomorphize_start_not_found)]
 #[help]
@@ -13,6 +13,7 @@
 
 mod collector;
 mod errors;
+mod merging;
 mod partitioning;
 mod polymorphize;
 mod util;
@@ -0,0 +1,304 @@
+//! Finds the instances of a codegen unit whose code is identical to that of another instance of
+//! the same item, so that the backend can emit them as aliases of it instead of generating the
+//! same code again, see `-Z merge-identical-instances`.
+//!
+//! Instances like `Vec::<u32>::push` and `Vec::<i32>::push` only differ by the signedness of
+//! integers. Two instances are merged if their monomorphized MIR bodies are equal once the integer
+//! types are made unsigned, where they appear as such or as the components of other types, and if
+//! signedness does not matter in them: the operands of arithmetic and comparisons and the types of
+//! numeric casts and unsizing coercions must be the same. Every type of the bodies must have the
+//! same layout, their callees must in turn be identical instances, their constants must have the
+//! same values, and their arguments must be passed the same way. Trait objects, function pointers,
+//! closures and the other types whose signedness could change which code they run are compared
+//! as they are.
+//!
+//! Instances are bucketed by a hash of all of this, and only merged if it is equal, not just its
+//! hash.
+//!
+//! Only the instances of the same codegen unit are merged, since backends can only define
+//! aliases of functions that they define too.
+
+use std::collections::hash_map::Entry;
+
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_hir::def_id::DefId;
+use rustc_middle::mir::mono::{CodegenUnit, Linkage, MonoItem};
+use rustc_middle::mir::visit::{TyContext, Visitor as MirVisitor};
+use rustc_middle::mir::{self, CastKind, Location};
+use rustc_middle::ty::adjustment::PointerCoercion;
+use rustc_middle::ty::fold::{TypeFoldable, TypeFolder, TypeSuperFoldable};
+use rustc_middle::ty::{self, Instance, InstanceKind, Ty, TyCtxt};
+use rustc_target::abi::call::{Conv, PassMode};
+use rustc_target::abi::Layout;
+use tracing::debug;
+
+pub(crate) fn merge_identical_instances<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    codegen_units: &mut [CodegenUnit<'tcx>],
+) {
+    let mut classes = InstanceClasses {
+        tcx,
+        classes: Default::default(),
+        shapes: Default::default(),
+        in_progress: Default::default(),
+    };
+    for cgu in codegen_units {
+        let mut representatives = FxHashMap::default();
+        for (item, data) in cgu.items_in_deterministic_order(tcx) {
+            let MonoItem::Fn(instance) = item else { continue };
+            // Aliases of items in a comdat would not be defined by the copies of the item that
+            // the linker may pick from other object files.
+            if !matches!(data.linkage, Linkage::External | Linkage::Internal) {
+                continue;
+            }
+            let Some(class) = classes.class(instance) else { continue };
+            match representatives.entry(class) {
+                Entry::Occupied(representative) => {
+                    debug!("merging {instance} into {}", representative.get());
+                    cgu.aliases_mut().insert(instance, *representative.get());
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(instance);
+                }
+            }
+        }
+    }
+}
+
+/// Sorts instances into classes of identical instances.
+struct InstanceClasses<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    classes: FxHashMap<Instance<'tcx>, Option<usize>>,
+    /// The class of each shape, numbered in the order they were found.
+    shapes: FxHashMap<Shape<'tcx>, usize>,
+    /// The instances whose class is being computed, which are treated as distinct from all other
+    /// instances by their recursive callees.
+    in_progress: FxHashSet<Instance<'tcx>>,
+}
+
+impl<'tcx> InstanceClasses<'tcx> {
+    /// Returns the class of the instances that are identical to `instance`, or `None` if it
+    /// cannot be merged with other instances.
+    fn class(&mut self, instance: Instance<'tcx>) -> Option<usize> {
+        if let Some(&class) = self.classes.get(&instance) {
+            return class;
+        }
+        let is_candidate = matches!(instance.def, InstanceKind::Item(_))
+            || matches!(instance.def, InstanceKind::DropGlue(_, Some(_)));
+        if !is_candidate
+            || instance.args.non_erasable_generics(self.tcx, instance.def_id()).next().is_none()
+            || !self.tcx.should_codegen_locally(instance)
+        {
+            self.classes.insert(instance, None);
+            return None;
+        }
+        if !self.in_progress.insert(instance) {
+            return None;
+        }
+        let shape = self.shape(instance);
+        self.in_progress.remove(&instance);
+        let class = shape.map(|shape| {
+            let next = self.shapes.len();
+            *self.shapes.entry(shape).or_insert(next)
+        });
+        self.classes.insert(instance, class);
+        class
+    }
+
+    fn shape(&mut self, instance: Instance<'tcx>) -> Option<Shape<'tcx>> {
+        let tcx = self.tcx;
+        let param_env = ty::ParamEnv::reveal_all();
+        let fn_abi = tcx.fn_abi_of_instance(param_env.and((instance, ty::List::empty()))).ok()?;
+        let body = instance.instantiate_mir_and_normalize_erasing_regions(
+            tcx,
+            param_env,
+            ty::EarlyBinder::bind(tcx.instance_mir(instance.def).clone()),
+        );
+
+        let mut facts =
+            Facts { classes: self, body: &body, facts: vec![], layouts: vec![], mergeable: true };
+        facts.visit_body(&body);
+        let Facts { facts, layouts, mergeable, .. } = facts;
+        if !mergeable {
+            return None;
+        }
+        let body = body.fold_with(&mut EraseSignedness { tcx });
+
+        let arg_modes = fn_abi.args.iter().chain([&fn_abi.ret]).map(|arg| arg.mode.clone());
+        Some(Shape {
+            def_id: instance.def_id(),
+            arg_modes: arg_modes.collect(),
+            c_variadic: fn_abi.c_variadic,
+            conv: fn_abi.conv,
+            can_unwind: fn_abi.can_unwind,
+            spread_arg: body.spread_arg,
+            locals: body.local_decls.iter().map(|decl| decl.ty).collect(),
+            blocks: body
+                .basic_blocks
+                .iter()
+                .map(|block| {
+                    let statements = block.statements.iter().map(|s| s.kind.clone()).collect();
+                    (statements, block.terminator().kind.clone())
+                })
+                .collect(),
+            layouts,
+            facts,
+        })
+    }
+}
+
+/// Everything the code generated for an instance depends on, with the signedness of integers
+/// erased from its MIR body.
+#[derive(PartialEq, Hash)]
+struct Shape<'tcx> {
+    def_id: DefId,
+    arg_modes: Vec<PassMode>,
+    c_variadic: bool,
+    conv: Conv,
+    can_unwind: bool,
+    spread_arg: Option<mir::Local>,
+    locals: Vec<Ty<'tcx>>,
+    blocks: Vec<(Vec<mir::StatementKind<'tcx>>, mir::TerminatorKind<'tcx>)>,
+    /// The layouts of the types of the MIR body, before erasing signedness.
+    layouts: Vec<Layout<'tcx>>,
+    facts: Vec<Fact<'tcx>>,
+}
+
+// The equality of MIR statements and terminators is an equivalence, they only do not implement
+// `Eq`.
+impl Eq for Shape<'_> {}
+
+/// Something in the code of an instance that distinguishes it from other instances, and that is
+/// lost when erasing signedness or not part of the MIR body.
+#[derive(PartialEq, Hash)]
+enum Fact<'tcx> {
+    /// A type whose signedness matters.
+    Ty(Ty<'tcx>),
+    /// A callee that is not merged with other instances.
+    Instance(Instance<'tcx>),
+    /// A callee that can be merged with identical instances, by its class.
+    Class(usize),
+    /// A drop of a value without drop glue.
+    NoDropGlue,
+    /// The value of a constant that is not evaluated in the MIR body.
+    Value(mir::ConstValue<'tcx>),
+}
+
+/// Collects the facts of a monomorphized MIR body, and the layouts of its types, in the order
+/// they appear in it.
+struct Facts<'a, 'tcx> {
+    classes: &'a mut InstanceClasses<'tcx>,
+    body: &'a mir::Body<'tcx>,
+    facts: Vec<Fact<'tcx>>,
+    layouts: Vec<Layout<'tcx>>,
+    mergeable: bool,
+}
+
+impl<'tcx> Facts<'_, 'tcx> {
+    fn callee(&mut self, callee: Instance<'tcx>) {
+        let fact = match callee.def {
+            InstanceKind::DropGlue(_, None) => Fact::NoDropGlue,
+            _ => match self.classes.class(callee) {
+                Some(class) => Fact::Class(class),
+                None => Fact::Instance(callee),
+            },
+        };
+        self.facts.push(fact);
+    }
+}
+
+impl<'tcx> MirVisitor<'tcx> for Facts<'_, 'tcx> {
+    fn visit_ty(&mut self, ty: Ty<'tcx>, context: TyContext) {
+        if let TyContext::UserTy(_) = context {
+            return;
+        }
+        let param_env = ty::ParamEnv::reveal_all();
+        match self.classes.tcx.layout_of(param_env.and(ty)) {
+            Ok(layout) => self.layouts.push(layout.layout),
+            Err(_) => self.mergeable = false,
+        }
+    }
+
+    fn visit_rvalue(&mut self, rvalue: &mir::Rvalue<'tcx>, location: Location) {
+        let tcx = self.classes.tcx;
+        match rvalue {
+            mir::Rvalue::BinaryOp(_, operands) => {
+                let (lhs, rhs) = &**operands;
+                for operand in [lhs, rhs] {
+                    let ty = operand.ty(self.body, tcx);
+                    if ty.is_integral() {
+                        self.facts.push(Fact::Ty(ty));
+                    }
+                }
+            }
+            mir::Rvalue::Cast(
+                CastKind::IntToInt
+                | CastKind::IntToFloat
+                | CastKind::FloatToInt
+                | CastKind::FloatToFloat
+                | CastKind::DynStar
+                | CastKind::PointerCoercion(PointerCoercion::Unsize),
+                operand,
+                target,
+            ) => {
+                self.facts.push(Fact::Ty(operand.ty(self.body, tcx)));
+                self.facts.push(Fact::Ty(*target));
+            }
+            _ => {}
+        }
+        self.super_rvalue(rvalue, location);
+    }
+
+    fn visit_const_operand(&mut self, constant: &mir::ConstOperand<'tcx>, location: Location) {
+        let tcx = self.classes.tcx;
+        let param_env = ty::ParamEnv::reveal_all();
+        if let ty::FnDef(def_id, args) = *constant.const_.ty().kind() {
+            match Instance::try_resolve(tcx, param_env, def_id, args) {
+                Ok(Some(callee)) => self.callee(callee),
+                _ => self.facts.push(Fact::Ty(constant.const_.ty())),
+            }
+        } else if !matches!(constant.const_, mir::Const::Val(..)) {
+            match constant.const_.eval(tcx, param_env, constant.span) {
+                Ok(value) => self.facts.push(Fact::Value(value)),
+                Err(_) => self.mergeable = false,
+            }
+        }
+        self.super_const_operand(constant, location);
+    }
+
+    fn visit_terminator(&mut self, terminator: &mir::Terminator<'tcx>, location: Location) {
+        if let mir::TerminatorKind::Drop { place, .. } = terminator.kind {
+            let ty = place.ty(self.body, self.classes.tcx).ty;
+            self.callee(Instance::resolve_drop_in_place(self.classes.tcx, ty));
+        }
+        self.super_terminator(terminator, location);
+    }
+}
+
+/// Replaces signed integer types by the unsigned ones of the same size, where they appear as such
+/// or as the components of other types whose code does not depend on them.
+struct EraseSignedness<'tcx> {
+    tcx: TyCtxt<'tcx>,
+}
+
+impl<'tcx> TypeFolder<TyCtxt<'tcx>> for EraseSignedness<'tcx> {
+    fn cx(&self) -> TyCtxt<'tcx> {
+        self.tcx
+    }
+
+    fn fold_ty(&mut self, ty: Ty<'tcx>) -> Ty<'tcx> {
+        match *ty.kind() {
+            ty::Int(int) => Ty::new_uint(self.tcx, int.to_unsigned()),
+            // The layouts of these types are compared separately, and their callees are compared
+            // by the facts.
+            ty::Adt(..)
+            | ty::Ref(..)
+            | ty::RawPtr(..)
+            | ty::Array(..)
+            | ty::Slice(_)
+            | ty::Tuple(_)
+            | ty::FnDef(..) => ty.super_fold_with(self),
+            _ => ty,
+        }
+    }
+}
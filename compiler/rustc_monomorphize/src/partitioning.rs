@@ -116,13 +116,17 @@
 use rustc_middle::ty::visit::TypeVisitableExt;
 use rustc_middle::ty::{self, InstanceKind, TyCtxt};
 use rustc_middle::util::Providers;
-use rustc_session::config::{DumpMonoStatsFormat, SwitchWithOptPath};
+use rustc_session::config::{DumpMonoStatsFormat, Lto, SwitchWithOptPath};
 use rustc_session::CodegenUnits;
 use rustc_span::symbol::Symbol;
 use tracing::debug;
 
 use crate::collector::{self, MonoItemCollectionStrategy, UsageMap};
-use crate::errors::{CouldntDumpMonoStats, SymbolAlreadyDefined, UnknownCguCollectionMode};
+use crate::errors::{
+    CouldntDumpMonoStats, SecondaryCodegenBackendLto, SymbolAlreadyDefined,
+    UnknownCguCollectionMode,
+};
+use crate::merging;
 
 struct PartitioningCx<'a, 'tcx> {
     tcx: TyCtxt<'tcx>,
@@ -160,12 +164,26 @@ fn partition<'tcx, I>(
         placed
     };
 
+    // The LTO of the main codegen backend needs the code of all the CGUs in its own format.
+    if matches!(tcx.sess.lto(), Lto::Fat | Lto::Thin) {
+        if let Some(backend) = codegen_units.iter().find_map(|cgu| cgu.codegen_backend()) {
+            tcx.dcx().emit_err(SecondaryCodegenBackendLto { backend });
+        }
+    }
+
     // Merge until we don't exceed the max CGU count.
     // `merge_codegen_units` is responsible for updating the CGU size
-    // estimates.
+    // estimates. The CGUs of secondary codegen backends are not merged.
     {
         let _prof_timer = tcx.prof.generic_activity("cgu_partitioning_merge_cgus");
-        merge_codegen_units(cx, &mut codegen_units);
+        let (mut main_cgus, secondary_cgus): (Vec<_>, Vec<_>) =
+            codegen_units.into_iter().partition(|cgu| cgu.codegen_backend().is_none());
+        merge_codegen_units(cx, &mut main_cgus);
+        codegen_units = main_cgus;
+        if !secondary_cgus.is_empty() {
+            codegen_units.extend(secondary_cgus);
+            codegen_units.sort_by(|a, b| a.name().as_str().cmp(b.name().as_str()));
+        }
         debug_dump(tcx, "MERGE", &codegen_units);
     }
 
@@ -178,6 +196,13 @@ fn partition<'tcx, I>(
         debug_dump(tcx, "INTERNALIZE", &codegen_units);
     }
 
+    // Let the backend emit the instances that are identical to another instance of the same CGU
+    // as aliases of it.
+    if tcx.sess.opts.unstable_opts.merge_identical_instances {
+        let _prof_timer = tcx.prof.generic_activity("cgu_partitioning_merge_instances");
+        merging::merge_identical_instances(tcx, &mut codegen_units);
+    }
+
     // Mark one CGU for dead code, if necessary.
     if tcx.sess.instrument_coverage() {
         mark_code_coverage_dead_code_cgu(&mut codegen_units);
@@ -229,19 +254,28 @@ fn place_mono_items<'tcx, I>(cx: &PartitioningCx<'_, 'tcx>, mono_items: I) -> Pl
 
         let characteristic_def_id = characteristic_def_id_of_mono_item(cx.tcx, mono_item);
         let is_volatile = is_incremental_build && mono_item.is_generic_fn(cx.tcx);
+        let codegen_backend = secondary_codegen_backend(cx.tcx, mono_item);
 
-        let cgu_name = match characteristic_def_id {
-            Some(def_id) => compute_codegen_unit_name(
+        let cgu_name = match (codegen_backend, characteristic_def_id) {
+            // Each secondary codegen backend gets a single CGU.
+            (Some(backend), _) => {
+                cgu_name_builder.build_cgu_name(LOCAL_CRATE, &["codegen-backend"], Some(backend))
+            }
+            (None, Some(def_id)) => compute_codegen_unit_name(
                 cx.tcx,
                 cgu_name_builder,
                 def_id,
                 is_volatile,
                 cgu_name_cache,
             ),
-            None => fallback_cgu_name(cgu_name_builder),
+            (None, None) => fallback_cgu_name(cgu_name_builder),
         };
 
-        let cgu = codegen_units.entry(cgu_name).or_insert_with(|| CodegenUnit::new(cgu_name));
+        let cgu = codegen_units.entry(cgu_name).or_insert_with(|| {
+            let mut cgu = CodegenUnit::new(cgu_name);
+            cgu.set_codegen_backend(codegen_backend);
+            cgu
+        });
 
         let mut can_be_internalized = true;
         let (linkage, visibility) = mono_item_linkage_and_visibility(
@@ -279,9 +313,10 @@ fn place_mono_items<'tcx, I>(cx: &PartitioningCx<'_, 'tcx>, mono_items: I) -> Pl
         }
     }
 
-    // Always ensure we have at least one CGU; otherwise, if we have a
-    // crate with just types (for example), we could wind up with no CGU.
-    if codegen_units.is_empty() {
+    // Always ensure we have at least one CGU for the main codegen backend;
+    // otherwise, if we have a crate with just types (for example), we could
+    // wind up with no CGU.
+    if codegen_units.items().all(|(_, cgu)| cgu.codegen_backend().is_some()) {
         let cgu_name = fallback_cgu_name(cgu_name_builder);
         codegen_units.insert(cgu_name, CodegenUnit::new(cgu_name));
     }
@@ -727,6 +762,19 @@ fn compute_codegen_unit_name(
     })
 }
 
+/// Returns the secondary codegen backend that `#[codegen_backend]` selects for `mono_item`, if it
+/// is one of `-Z secondary-codegen-backends`. The other backends that the attribute may name are
+/// checked with the attributes of the local crate.
+fn secondary_codegen_backend(tcx: TyCtxt<'_>, mono_item: MonoItem<'_>) -> Option<Symbol> {
+    let MonoItem::Fn(instance) = mono_item else { return None };
+    if !matches!(instance.def, InstanceKind::Item(_)) {
+        return None;
+    }
+    let backend = tcx.codegen_fn_attrs(instance.def_id()).codegen_backend?;
+    let secondary_backends = &tcx.sess.opts.unstable_opts.secondary_codegen_backends;
+    secondary_backends.iter().any(|name| name == backend.as_str()).then_some(backend)
+}
+
 // Anything we can't find a proper codegen unit for goes into this.
 fn fallback_cgu_name(name_builder: &mut CodegenUnitNameBuilder<'_>) -> Symbol {
     name_builder.build_cgu_name(LOCAL_CRATE, &["fallback"], Some("cgu"))
@@ -1168,6 +1216,13 @@ fn collect_and_partition_mono_items(tcx: TyCtxt<'_>, (): ()) -> (&DefIdSet, &[Co
         }
     }
 
+    if let Some(item_times) = &tcx.sess.item_times {
+        for (def_id, items) in mono_items_per_def_id(codegen_units) {
+            let size_estimate = items.iter().map(|item| item.size_estimate(tcx)).sum();
+            item_times.record_instances(def_id, items.len(), size_estimate);
+        }
+    }
+
     if tcx.sess.opts.unstable_opts.print_mono_items.is_some() {
         let mut item_to_cgus: UnordMap<_, Vec<_>> = Default::default();
 
@@ -1244,17 +1299,7 @@ fn dump_mono_items_stats<'tcx>(
     let file = File::create(&output_path)?;
     let mut file = BufWriter::new(file);
 
-    // Gather instantiated mono items grouped by def_id
-    let mut items_per_def_id: FxIndexMap<_, Vec<_>> = Default::default();
-    for cgu in codegen_units {
-        cgu.items()
-            .keys()
-            // Avoid variable-sized compiler-generated shims
-            .filter(|mono_item| mono_item.is_user_defined())
-            .for_each(|mono_item| {
-                items_per_def_id.entry(mono_item.def_id()).or_default().push(mono_item);
-            });
-    }
+    let items_per_def_id = mono_items_per_def_id(codegen_units);
 
     #[derive(serde::Serialize)]
     struct MonoItem {
@@ -1300,6 +1345,23 @@ struct MonoItem {
     Ok(())
 }
 
+/// Gathers the instantiated mono items grouped by def_id.
+fn mono_items_per_def_id<'a, 'tcx>(
+    codegen_units: &'a [CodegenUnit<'tcx>],
+) -> FxIndexMap<DefId, Vec<&'a MonoItem<'tcx>>> {
+    let mut items_per_def_id: FxIndexMap<_, Vec<_>> = Default::default();
+    for cgu in codegen_units {
+        cgu.items()
+            .keys()
+            // Avoid variable-sized compiler-generated shims
+            .filter(|mono_item| mono_item.is_user_defined())
+            .for_each(|mono_item| {
+                items_per_def_id.entry(mono_item.def_id()).or_default().push(mono_item);
+            });
+    }
+    items_per_def_id
+}
+
 pub(crate) fn provide(providers: &mut Providers) {
     providers.collect_and_partition_mono_items = collect_and_partition_mono_items;
 
@@ -122,6 +122,11 @@ passes_debug_visualizer_invalid =
     .note_1 = expected: `natvis_file = "..."`
     .note_2 = OR
     .note_3 = expected: `gdb_script_file = "..."`
+    .note_4 = OR
+    .note_5 = expected: `lldb_script_file = "..."`
+
+passes_debug_visualizer_lldb_unstable =
+    embedding LLDB formatters is unstable
 
 passes_debug_visualizer_placement =
     attribute should be applied to a module
@@ -3,13 +3,16 @@
 use rustc_ast::Attribute;
 use rustc_data_structures::sync::Lrc;
 use rustc_expand::base::resolve_path;
+use rustc_feature::Features;
 use rustc_middle::middle::debugger_visualizer::{DebuggerVisualizerFile, DebuggerVisualizerType};
 use rustc_middle::query::{LocalCrate, Providers};
 use rustc_middle::ty::TyCtxt;
+use rustc_session::parse::feature_err;
 use rustc_session::Session;
 use rustc_span::sym;
 
 use crate::errors::{DebugVisualizerInvalid, DebugVisualizerUnreadable};
+use crate::fluent_generated as fluent;
 
 impl DebuggerVisualizerCollector<'_> {
     fn check_for_debugger_visualizer(&mut self, attr: &Attribute) {
@@ -35,6 +38,18 @@ fn check_for_debugger_visualizer(&mut self, attr: &Attribute) {
                     (sym::gdb_script_file, Some(value)) => {
                         (DebuggerVisualizerType::GdbPrettyPrinter, value)
                     }
+                    (sym::lldb_script_file, Some(value)) => {
+                        if !self.features.lldb_debugger_visualizer {
+                            feature_err(
+                                self.sess,
+                                sym::lldb_debugger_visualizer,
+                                meta_item.span,
+                                fluent::passes_debug_visualizer_lldb_unstable,
+                            )
+                            .emit();
+                        }
+                        (DebuggerVisualizerType::LldbFormatter, value)
+                    }
                     (_, _) => {
                         self.sess.dcx().emit_err(DebugVisualizerInvalid { span: meta_item.span });
                         return;
@@ -71,6 +86,7 @@ fn check_for_debugger_visualizer(&mut self, attr: &Attribute) {
 
 struct DebuggerVisualizerCollector<'a> {
     sess: &'a Session,
+    features: &'a Features,
     visualizers: Vec<DebuggerVisualizerFile>,
 }
 
@@ -86,7 +102,11 @@ fn debugger_visualizers(tcx: TyCtxt<'_>, _: LocalCrate) -> Vec<DebuggerVisualize
     let resolver_and_krate = tcx.resolver_for_lowering().borrow();
     let krate = &*resolver_and_krate.1;
 
-    let mut visitor = DebuggerVisualizerCollector { sess: tcx.sess, visualizers: Vec::new() };
+    let mut visitor = DebuggerVisualizerCollector {
+        sess: tcx.sess,
+        features: tcx.features(),
+        visualizers: Vec::new(),
+    };
     rustc_ast::visit::Visitor::visit_crate(&mut visitor, krate);
 
     // We are collecting visualizers in AST-order, which is deterministic,
@@ -574,6 +574,8 @@ pub(crate) struct DebugVisualizerPlacement {
 #[note(passes_note_1)]
 #[note(passes_note_2)]
 #[note(passes_note_3)]
+#[note(passes_note_4)]
+#[note(passes_note_5)]
 pub(crate) struct DebugVisualizerInvalid {
     #[primary_span]
     pub span: Span,
@@ -619,6 +619,9 @@ pub(crate) fn dynamic_query<'tcx>()
                     compute: |tcx, key| {
                         #[cfg(debug_assertions)]
                         let _guard = tracing::span!(tracing::Level::TRACE, stringify!($name), ?key).entered();
+                        let _item_timer = tcx.sess.item_times.as_ref().and_then(|times| {
+                            Some(times.start_query(key.key_as_item_def_id()?, stringify!($name)))
+                        });
                         __rust_begin_short_backtrace(||
                             queries::$name::provided_to_erased(
                                 tcx,
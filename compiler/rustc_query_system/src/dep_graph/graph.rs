@@ -106,6 +106,22 @@ pub(crate) struct DepGraphData<D: Deps> {
     /// a particular query result was decoded from disk
     /// (not just marked green)
     debug_loaded_from_disk: Lock<FxHashSet<DepNode>>,
+
+    /// For each node from the previous session that could not be marked green,
+    /// the dependency that prevented it. Only recorded with `-Z incremental-explain`.
+    rebuild_causes: Option<Lock<FxHashMap<SerializedDepNodeIndex, SerializedDepNodeIndex>>>,
+}
+
+/// A node from the previous session that could not be marked green, together
+/// with the dependency that was found to be red (or could not be checked).
+#[derive(Clone, Copy, Debug)]
+pub struct RebuildCause {
+    pub node: DepNode,
+    pub dependency: DepNode,
+    /// Whether the node was re-executed and its result changed, which makes
+    /// it red. If `false`, the node was either re-executed with the same
+    /// result or not needed at all later on.
+    pub changed: bool,
 }
 
 pub fn hash_result<R>(hcx: &mut StableHashingContext<'_>, result: &R) -> Fingerprint
@@ -125,6 +141,7 @@ pub fn new(
         encoder: FileEncoder,
         record_graph: bool,
         record_stats: bool,
+        record_rebuild_causes: bool,
     ) -> DepGraph<D> {
         let prev_graph_node_count = prev_graph.node_count();
 
@@ -179,6 +196,7 @@ pub fn new(
                 previous: prev_graph,
                 colors,
                 debug_loaded_from_disk: Default::default(),
+                rebuild_causes: record_rebuild_causes.then(Default::default),
             })),
             virtual_dep_node_index: Lrc::new(AtomicU32::new(0)),
         }
@@ -851,7 +869,12 @@ fn try_mark_previous_green<Qcx: QueryContext<Deps = D>>(
         let prev_deps = self.previous.edge_targets_from(prev_dep_node_index);
 
         for dep_dep_node_index in prev_deps {
-            self.try_mark_parent_green(qcx, dep_dep_node_index, Some(&frame))?;
+            if self.try_mark_parent_green(qcx, dep_dep_node_index, Some(&frame)).is_none() {
+                if let Some(rebuild_causes) = &self.rebuild_causes {
+                    rebuild_causes.lock().insert(prev_dep_node_index, dep_dep_node_index);
+                }
+                return None;
+            }
         }
 
         // If we got here without hitting a `return` that means that all
@@ -969,6 +992,27 @@ pub fn print_incremental_info(&self) {
         }
     }
 
+    /// Returns the nodes from the previous session that could not be marked
+    /// green, in the order of the previous dep-graph. This is only recorded
+    /// with `-Z incremental-explain`, and is empty otherwise.
+    pub fn rebuild_causes(&self) -> Vec<RebuildCause> {
+        let Some(data) = &self.data else { return Vec::new() };
+        let Some(rebuild_causes) = &data.rebuild_causes else { return Vec::new() };
+
+        #[allow(rustc::potential_query_instability)]
+        let mut causes: Vec<_> =
+            rebuild_causes.lock().iter().map(|(&node, &dependency)| (node, dependency)).collect();
+        causes.sort_unstable();
+        causes
+            .into_iter()
+            .map(|(node, dependency)| RebuildCause {
+                node: data.previous.index_to_node(node),
+                dependency: data.previous.index_to_node(dependency),
+                changed: matches!(data.colors.get(node), Some(DepNodeColor::Red)),
+            })
+            .collect()
+    }
+
     pub fn finish_encoding(&self) -> FileEncodeResult {
         if let Some(data) = &self.data { data.current.encoder.finish() } else { Ok(0) }
     }
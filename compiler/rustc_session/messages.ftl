@@ -20,6 +20,8 @@ session_embed_source_requires_debug_info = `-Zembed-source=y` requires debug inf
 
 session_expr_parentheses_needed = parentheses are required to parse this as an expression
 
+session_failed_to_apply_suggestions = failed to apply suggestions: {$err}
+
 session_failed_to_create_profiler = failed to create profiler: {$err}
 
 session_feature_diagnostic_for_issue =
@@ -6,6 +6,8 @@
 use rustc_span::Symbol;
 use rustc_target::abi::{Align, Size};
 
+use crate::config::PrintSizesFormat;
+
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct VariantInfo {
     pub name: Option<Symbol>,
@@ -51,6 +53,19 @@ pub struct FieldInfo {
     pub type_name: Option<Symbol>,
 }
 
+/// The largest niche of a type, as recorded for `-Z print-type-sizes`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct NicheInfo {
+    pub offset: u64,
+    pub size: u64,
+    /// The start of the range of valid values, which may wrap around.
+    pub valid_range_start: u128,
+    /// The end of the range of valid values (inclusive).
+    pub valid_range_end: u128,
+    /// The number of invalid values that are available for encoding enum discriminants.
+    pub available: u128,
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum DataTypeKind {
     Struct,
@@ -68,6 +83,7 @@ pub struct TypeSizeInfo {
     pub overall_size: u64,
     pub packed: bool,
     pub opt_discr_size: Option<u64>,
+    pub niche: Option<NicheInfo>,
     pub variants: Vec<VariantInfo>,
 }
 
@@ -105,6 +121,7 @@ pub fn record_type_size<S: ToString>(
         overall_size: Size,
         packed: bool,
         opt_discr_size: Option<Size>,
+        niche: Option<NicheInfo>,
         mut variants: Vec<VariantInfo>,
     ) {
         // Sort variants so the largest ones are shown first. A stable sort is
@@ -122,6 +139,7 @@ pub fn record_type_size<S: ToString>(
             overall_size: overall_size.bytes(),
             packed,
             opt_discr_size: opt_discr_size.map(|s| s.bytes()),
+            niche,
             variants,
         };
         self.type_sizes.borrow_mut().insert(info);
@@ -135,7 +153,7 @@ pub fn record_vtable_size(&self, trait_did: DefId, trait_name: &str, info: VTabl
         );
     }
 
-    pub fn print_type_sizes(&self) {
+    pub fn print_type_sizes(&self, format: PrintSizesFormat) {
         let type_sizes = self.type_sizes.borrow();
         // We will soon sort, so the initial order does not matter.
         #[allow(rustc::potential_query_instability)]
@@ -145,6 +163,13 @@ pub fn print_type_sizes(&self) {
         // Secondary sort: description (dictionary order)
         sorted.sort_by_key(|info| (cmp::Reverse(info.overall_size), &info.type_description));
 
+        if format == PrintSizesFormat::Json {
+            for info in sorted {
+                println!("{}", serde_json::to_string(&json::TypeSize::new(info)).unwrap());
+            }
+            return;
+        }
+
         for info in sorted {
             let TypeSizeInfo { type_description, overall_size, align, kind, variants, .. } = info;
             println!(
@@ -239,7 +264,7 @@ pub fn print_type_sizes(&self) {
         }
     }
 
-    pub fn print_vtable_sizes(&self, crate_name: Symbol) {
+    pub fn print_vtable_sizes(&self, crate_name: Symbol, format: PrintSizesFormat) {
         // We will soon sort, so the initial order does not matter.
         #[allow(rustc::potential_query_instability)]
         let mut infos =
@@ -262,9 +287,161 @@ pub fn print_vtable_sizes(&self, crate_name: Symbol) {
             upcasting_cost_percent,
         } in infos
         {
+            if format == PrintSizesFormat::Json {
+                let record = json::VTableSize {
+                    record: "vtable",
+                    crate_name: crate_name.as_str(),
+                    trait_name: &trait_name,
+                    entries,
+                    entries_ignoring_upcasting,
+                    entries_for_upcasting,
+                    upcasting_cost_percent,
+                };
+                println!("{}", serde_json::to_string(&record).unwrap());
+                continue;
+            }
             println!(
                 r#"print-vtable-sizes {{ "crate_name": "{crate_name}", "trait_name": "{trait_name}", "entries": "{entries}", "entries_ignoring_upcasting": "{entries_ignoring_upcasting}", "entries_for_upcasting": "{entries_for_upcasting}", "upcasting_cost_percent": "{upcasting_cost_percent}" }}"#
             );
         }
     }
 }
+
+/// The records printed with `-Z print-sizes-format=json`, one per line.
+///
+/// Unlike the text output, these contain every field, the largest niche and the padding between
+/// fields, so that the layouts of two builds can be compared mechanically.
+mod json {
+    use serde::Serialize;
+
+    use super::{DataTypeKind, FieldInfo, NicheInfo, SizeKind, TypeSizeInfo, VariantInfo};
+
+    #[derive(Serialize)]
+    pub(super) struct TypeSize<'a> {
+        record: &'static str,
+        #[serde(rename = "type")]
+        type_description: &'a str,
+        kind: &'static str,
+        size: u64,
+        align: u64,
+        packed: bool,
+        discriminant_size: Option<u64>,
+        niche: Option<Niche>,
+        variants: Vec<Variant>,
+        end_padding: u64,
+    }
+
+    #[derive(Serialize)]
+    struct Niche {
+        offset: u64,
+        size: u64,
+        valid_range_start: u128,
+        valid_range_end: u128,
+        available: u128,
+    }
+
+    #[derive(Serialize)]
+    struct Variant {
+        name: Option<String>,
+        /// Whether `size` is exact or a lower bound (for unsized types).
+        size_kind: &'static str,
+        /// The size of the variant, including the discriminant.
+        size: u64,
+        align: u64,
+        /// The fields in order of increasing offset.
+        fields: Vec<Field>,
+    }
+
+    #[derive(Serialize)]
+    struct Field {
+        kind: String,
+        name: String,
+        offset: u64,
+        size: u64,
+        align: u64,
+        #[serde(rename = "type")]
+        type_name: Option<String>,
+        /// Padding between the end of the previous field (or the discriminant) and this field.
+        padding_before: u64,
+    }
+
+    impl<'a> TypeSize<'a> {
+        pub(super) fn new(info: &'a TypeSizeInfo) -> Self {
+            let discr_size = info.opt_discr_size.unwrap_or(0);
+            // Computed like in the text output: the largest variant, but at least the
+            // discriminant for C-like enums.
+            let max_variant_size =
+                info.variants.iter().map(|v| v.size).fold(discr_size, std::cmp::max);
+            TypeSize {
+                record: "type",
+                type_description: &info.type_description,
+                kind: match info.kind {
+                    DataTypeKind::Struct => "struct",
+                    DataTypeKind::Union => "union",
+                    DataTypeKind::Enum => "enum",
+                    DataTypeKind::Closure => "closure",
+                    DataTypeKind::Coroutine => "coroutine",
+                },
+                size: info.overall_size,
+                align: info.align,
+                packed: info.packed,
+                discriminant_size: info.opt_discr_size,
+                niche: info.niche.map(
+                    |NicheInfo { offset, size, valid_range_start, valid_range_end, available }| {
+                        Niche { offset, size, valid_range_start, valid_range_end, available }
+                    },
+                ),
+                variants: info.variants.iter().map(|v| Variant::new(v, discr_size)).collect(),
+                end_padding: info.overall_size - max_variant_size,
+            }
+        }
+    }
+
+    impl Variant {
+        fn new(info: &VariantInfo, discr_size: u64) -> Self {
+            let mut fields = info.fields.clone();
+            fields.sort_by_key(|f| (f.offset, f.size));
+
+            let mut min_offset = discr_size;
+            let fields = fields
+                .into_iter()
+                .map(|FieldInfo { kind, name, offset, size, align, type_name }| {
+                    // Fields of unions overlap, so there is no padding between them.
+                    let padding_before = offset.saturating_sub(min_offset);
+                    min_offset = offset + size;
+                    Field {
+                        kind: kind.to_string(),
+                        name: name.to_string(),
+                        offset,
+                        size,
+                        align,
+                        type_name: type_name.map(|name| name.to_string()),
+                        padding_before,
+                    }
+                })
+                .collect();
+
+            Variant {
+                name: info.name.map(|name| name.to_string()),
+                size_kind: match info.kind {
+                    SizeKind::Exact => "exact",
+                    SizeKind::Min => "min",
+                },
+                size: info.size,
+                align: info.align,
+                fields,
+            }
+        }
+    }
+
+    #[derive(Serialize)]
+    pub(super) struct VTableSize<'a> {
+        pub record: &'static str,
+        pub crate_name: &'a str,
+        pub trait_name: &'a str,
+        pub entries: usize,
+        pub entries_ignoring_upcasting: usize,
+        pub entries_for_upcasting: usize,
+        pub upcasting_cost_percent: f64,
+    }
+}
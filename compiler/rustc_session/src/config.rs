@@ -12,6 +12,7 @@
 use std::path::{Path, PathBuf};
 use std::str::{self, FromStr};
 use std::sync::LazyLock;
+use std::time::Duration;
 use std::{fmt, fs, iter};
 
 use rustc_data_structures::fx::{FxHashSet, FxIndexMap};
@@ -631,6 +632,8 @@ pub enum ErrorOutputType {
         json_rendered: HumanReadableErrorType,
         color_config: ColorConfig,
     },
+    /// A single SARIF 2.1.0 log, consumed by code-scanning tools.
+    Sarif,
 }
 
 impl Default for ErrorOutputType {
@@ -832,6 +835,7 @@ pub enum PrintKind {
     LinkArgs,
     SplitDebuginfo,
     DeploymentTarget,
+    LintsJson,
 }
 
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
@@ -1149,6 +1153,7 @@ fn default() -> Options {
             debuginfo_compression: DebugInfoCompression::None,
             lint_opts: Vec::new(),
             lint_cap: None,
+            lint_config: None,
             describe_lints: false,
             output_types: OutputTypes(BTreeMap::new()),
             search_paths: vec![],
@@ -1632,6 +1637,27 @@ pub fn get_cmd_lint_options(
     (lint_opts, describe_lints, lint_cap)
 }
 
+/// The lint configuration file passed with `-Z lint-config`.
+#[derive(Clone, Debug, Hash)]
+pub struct LintConfigFile {
+    pub path: PathBuf,
+    pub src: String,
+}
+
+/// Reads the file passed with `-Z lint-config`, if any.
+fn read_lint_config(
+    early_dcx: &EarlyDiagCtxt,
+    unstable_opts: &UnstableOptions,
+) -> Option<LintConfigFile> {
+    let path = unstable_opts.lint_config.clone()?;
+    match fs::read_to_string(&path) {
+        Ok(src) => Some(LintConfigFile { path, src }),
+        Err(e) => {
+            early_dcx.early_fatal(format!("failed to read lint config `{}`: {e}", path.display()))
+        }
+    }
+}
+
 /// Parses the `--color` flag.
 pub fn parse_color(early_dcx: &EarlyDiagCtxt, matches: &getopts::Matches) -> ColorConfig {
     match matches.opt_str("color").as_deref() {
@@ -1751,14 +1777,15 @@ pub fn parse_error_format(
                 ErrorOutputType::Json { pretty: true, json_rendered, color_config: json_color }
             }
             Some("short") => ErrorOutputType::HumanReadable(HumanReadableErrorType::Short, color),
+            Some("sarif") => ErrorOutputType::Sarif,
             Some(arg) => {
                 early_dcx.abort_if_error_and_set_error_format(ErrorOutputType::HumanReadable(
                     HumanReadableErrorType::Default,
                     color,
                 ));
                 early_dcx.early_fatal(format!(
-                    "argument for `--error-format` must be `human`, `json` or \
-                     `short` (instead was `{arg}`)"
+                    "argument for `--error-format` must be `human`, `json`, `short` or \
+                     `sarif` (instead was `{arg}`)"
                 ))
             }
         }
@@ -1822,6 +1849,9 @@ fn check_error_format_stability(
         {
             early_dcx.early_fatal("`--error-format=human-annotate-rs` is unstable");
         }
+        if let ErrorOutputType::Sarif = error_format {
+            early_dcx.early_fatal("`--error-format=sarif` is unstable");
+        }
     }
 }
 
@@ -1937,6 +1967,7 @@ fn collect_print_requests(
         ("deployment-target", PrintKind::DeploymentTarget),
         ("file-names", PrintKind::FileNames),
         ("link-args", PrintKind::LinkArgs),
+        ("lints-json", PrintKind::LintsJson),
         ("native-static-libs", PrintKind::NativeStaticLibs),
         ("relocation-models", PrintKind::RelocationModels),
         ("split-debuginfo", PrintKind::SplitDebuginfo),
@@ -1991,6 +2022,16 @@ fn collect_print_requests(
                     );
                 }
             }
+            Some((_, PrintKind::LintsJson)) => {
+                if unstable_opts.unstable_options {
+                    PrintKind::LintsJson
+                } else {
+                    early_dcx.early_fatal(
+                        "the `-Z unstable-options` flag must also be passed to \
+                         enable the lints-json print option",
+                    );
+                }
+            }
             Some(&(_, print_kind)) => print_kind,
             None => {
                 let prints =
@@ -2438,6 +2479,7 @@ pub fn build_session_options(early_dcx: &mut EarlyDiagCtxt, matches: &getopts::M
 
     let mut unstable_opts = UnstableOptions::build(early_dcx, matches);
     let (lint_opts, describe_lints, lint_cap) = get_cmd_lint_options(early_dcx, matches);
+    let lint_config = read_lint_config(early_dcx, &unstable_opts);
 
     check_error_format_stability(early_dcx, &unstable_opts, error_format);
 
@@ -2695,6 +2737,7 @@ pub fn build_session_options(early_dcx: &mut EarlyDiagCtxt, matches: &getopts::M
         debuginfo_compression,
         lint_opts,
         lint_cap,
+        lint_config,
         describe_lints,
         output_types,
         search_paths,
@@ -3014,9 +3057,9 @@ pub(crate) mod dep_tracking {
     use super::{
         BranchProtection, CFGuard, CFProtection, CollapseMacroDebuginfo, CoverageOptions,
         CrateType, DebugInfo, DebugInfoCompression, ErrorOutputType, FmtDebug, FunctionReturn,
-        InliningThreshold, InstrumentCoverage, InstrumentXRay, LinkerPluginLto, LocationDetail,
-        LtoCli, NextSolverConfig, OomStrategy, OptLevel, OutFileName, OutputType, OutputTypes,
-        PatchableFunctionEntry, Polonius, RemapPathScopeComponents, ResolveDocLinks,
+        InliningThreshold, InstrumentCoverage, InstrumentXRay, LinkerPluginLto, LintConfigFile,
+        LocationDetail, LtoCli, NextSolverConfig, OomStrategy, OptLevel, OutFileName, OutputType,
+        OutputTypes, PatchableFunctionEntry, Polonius, RemapPathScopeComponents, ResolveDocLinks,
         SourceFileHashAlgorithm, SplitDwarfKind, SwitchWithOptPath, SymbolManglingVersion,
         WasiExecModel,
     };
@@ -3095,6 +3138,7 @@ fn hash(
         TargetTriple,
         Edition,
         LinkerPluginLto,
+        LintConfigFile,
         ResolveDocLinks,
         SplitDebuginfo,
         SplitDwarfKind,
@@ -3272,6 +3316,24 @@ pub enum CollapseMacroDebuginfo {
     Yes = 3,
 }
 
+/// Which format to use for `-Z dump-mir`
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum DumpMirFormat {
+    /// Pretty-print the MIR, like `--emit=mir` does
+    Text,
+    /// Emit structured JSON
+    Json,
+}
+
+impl DumpMirFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Text => "mir",
+            Self::Json => "json",
+        }
+    }
+}
+
 /// Which format to use for `-Z dump-mono-stats`
 #[derive(Clone, Copy, PartialEq, Hash, Debug)]
 pub enum DumpMonoStatsFormat {
@@ -3290,6 +3352,24 @@ pub fn extension(self) -> &'static str {
     }
 }
 
+/// Which format to use for `-Z print-type-sizes` and `-Z print-vtable-sizes`
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum PrintSizesFormat {
+    /// Print human-readable lines prefixed with `print-type-size` or `print-vtable-sizes`
+    Text,
+    /// Print one JSON object per line
+    Json,
+}
+
+/// The budget of the incremental compilation directory, set with `-Z incremental-gc`.
+#[derive(Clone, Copy, PartialEq, Hash, Debug, Default)]
+pub struct IncrementalGcBudget {
+    /// The maximum total size of all session directories, in bytes.
+    pub max_size: Option<u64>,
+    /// The maximum time since a session directory was last used.
+    pub max_age: Option<Duration>,
+}
+
 /// `-Z patchable-function-entry` representation - how many nops to put before and after function
 /// entry.
 #[derive(Clone, Copy, PartialEq, Hash, Debug, Default)]
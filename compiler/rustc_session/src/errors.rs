@@ -479,6 +479,12 @@ pub(crate) struct IncompatibleLinkerFlavor {
 #[diag(session_function_return_thunk_extern_requires_non_large_code_model)]
 pub(crate) struct FunctionReturnThunkExternRequiresNonLargeCodeModel;
 
+#[derive(Diagnostic)]
+#[diag(session_failed_to_apply_suggestions)]
+pub(crate) struct FailedToApplySuggestions {
+    pub(crate) err: String,
+}
+
 #[derive(Diagnostic)]
 #[diag(session_failed_to_create_profiler)]
 pub(crate) struct FailedToCreateProfiler {
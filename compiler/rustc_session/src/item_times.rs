@@ -0,0 +1,155 @@
+//! Support for `-Z item-time-report`, which attributes compile time to the items that caused it.
+//!
+//! Query executions whose key has a `DefId` and the codegen of each mono item are timed and
+//! charged to that `DefId`. Only *self* time is recorded: the time spent in a nested timed
+//! activity is charged to the nested item, while work without a `DefId` (for example queries
+//! keyed by types or by `()`) is charged to the innermost enclosing item. LLVM optimization runs
+//! per codegen unit and cannot be attributed to single items, so the report shows the number of
+//! instantiations and their size estimate instead, which is what drives that cost.
+
+use std::cell::RefCell;
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::sync::Lock;
+use rustc_span::def_id::DefId;
+
+/// The number of queries listed for each item in the report.
+const QUERIES_PER_ITEM: usize = 3;
+
+#[derive(Default)]
+pub struct ItemTimes {
+    items: Lock<FxHashMap<DefId, ItemTime>>,
+}
+
+#[derive(Default)]
+struct ItemTime {
+    queries: FxHashMap<&'static str, Duration>,
+    codegen: Duration,
+    /// The number of mono items instantiated from this item.
+    instances: usize,
+    /// The sum of the size estimates of those mono items.
+    size_estimate: usize,
+}
+
+impl ItemTime {
+    fn total(&self) -> Duration {
+        self.queries.values().sum::<Duration>() + self.codegen
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Activity {
+    Query(&'static str),
+    Codegen,
+}
+
+thread_local! {
+    /// For each timer that is running on this thread, the time spent in timers nested in it.
+    static NESTED: RefCell<Vec<Duration>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Charges the self time between its creation and its drop to an item.
+#[must_use]
+pub struct ItemTimer<'a> {
+    times: &'a ItemTimes,
+    def_id: DefId,
+    activity: Activity,
+    start: Instant,
+}
+
+impl ItemTimes {
+    /// Starts timing the execution of the query `name` for `def_id`.
+    pub fn start_query(&self, def_id: DefId, name: &'static str) -> ItemTimer<'_> {
+        self.start(def_id, Activity::Query(name))
+    }
+
+    /// Starts timing the codegen of a mono item instantiated from `def_id`.
+    pub fn start_codegen(&self, def_id: DefId) -> ItemTimer<'_> {
+        self.start(def_id, Activity::Codegen)
+    }
+
+    fn start(&self, def_id: DefId, activity: Activity) -> ItemTimer<'_> {
+        NESTED.with_borrow_mut(|nested| nested.push(Duration::ZERO));
+        ItemTimer { times: self, def_id, activity, start: Instant::now() }
+    }
+
+    /// Records that `instances` mono items with a total size estimate of `size_estimate` were
+    /// instantiated from `def_id`.
+    pub fn record_instances(&self, def_id: DefId, instances: usize, size_estimate: usize) {
+        let mut items = self.items.lock();
+        let item = items.entry(def_id).or_default();
+        item.instances += instances;
+        item.size_estimate += size_estimate;
+    }
+
+    /// Prints the `limit` items with the highest total time to stdout. This takes the recorded
+    /// data, so it should only be called once compilation of the items has finished.
+    pub fn print_report(&self, limit: usize, def_path_str: impl Fn(DefId) -> String) {
+        // Take the data out of the lock, as computing the paths may run queries.
+        let items = std::mem::take(&mut *self.items.lock());
+        // The items are sorted below, so the iteration order doesn't matter.
+        #[allow(rustc::potential_query_instability)]
+        let mut items: Vec<_> =
+            items.into_iter().map(|(def_id, item)| (def_path_str(def_id), item)).collect();
+        // Sort by name first so that items with the same time are printed in a stable order.
+        items.sort_by(|(a, _), (b, _)| a.cmp(b));
+        items.sort_by(|(_, a), (_, b)| b.total().cmp(&a.total()));
+
+        let mut out = String::new();
+        let total_items = items.len();
+        writeln!(out, "item-time-report: top {} of {total_items} items", limit.min(total_items))
+            .unwrap();
+        writeln!(
+            out,
+            "{:>12} {:>12} {:>10} {:>12}  item",
+            "total (ms)", "codegen (ms)", "instances", "size est."
+        )
+        .unwrap();
+        for (name, item) in items.iter().take(limit) {
+            writeln!(
+                out,
+                "{:>12} {:>12} {:>10} {:>12}  {name}",
+                millis(item.total()),
+                millis(item.codegen),
+                item.instances,
+                item.size_estimate,
+            )
+            .unwrap();
+            #[allow(rustc::potential_query_instability)]
+            let mut queries: Vec<_> = item.queries.iter().collect();
+            queries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            queries.sort_by(|(_, a), (_, b)| b.cmp(a));
+            for (query, time) in queries.iter().take(QUERIES_PER_ITEM) {
+                writeln!(out, "{:>12}  {:>37}{query}", millis(**time), "").unwrap();
+            }
+        }
+        print!("{out}");
+    }
+}
+
+impl Drop for ItemTimer<'_> {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed();
+        let nested = NESTED.with_borrow_mut(|nested| {
+            let own = nested.pop().unwrap_or_default();
+            if let Some(parent) = nested.last_mut() {
+                *parent += elapsed;
+            }
+            own
+        });
+        let self_time = elapsed.saturating_sub(nested);
+
+        let mut items = self.times.items.lock();
+        let item = items.entry(self.def_id).or_default();
+        match self.activity {
+            Activity::Query(name) => *item.queries.entry(name).or_default() += self_time,
+            Activity::Codegen => item.codegen += self_time,
+        }
+    }
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64() * 1000.0)
+}
@@ -19,6 +19,7 @@
 pub mod config;
 pub mod cstore;
 pub mod filesearch;
+pub mod item_times;
 mod options;
 pub mod search_paths;
 
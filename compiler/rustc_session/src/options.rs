@@ -3,10 +3,12 @@
 use std::num::{IntErrorKind, NonZero};
 use std::path::PathBuf;
 use std::str;
+use std::time::Duration;
 
 use rustc_data_structures::fx::FxIndexMap;
 use rustc_data_structures::profiling::TimePassesFormat;
 use rustc_data_structures::stable_hasher::Hash64;
+use rustc_errors::fix::ApplySuggestions;
 use rustc_errors::{ColorConfig, LanguageIdentifier, TerminalUrl};
 use rustc_feature::UnstableFeatures;
 use rustc_span::edition::Edition;
@@ -140,6 +142,9 @@ pub struct Options {
         debuginfo_compression: DebugInfoCompression [TRACKED],
         lint_opts: Vec<(String, lint::Level)> [TRACKED_NO_CRATE_HASH],
         lint_cap: Option<lint::Level> [TRACKED_NO_CRATE_HASH],
+        /// The lint configuration file passed with `-Z lint-config`, read eagerly
+        /// so that edits to it are tracked like any other lint flag.
+        lint_config: Option<LintConfigFile> [TRACKED_NO_CRATE_HASH],
         describe_lints: bool [UNTRACKED],
         output_types: OutputTypes [TRACKED],
         search_paths: Vec<SearchPath> [UNTRACKED],
@@ -394,11 +399,16 @@ mod desc {
     pub const parse_strip: &str = "either `none`, `debuginfo`, or `symbols`";
     pub const parse_linker_flavor: &str = ::rustc_target::spec::LinkerFlavorCli::one_of();
     pub const parse_optimization_fuel: &str = "crate=integer";
+    pub const parse_dump_mir_format: &str = "`text` (default) or `json`";
     pub const parse_dump_mono_stats: &str = "`markdown` (default) or `json`";
+    pub const parse_print_sizes_format: &str = "`text` (default) or `json`";
+    pub const parse_apply_suggestions: &str = "either `in-place` or `diff`";
     pub const parse_instrument_coverage: &str = parse_bool;
     pub const parse_coverage_options: &str =
         "`block` | `branch` | `condition` | `mcdc` | `no-mir-spans`";
+    pub const parse_incremental_gc: &str = "a comma separated list of `max-size=<bytes>` (with an optional `K`, `M` or `G` suffix) and `max-age=<duration>` (with a `s`, `m`, `h` or `d` suffix)";
     pub const parse_instrument_xray: &str = "either a boolean (`yes`, `no`, `on`, `off`, etc), or a comma separated list of settings: `always` or `never` (mutually exclusive), `ignore-loops`, `instruction-threshold=N`, `skip-entry`, `skip-exit`";
+    pub const parse_item_time_report: &str = "an optional number of items";
     pub const parse_unpretty: &str = "`string` or `string=string`";
     pub const parse_treat_err_as_bug: &str = "either no value or a non-negative number";
     pub const parse_next_solver_config: &str =
@@ -673,6 +683,19 @@ pub(crate) fn parse_number<T: Copy + FromStr>(slot: &mut T, v: Option<&str>) ->
         }
     }
 
+    pub(crate) fn parse_item_time_report(slot: &mut Option<usize>, v: Option<&str>) -> bool {
+        match v {
+            None => {
+                *slot = Some(20);
+                true
+            }
+            Some(s) => {
+                *slot = s.parse().ok();
+                slot.is_some()
+            }
+        }
+    }
+
     /// Use this for any numeric option that lacks a static default.
     pub(crate) fn parse_opt_number<T: Copy + FromStr>(
         slot: &mut Option<T>,
@@ -959,6 +982,21 @@ pub(crate) fn parse_time_passes_format(slot: &mut TimePassesFormat, v: Option<&s
         }
     }
 
+    pub(crate) fn parse_dump_mir_format(slot: &mut DumpMirFormat, v: Option<&str>) -> bool {
+        match v {
+            None => true,
+            Some("json") => {
+                *slot = DumpMirFormat::Json;
+                true
+            }
+            Some("text") => {
+                *slot = DumpMirFormat::Text;
+                true
+            }
+            Some(_) => false,
+        }
+    }
+
     pub(crate) fn parse_dump_mono_stats(slot: &mut DumpMonoStatsFormat, v: Option<&str>) -> bool {
         match v {
             None => true,
@@ -974,6 +1012,33 @@ pub(crate) fn parse_dump_mono_stats(slot: &mut DumpMonoStatsFormat, v: Option<&s
         }
     }
 
+    pub(crate) fn parse_print_sizes_format(slot: &mut PrintSizesFormat, v: Option<&str>) -> bool {
+        match v {
+            None => true,
+            Some("json") => {
+                *slot = PrintSizesFormat::Json;
+                true
+            }
+            Some("text") => {
+                *slot = PrintSizesFormat::Text;
+                true
+            }
+            Some(_) => false,
+        }
+    }
+
+    pub(crate) fn parse_apply_suggestions(
+        slot: &mut Option<ApplySuggestions>,
+        v: Option<&str>,
+    ) -> bool {
+        *slot = match v {
+            Some("in-place") => Some(ApplySuggestions::InPlace),
+            Some("diff") => Some(ApplySuggestions::Diff),
+            _ => return false,
+        };
+        true
+    }
+
     pub(crate) fn parse_instrument_coverage(
         slot: &mut InstrumentCoverage,
         v: Option<&str>,
@@ -1017,6 +1082,49 @@ pub(crate) fn parse_coverage_options(slot: &mut CoverageOptions, v: Option<&str>
         true
     }
 
+    pub(crate) fn parse_incremental_gc(
+        slot: &mut Option<IncrementalGcBudget>,
+        v: Option<&str>,
+    ) -> bool {
+        let Some(v) = v else { return false };
+        let mut budget = IncrementalGcBudget::default();
+        for option in v.split(',') {
+            match option.split_once('=') {
+                Some(("max-size", size)) if budget.max_size.is_none() => {
+                    let (n, unit) = size.split_at(size.trim_end_matches(char::is_alphabetic).len());
+                    let shift = match unit {
+                        "" => 0,
+                        "K" => 10,
+                        "M" => 20,
+                        "G" => 30,
+                        _ => return false,
+                    };
+                    match n.parse::<u64>().ok().and_then(|n| n.checked_mul(1 << shift)) {
+                        Some(size) => budget.max_size = Some(size),
+                        None => return false,
+                    }
+                }
+                Some(("max-age", age)) if budget.max_age.is_none() => {
+                    let (n, unit) = age.split_at(age.trim_end_matches(char::is_alphabetic).len());
+                    let secs = match unit {
+                        "s" => 1,
+                        "m" => 60,
+                        "h" => 60 * 60,
+                        "d" => 24 * 60 * 60,
+                        _ => return false,
+                    };
+                    match n.parse::<u64>().ok().and_then(|n| n.checked_mul(secs)) {
+                        Some(secs) => budget.max_age = Some(Duration::from_secs(secs)),
+                        None => return false,
+                    }
+                }
+                _ => return false,
+            }
+        }
+        *slot = Some(budget);
+        true
+    }
+
     pub(crate) fn parse_instrument_xray(
         slot: &mut Option<InstrumentXRay>,
         v: Option<&str>,
@@ -1648,6 +1756,11 @@ pub(crate) fn parse_mir_include_spans(slot: &mut MirIncludeSpans, v: Option<&str
         "only allow the listed language features to be enabled in code (comma separated)"),
     always_encode_mir: bool = (false, parse_bool, [TRACKED],
         "encode MIR of all functions into the crate metadata (default: no)"),
+    apply_suggestions: Option<ApplySuggestions> = (None, parse_apply_suggestions, [UNTRACKED],
+        "apply machine-applicable suggestions to the source files (`in-place`) or print them \
+        as a unified diff (`diff`)"),
+    apply_suggestions_filter: Option<Vec<String>> = (None, parse_opt_comma_list, [UNTRACKED],
+        "only apply the suggestions of the given lints or error codes (comma separated)"),
     assert_incr_state: Option<String> = (None, parse_opt_string, [UNTRACKED],
         "assert that the incremental cache is in given state: \
          either `loaded` or `not-loaded`."),
@@ -1709,6 +1822,8 @@ pub(crate) fn parse_mir_include_spans(slot: &mut MirIncludeSpans, v: Option<&str
         "exclude the raw bytes of allocations when dumping MIR (used in tests) (default: no)"),
     dump_mir_exclude_pass_number: bool = (false, parse_bool, [UNTRACKED],
         "exclude the pass number when dumping MIR (used in tests) (default: no)"),
+    dump_mir_format: DumpMirFormat = (DumpMirFormat::Text, parse_dump_mir_format, [UNTRACKED],
+        "the format of the files written by `-Z dump-mir` (`text` (default) or `json`)"),
     dump_mir_graphviz: bool = (false, parse_bool, [UNTRACKED],
         "in addition to `.mir` files, create graphviz `.dot` files (default: no)"),
     dump_mono_stats: SwitchWithOptPath = (SwitchWithOptPath::Disabled,
@@ -1735,6 +1850,9 @@ pub(crate) fn parse_mir_include_spans(slot: &mut MirIncludeSpans, v: Option<&str
         "enforce the type length limit when monomorphizing instances in codegen"),
     export_executable_symbols: bool = (false, parse_bool, [TRACKED],
         "export symbols from executables, as if they were dynamic libraries"),
+    export_list: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "merge the symbol versions, visibilities and aliases of a TOML file into the exported \
+        symbols of dynamic and static libraries"),
     external_clangrt: bool = (false, parse_bool, [UNTRACKED],
         "rely on user specified linker commands to find clangrt"),
     extra_const_ub_checks: bool = (false, parse_bool, [TRACKED],
@@ -1776,6 +1894,14 @@ pub(crate) fn parse_mir_include_spans(slot: &mut MirIncludeSpans, v: Option<&str
         "display unnamed regions as `'<id>`, using a non-ident unique id (default: no)"),
     ignore_directory_in_diagnostics_source_blocks: Vec<String> = (Vec::new(), parse_string_push, [UNTRACKED],
         "do not display the source code block in diagnostics for files in the directory"),
+    incremental_explain: SwitchWithOptPath = (SwitchWithOptPath::Disabled,
+        parse_switch_with_opt_path, [UNTRACKED],
+        "explain why queries and codegen units were re-executed in an incremental session, \
+        printing a summary and writing a JSON report to the given directory (default: the current \
+        directory)"),
+    incremental_gc: Option<IncrementalGcBudget> = (None, parse_incremental_gc, [UNTRACKED],
+        "after compiling, evict the least recently used incremental session directories of all \
+        crates in the incremental directory until it fits the given size and age budget"),
     incremental_ignore_spans: bool = (false, parse_bool, [TRACKED],
         "ignore spans during ICH computation -- used for testing (default: no)"),
     incremental_info: bool = (false, parse_bool, [UNTRACKED],
@@ -1795,6 +1921,9 @@ pub(crate) fn parse_mir_include_spans(slot: &mut MirIncludeSpans, v: Option<&str
         "inlining threshold when the caller is a simple forwarding function (default: 30)"),
     inline_mir_hint_threshold: Option<usize> = (None, parse_opt_number, [TRACKED],
         "inlining threshold for functions with inline hint (default: 100)"),
+    inline_mir_hot_threshold: Option<usize> = (None, parse_opt_number, [TRACKED],
+        "inlining threshold for call sites that are hot in the profile passed with \
+        `-C profile-use` (default: 200)"),
     inline_mir_preserve_debug: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "when MIR inlining, whether to preserve debug info for callee variables \
         (default: preserve for debuginfo != None, otherwise remove)"),
@@ -1814,6 +1943,10 @@ pub(crate) fn parse_mir_include_spans(slot: &mut MirIncludeSpans, v: Option<&str
          `=skip-entry`
          `=skip-exit`
          Multiple options can be combined with commas."),
+    item_time_report: Option<usize> = (None, parse_item_time_report, [UNTRACKED],
+        "print the items that took the most compile time, attributing query, codegen and \
+        monomorphization costs to their source item; optionally takes the number of items \
+        to print (default: 20)"),
     layout_seed: Option<u64> = (None, parse_opt_number, [TRACKED],
         "seed layout randomization"),
     link_directives: bool = (true, parse_bool, [TRACKED],
@@ -1824,6 +1957,11 @@ pub(crate) fn parse_mir_include_spans(slot: &mut MirIncludeSpans, v: Option<&str
         "link the `.rlink` file generated by `-Z no-link` (default: no)"),
     linker_features: LinkerFeaturesCli = (LinkerFeaturesCli::default(), parse_linker_features, [UNTRACKED],
         "a comma-separated list of linker features to enable (+) or disable (-): `lld`"),
+    linker_map: SwitchWithOptPath = (SwitchWithOptPath::Disabled,
+        parse_switch_with_opt_path, [UNTRACKED],
+        "ask the linker to write a map file, next to the output by default"),
+    lint_config: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "read lint levels from a TOML file, applied at command-line precedence"),
     lint_llvm_ir: bool = (false, parse_bool, [TRACKED],
         "lint LLVM IR (default: no)"),
     lint_mir: bool = (false, parse_bool, [UNTRACKED],
@@ -1849,10 +1987,16 @@ pub(crate) fn parse_mir_include_spans(slot: &mut MirIncludeSpans, v: Option<&str
     merge_functions: Option<MergeFunctions> = (None, parse_merge_functions, [TRACKED],
         "control the operation of the MergeFunctions LLVM pass, taking \
         the same values as the target option of the same name"),
+    merge_identical_instances: bool = (false, parse_bool, [TRACKED],
+        "emit the instances of a generic item whose code is identical as aliases of one of them \
+        (default: no)"),
     meta_stats: bool = (false, parse_bool, [UNTRACKED],
         "gather metadata statistics (default: no)"),
     metrics_dir: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
         "stores metrics about the errors being emitted by rustc to disk"),
+    mir_bounds_check_remarks: bool = (false, parse_bool, [TRACKED],
+        "emit a note for each bounds check that the `BoundsCheckElimination` MIR pass could not \
+        remove (default: no)"),
     mir_emit_retag: bool = (false, parse_bool, [TRACKED],
         "emit Retagging MIR statements, interpreted e.g., by miri; implies -Zmir-opt-level=0 \
         (default: no)"),
@@ -1947,6 +2091,10 @@ pub(crate) fn parse_mir_include_spans(slot: &mut MirIncludeSpans, v: Option<&str
         "print the result of the monomorphization collection pass. \
          Value `lazy` means to use normal collection; `eager` means to collect all items.
          Note that this overwrites the effect `-Clink-dead-code` has on collection!"),
+    print_sizes_format: PrintSizesFormat = (PrintSizesFormat::Text, parse_print_sizes_format, [UNTRACKED],
+        "the format to use for -Z print-type-sizes, -Z print-vtable-sizes and -Z print-symbol-sizes (`text` (default) or `json`)"),
+    print_symbol_sizes: bool = (false, parse_bool, [UNTRACKED],
+        "print the size of each symbol of the linked output, read from the linker map (default: no)"),
     print_type_sizes: bool = (false, parse_bool, [UNTRACKED],
         "print layout information for each type encountered (default: no)"),
     print_vtable_sizes: bool = (false, parse_bool, [UNTRACKED],
@@ -1997,6 +2145,9 @@ pub(crate) fn parse_mir_include_spans(slot: &mut MirIncludeSpans, v: Option<&str
     saturating_float_casts: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "make float->int casts UB-free: numbers outside the integer type's range are clipped to \
         the max/min integer respectively, and NaN is mapped to 0 (default: yes)"),
+    secondary_codegen_backends: Vec<String> = (Vec::new(), parse_comma_list, [TRACKED],
+        "the backends that generate the functions marked `#[codegen_backend = \"name\"]`, in \
+        addition to the backend of the rest of the crate (default: none)"),
     self_profile: SwitchWithOptPath = (SwitchWithOptPath::Disabled,
         parse_switch_with_opt_path, [UNTRACKED],
         "run the self profiler and output the raw event data"),
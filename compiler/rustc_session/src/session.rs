@@ -17,8 +17,10 @@
 use rustc_errors::annotate_snippet_emitter_writer::AnnotateSnippetEmitter;
 use rustc_errors::codes::*;
 use rustc_errors::emitter::{stderr_destination, DynEmitter, HumanEmitter, HumanReadableErrorType};
+use rustc_errors::fix::{CollectedFixes, FixEmitter};
 use rustc_errors::json::JsonEmitter;
 use rustc_errors::registry::Registry;
+use rustc_errors::sarif::{SarifEmitter, SharedSarifLog};
 use rustc_errors::{
     fallback_fluent_bundle, Diag, DiagCtxt, DiagCtxtHandle, DiagMessage, Diagnostic,
     ErrorGuaranteed, FatalAbort, FluentBundle, LazyFallbackBundle, TerminalUrl,
@@ -35,12 +37,13 @@
 };
 
 use crate::code_stats::CodeStats;
-pub use crate::code_stats::{DataTypeKind, FieldInfo, FieldKind, SizeKind, VariantInfo};
+pub use crate::code_stats::{DataTypeKind, FieldInfo, FieldKind, NicheInfo, SizeKind, VariantInfo};
 use crate::config::{
     self, CoverageLevel, CrateType, DebugInfo, ErrorOutputType, FunctionReturn, Input,
     InstrumentCoverage, OptLevel, OutFileName, OutputType, RemapPathScopeComponents,
     SwitchWithOptPath,
 };
+use crate::item_times::ItemTimes;
 use crate::parse::{add_feature_diagnostics, ParseSess};
 use crate::search_paths::{PathKind, SearchPath};
 use crate::{errors, filesearch, lint};
@@ -159,6 +162,9 @@ pub struct Session {
     /// Data about code being compiled, gathered during compilation.
     pub code_stats: CodeStats,
 
+    /// Used by `-Z item-time-report`.
+    pub item_times: Option<ItemTimes>,
+
     /// Tracks fuel info if `-zfuel=crate=n` is specified.
     optimization_fuel: Lock<OptimizationFuel>,
 
@@ -215,6 +221,9 @@ pub struct Session {
     /// This is mainly useful for other tools that reads that debuginfo to figure out
     /// how to call the compiler with the same arguments.
     pub expanded_args: Vec<String>,
+
+    /// The machine-applicable suggestions recorded for `-Zapply-suggestions`.
+    collected_fixes: Option<Lrc<Lock<CollectedFixes>>>,
 }
 
 #[derive(PartialEq, Eq, PartialOrd, Ord)]
@@ -287,6 +296,7 @@ pub fn finish_diagnostics(&self, registry: &Registry) -> Option<ErrorGuaranteed>
         let mut guar = None;
         guar = guar.or(self.check_miri_unleashed_features());
         guar = guar.or(self.dcx().emit_stashed_diagnostics());
+        guar = guar.or(self.apply_suggestions());
         self.dcx().print_error_count(registry);
         if self.opts.json_future_incompat {
             self.dcx().emit_future_breakage_report();
@@ -294,6 +304,19 @@ pub fn finish_diagnostics(&self, registry: &Registry) -> Option<ErrorGuaranteed>
         guar
     }
 
+    /// Applies the suggestions collected for `-Zapply-suggestions`, if enabled.
+    fn apply_suggestions(&self) -> Option<ErrorGuaranteed> {
+        let mode = self.opts.unstable_opts.apply_suggestions?;
+        let fixes = self.collected_fixes.as_ref()?.lock();
+        let mut guar = None;
+        for err in fixes.apply(self.source_map(), mode) {
+            guar = Some(
+                self.dcx().emit_err(errors::FailedToApplySuggestions { err: err.to_string() }),
+            );
+        }
+        guar
+    }
+
     /// Returns true if the crate is a testing one.
     pub fn is_test_crate(&self) -> bool {
         self.opts.test
@@ -723,6 +746,12 @@ pub fn panic_strategy(&self) -> PanicStrategy {
         self.opts.cg.panic.unwrap_or(self.target.panic_strategy)
     }
 
+    /// Returns the name of the codegen backend that generates the code of the crate, or its path
+    /// if it is loaded from a dynamic library.
+    pub fn codegen_backend_name(&self) -> &str {
+        codegen_backend_name(self.opts.unstable_opts.codegen_backend.as_deref(), &self.target)
+    }
+
     pub fn fewer_names(&self) -> bool {
         if let Some(fewer_names) = self.opts.unstable_opts.fewer_names {
             fewer_names
@@ -922,6 +951,7 @@ fn default_emitter(
     source_map: Lrc<SourceMap>,
     bundle: Option<Lrc<FluentBundle>>,
     fallback_bundle: LazyFallbackBundle,
+    sarif_log: Option<SharedSarifLog>,
 ) -> Box<DynEmitter> {
     let macro_backtrace = sopts.unstable_opts.macro_backtrace;
     let track_diagnostics = sopts.unstable_opts.track_diagnostics;
@@ -987,6 +1017,19 @@ fn default_emitter(
             .track_diagnostics(track_diagnostics)
             .terminal_url(terminal_url),
         ),
+        config::ErrorOutputType::Sarif => {
+            // Continue the log of the early emitter, so that its diagnostics end up in the same
+            // document.
+            let emitter = match sarif_log {
+                Some(log) => SarifEmitter::with_log(log, source_map, fallback_bundle),
+                None => SarifEmitter::new(
+                    Box::new(io::BufWriter::new(io::stderr())),
+                    source_map,
+                    fallback_bundle,
+                ),
+            };
+            Box::new(emitter.registry(Some(registry)).fluent_bundle(bundle))
+        }
     }
 }
 
@@ -1032,7 +1075,23 @@ pub fn build_session(
         sopts.unstable_opts.translate_directionality_markers,
     );
     let source_map = rustc_span::source_map::get_source_map().unwrap();
-    let emitter = default_emitter(&sopts, registry, source_map.clone(), bundle, fallback_bundle);
+    let mut emitter = default_emitter(
+        &sopts,
+        registry,
+        source_map.clone(),
+        bundle,
+        fallback_bundle,
+        early_dcx.sarif_log.clone(),
+    );
+    let collected_fixes = if sopts.unstable_opts.apply_suggestions.is_some() {
+        let fixes = Lrc::new(Lock::new(CollectedFixes::new(
+            sopts.unstable_opts.apply_suggestions_filter.clone(),
+        )));
+        emitter = Box::new(FixEmitter::new(emitter, fixes.clone()));
+        Some(fixes)
+    } else {
+        None
+    };
 
     let mut dcx =
         DiagCtxt::new(emitter).with_flags(sopts.unstable_opts.dcx_flags(can_emit_warnings));
@@ -1111,6 +1170,7 @@ pub fn build_session(
         incr_comp_session: RwLock::new(IncrCompSession::NotInitialized),
         prof,
         code_stats: Default::default(),
+        item_times: sopts.unstable_opts.item_time_report.map(|_| ItemTimes::default()),
         optimization_fuel,
         print_fuel,
         jobserver: jobserver::client(),
@@ -1125,6 +1185,7 @@ pub fn build_session(
         cfg_version,
         using_internal_features,
         expanded_args,
+        collected_fixes,
     };
 
     validate_commandline_args_with_session_available(&sess);
@@ -1132,6 +1193,15 @@ pub fn build_session(
     sess
 }
 
+/// Returns the name of the codegen backend that `-Z codegen-backend=backend_name` selects for
+/// `target`, or its path if it is loaded from a dynamic library.
+pub fn codegen_backend_name<'a>(backend_name: Option<&'a str>, target: &'a Target) -> &'a str {
+    backend_name
+        .or(target.default_codegen_backend.as_deref())
+        .or(option_env!("CFG_DEFAULT_CODEGEN_BACKEND"))
+        .unwrap_or("llvm")
+}
+
 /// Validate command line arguments with a `Session`.
 ///
 /// If it is useful to have a Session available already for validating a commandline argument, you
@@ -1365,12 +1435,15 @@ enum IncrCompSession {
 /// A wrapper around an [`DiagCtxt`] that is used for early error emissions.
 pub struct EarlyDiagCtxt {
     dcx: DiagCtxt,
+    /// The log of the emitter of `dcx` if it is a `SarifEmitter`, which the emitter of the
+    /// session continues.
+    sarif_log: Option<SharedSarifLog>,
 }
 
 impl EarlyDiagCtxt {
     pub fn new(output: ErrorOutputType) -> Self {
-        let emitter = mk_emitter(output);
-        Self { dcx: DiagCtxt::new(emitter) }
+        let (emitter, sarif_log) = mk_emitter(output);
+        Self { dcx: DiagCtxt::new(emitter), sarif_log }
     }
 
     /// Swap out the underlying dcx once we acquire the user's preference on error emission
@@ -1379,8 +1452,9 @@ pub fn new(output: ErrorOutputType) -> Self {
     pub fn abort_if_error_and_set_error_format(&mut self, output: ErrorOutputType) {
         self.dcx.handle().abort_if_errors();
 
-        let emitter = mk_emitter(output);
+        let (emitter, sarif_log) = mk_emitter(output);
         self.dcx = DiagCtxt::new(emitter);
+        self.sarif_log = sarif_log;
     }
 
     #[allow(rustc::untranslatable_diagnostic)]
@@ -1427,7 +1501,7 @@ pub fn early_struct_warn(&self, msg: impl Into<DiagMessage>) -> Diag<'_, ()> {
     }
 }
 
-fn mk_emitter(output: ErrorOutputType) -> Box<DynEmitter> {
+fn mk_emitter(output: ErrorOutputType) -> (Box<DynEmitter>, Option<SharedSarifLog>) {
     // FIXME(#100717): early errors aren't translated at the moment, so this is fine, but it will
     // need to reference every crate that might emit an early error for translation to work.
     let fallback_bundle =
@@ -1450,8 +1524,18 @@ fn mk_emitter(output: ErrorOutputType) -> Box<DynEmitter> {
                 color_config,
             ))
         }
+        config::ErrorOutputType::Sarif => {
+            let emitter = SarifEmitter::new(
+                Box::new(io::BufWriter::new(io::stderr())),
+                Lrc::new(SourceMap::new(FilePathMapping::empty())),
+                fallback_bundle,
+            )
+            .omit_empty_log(true);
+            let log = emitter.log();
+            return (Box::new(emitter), Some(log));
+        }
     };
-    emitter
+    (emitter, None)
 }
 
 pub trait RemapFileNameExt {
@@ -25,6 +25,7 @@
 
 mod internal;
 pub mod pretty;
+pub mod transform;
 
 /// Convert an internal Rust compiler item into its stable counterpart, if one exists.
 ///
@@ -209,7 +210,26 @@ pub fn run<F, T>(tcx: TyCtxt<'_>, f: F) -> Result<T, Error>
 where
     F: FnOnce() -> T,
 {
-    let tables = TablesWrapper(RefCell::new(Tables {
+    let tables = new_tables(tcx);
+    stable_mir::compiler_interface::run(&tables, || init(&tables, f))
+}
+
+/// Like [run], but may be called while StableMIR is already running, e.g. from a query provider
+/// that was invoked by a StableMIR call. `f` runs with fresh tables, so items from the outer
+/// context must not be used in it and vice versa.
+pub(crate) fn run_nested<F, T>(tcx: TyCtxt<'_>, f: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    let tables = new_tables(tcx);
+    stable_mir::compiler_interface::run_nested(&tables, || {
+        let ptr = &tables as *const _ as *const ();
+        TLV.set(&Cell::new(ptr), f)
+    })
+}
+
+fn new_tables(tcx: TyCtxt<'_>) -> TablesWrapper<'_> {
+    TablesWrapper(RefCell::new(Tables {
         tcx,
         def_ids: IndexMap::default(),
         alloc_ids: IndexMap::default(),
@@ -219,8 +239,7 @@ pub fn run<F, T>(tcx: TyCtxt<'_>, f: F) -> Result<T, Error>
         ty_consts: IndexMap::default(),
         mir_consts: IndexMap::default(),
         layouts: IndexMap::default(),
-    }));
-    stable_mir::compiler_interface::run(&tables, || init(&tables, f))
+    }))
 }
 
 /// Instantiate and run the compiler with the provided arguments and callback.
@@ -272,10 +291,25 @@ pub fn run<F, T>(tcx: TyCtxt<'_>, f: F) -> Result<T, Error>
 #[macro_export]
 macro_rules! run {
     ($args:expr, $callback_fn:ident) => {
-        run_driver!($args, || $callback_fn())
+        run_driver!($args, Vec::new(), || $callback_fn())
     };
     ($args:expr, $callback:expr) => {
-        run_driver!($args, $callback)
+        run_driver!($args, Vec::new(), $callback)
+    };
+}
+
+/// Instantiate and run the compiler with the provided arguments, body transforms and callback.
+///
+/// This is similar to `run`, but the given [body transforms](crate::rustc_internal::transform)
+/// are applied to the optimized MIR of the local functions, which is then used for codegen.
+/// The transforms are a `Vec<rustc_internal::transform::BodyTransform>`.
+#[macro_export]
+macro_rules! run_with_transforms {
+    ($args:expr, $transforms:expr, $callback_fn:ident) => {
+        run_driver!($args, $transforms, || $callback_fn())
+    };
+    ($args:expr, $transforms:expr, $callback:expr) => {
+        run_driver!($args, $transforms, $callback)
     };
 }
 
@@ -286,10 +320,10 @@ macro_rules! run {
 #[macro_export]
 macro_rules! run_with_tcx {
     ($args:expr, $callback_fn:ident) => {
-        run_driver!($args, |tcx| $callback_fn(tcx), with_tcx)
+        run_driver!($args, Vec::new(), |tcx| $callback_fn(tcx), with_tcx)
     };
     ($args:expr, $callback:expr) => {
-        run_driver!($args, $callback, with_tcx)
+        run_driver!($args, Vec::new(), $callback, with_tcx)
     };
 }
 
@@ -302,16 +336,17 @@ macro_rules! optional {
     };
 }
 
-/// Prefer using [run!] and [run_with_tcx] instead.
+/// Prefer using [run!], [run_with_tcx] and [run_with_transforms] instead.
 ///
 /// This macro implements the instantiation of a StableMIR driver, and it will invoke
 /// the given callback after the compiler analyses.
 ///
-/// The third argument determines whether the callback requires `tcx` as an argument.
+/// The second argument are the body transforms to apply, and the fourth argument determines
+/// whether the callback requires `tcx` as an argument.
 #[macro_export]
 #[doc(hidden)]
 macro_rules! run_driver {
-    ($args:expr, $callback:expr $(, $with_tcx:ident)?) => {{
+    ($args:expr, $transforms:expr, $callback:expr $(, $with_tcx:ident)?) => {{
         use rustc_driver::{Callbacks, Compilation, RunCompiler};
         use rustc_interface::{interface, Queries};
         use stable_mir::CompilerError;
@@ -324,6 +359,7 @@ pub struct StableMir<B = (), C = (), F = fn($(optional!($with_tcx TyCtxt))?) ->
             F: FnOnce($(optional!($with_tcx TyCtxt))?) -> ControlFlow<B, C> + Send,
         {
             args: Vec<String>,
+            body_transforms: Vec<rustc_internal::transform::BodyTransform>,
             callback: Option<F>,
             result: Option<ControlFlow<B, C>>,
         }
@@ -334,9 +370,14 @@ impl<B, C, F> StableMir<B, C, F>
             C: Send,
             F: FnOnce($(optional!($with_tcx TyCtxt))?) -> ControlFlow<B, C> + Send,
         {
-            /// Creates a new `StableMir` instance, with given test_function and arguments.
-            pub fn new(args: Vec<String>, callback: F) -> Self {
-                StableMir { args, callback: Some(callback), result: None }
+            /// Creates a new `StableMir` instance, with given test_function, arguments and body
+            /// transforms.
+            pub fn new(
+                args: Vec<String>,
+                body_transforms: Vec<rustc_internal::transform::BodyTransform>,
+                callback: F,
+            ) -> Self {
+                StableMir { args, body_transforms, callback: Some(callback), result: None }
             }
 
             /// Runs the compiler against given target and tests it with `test_function`
@@ -368,6 +409,16 @@ impl<B, C, F> Callbacks for StableMir<B, C, F>
             C: Send,
             F: FnOnce($(optional!($with_tcx TyCtxt))?) -> ControlFlow<B, C> + Send,
         {
+            /// Installs the query overrides that apply the body transforms, if there are any.
+            fn config(&mut self, config: &mut interface::Config) {
+                if !self.body_transforms.is_empty() {
+                    config.override_queries = Some(rustc_internal::transform::override_queries);
+                    // The incremental cache does not know about the transforms, so bodies that
+                    // were cached by an earlier session must not be reused.
+                    config.opts.incremental = None;
+                }
+            }
+
             /// Called after analysis. Return value instructs the compiler whether to
             /// continue the compilation afterwards (defaults to `Compilation::Continue`)
             fn after_analysis<'tcx>(
@@ -382,6 +433,10 @@ fn after_analysis<'tcx>(
                         })
                         .unwrap();
                         if self.result.as_ref().is_some_and(|val| val.is_continue()) {
+                            rustc_internal::transform::transform_bodies(
+                                tcx,
+                                &self.body_transforms,
+                            );
                             Compilation::Continue
                         } else {
                             Compilation::Stop
@@ -393,7 +448,7 @@ fn after_analysis<'tcx>(
             }
         }
 
-        StableMir::new($args, $callback).run()
+        StableMir::new($args, $transforms, $callback).run()
     }};
 }
 
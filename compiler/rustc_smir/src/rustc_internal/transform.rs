@@ -0,0 +1,512 @@
+//! Support for changing the MIR that is used for codegen through StableMIR.
+//!
+//! Tools pass body transforms to the [run_with_transforms!](crate::run_with_transforms) macro,
+//! which installs [override_queries] as the `override_queries` callback of the compiler and, once
+//! the callback returned, calls [transform_bodies]. The transforms belong to that compiler session
+//! and are only known while [transform_bodies] runs. It computes the optimized MIR of every local
+//! function, and every transform receives the StableMIR body and may return an edited one, e.g.
+//! with new locals, new blocks or calls to other functions. The edited body is converted back,
+//! validated like the output of any other MIR pass, and used from then on, including for codegen
+//! and for inlining into other functions.
+//!
+//! The conversion starts from the original body, so the source scopes and the debug information
+//! of existing locals are kept. Statements and terminators are assigned the source scope of
+//! the original statement with the same span. Whether a block is a cleanup block is derived from
+//! the unwind edges that lead to it. Statements and terminators that StableMIR cannot represent
+//! completely, such as coverage statements and inline assembly, are only accepted if they are
+//! unchanged from the original body.
+//!
+//! Transforms run in their own StableMIR context, so items obtained in a transform must not be
+//! used outside of it and vice versa. A transform must not request the optimized body of the item
+//! it is transforming, as that would be a query cycle. Bodies that were already computed before
+//! [transform_bodies] runs, e.g. by the callback of the driver, are not transformed. Incremental
+//! compilation is disabled when there are transforms, as the transformed bodies are not tracked.
+
+use std::sync::OnceLock;
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_middle::mir;
+use rustc_middle::ty::TyCtxt;
+use rustc_middle::util::Providers;
+use rustc_session::Session;
+use rustc_span::def_id::LocalDefId;
+use rustc_span::source_map::Spanned;
+use rustc_span::Symbol;
+use scoped_tls::scoped_thread_local;
+use stable_mir::mir::visit::{Location, PlaceContext};
+use stable_mir::mir::{
+    BasicBlock, Body, Local, MirVisitor, Rvalue, Statement, StatementKind, Terminator,
+    TerminatorKind, UnwindAction, VarDebugInfo, VarDebugInfoContents,
+};
+use stable_mir::CrateItem;
+
+use super::{run_nested, with_tables, RustcInternal};
+use crate::rustc_smir::{Stable, Tables};
+
+/// A transform of the optimized MIR of local functions.
+///
+/// It is called with the item whose body is being computed and the current body, and returns the
+/// edited body, or `None` to keep the body as it is.
+pub type BodyTransform = fn(CrateItem, &Body) -> Option<Body>;
+
+// The transforms of the session that is running `transform_bodies` on this thread.
+scoped_thread_local!(static BODY_TRANSFORMS: Vec<BodyTransform>);
+
+/// The `optimized_mir` provider that was overridden by [override_queries].
+static DEFAULT_OPTIMIZED_MIR: OnceLock<
+    for<'tcx> fn(TyCtxt<'tcx>, LocalDefId) -> &'tcx mir::Body<'tcx>,
+> = OnceLock::new();
+
+/// Overrides the queries that apply the body transforms. This is meant to be used as the
+/// `override_queries` callback of `rustc_interface::Config`, and only if there are transforms.
+pub fn override_queries(_sess: &Session, providers: &mut Providers) {
+    let _ = DEFAULT_OPTIMIZED_MIR.set(providers.optimized_mir);
+    providers.optimized_mir = optimized_mir;
+}
+
+/// Computes the optimized MIR of the local functions with `transforms` applied. Transforms run in
+/// the order they are given in, each one receiving the output of the previous one.
+///
+/// This has no effect unless [override_queries] was installed in the compiler configuration.
+pub fn transform_bodies(tcx: TyCtxt<'_>, transforms: &[BodyTransform]) {
+    if transforms.is_empty() {
+        return;
+    }
+    BODY_TRANSFORMS.set(&transforms.to_vec(), || {
+        for &def_id in tcx.mir_keys(()) {
+            if tcx.def_kind(def_id).is_fn_like() {
+                tcx.ensure().optimized_mir(def_id);
+            }
+        }
+    });
+}
+
+fn optimized_mir(tcx: TyCtxt<'_>, def_id: LocalDefId) -> &mir::Body<'_> {
+    let body = DEFAULT_OPTIMIZED_MIR.get().unwrap()(tcx, def_id);
+    // Outside of `transform_bodies`, there is nothing to apply.
+    if !BODY_TRANSFORMS.is_set() || body.tainted_by_errors.is_some() {
+        return body;
+    }
+    let new_body = BODY_TRANSFORMS
+        .with(|transforms| run_nested(tcx, || transform_body(tcx, body, transforms)));
+    match new_body {
+        Some(new_body) => tcx.arena.alloc(new_body),
+        None => body,
+    }
+}
+
+/// Runs `transforms` on `body`, returning the new body if any of them changed it and the result
+/// is valid.
+fn transform_body<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &mir::Body<'tcx>,
+    transforms: &[BodyTransform],
+) -> Option<mir::Body<'tcx>> {
+    let (item, original) =
+        with_tables(|tables| (tables.crate_item(body.source.def_id()), body.stable(tables)));
+    let mut edited = None;
+    for transform in transforms {
+        if let Some(new_body) = transform(item, edited.as_ref().unwrap_or(&original)) {
+            edited = Some(new_body);
+        }
+    }
+    let edited = edited?;
+
+    let new_body = match with_tables(|tables| internal_body(tables, tcx, body, &original, &edited))
+    {
+        Ok(new_body) => new_body,
+        Err((span, msg)) => {
+            report_invalid_body(tcx, body, vec![(span, msg)]);
+            return None;
+        }
+    };
+    let failures = rustc_mir_transform::validate_body(tcx, new_body.phase, &new_body);
+    if !failures.is_empty() {
+        let failures = failures
+            .into_iter()
+            .map(|(location, msg)| {
+                (new_body.source_info(location).span, format!("{location:?}: {msg}"))
+            })
+            .collect();
+        report_invalid_body(tcx, body, failures);
+        return None;
+    }
+    Some(new_body)
+}
+
+#[allow(rustc::diagnostic_outside_of_impl)]
+#[allow(rustc::untranslatable_diagnostic)]
+fn report_invalid_body(
+    tcx: TyCtxt<'_>,
+    body: &mir::Body<'_>,
+    failures: Vec<(rustc_span::Span, String)>,
+) {
+    let mut diag = tcx.dcx().struct_span_err(
+        body.span,
+        format!(
+            "body transform produced invalid MIR for `{}`",
+            tcx.def_path_str(body.source.def_id())
+        ),
+    );
+    for (span, msg) in failures {
+        diag.span_note(span, msg);
+    }
+    diag.emit();
+}
+
+type ConversionResult<T> = Result<T, (rustc_span::Span, String)>;
+
+/// Converts the edited body `edited` back to an internal body. `original` is the StableMIR
+/// representation of `body`, which is used to recover what StableMIR does not represent.
+fn internal_body<'tcx>(
+    tables: &mut Tables<'_>,
+    tcx: TyCtxt<'tcx>,
+    body: &mir::Body<'tcx>,
+    original: &Body,
+    edited: &Body,
+) -> ConversionResult<mir::Body<'tcx>> {
+    let invalid = |msg: &str| Err((body.span, msg.to_string()));
+    if edited.blocks.is_empty() {
+        return invalid("the body has no basic blocks");
+    }
+    if edited.arg_locals().len() != body.arg_count || edited.spread_arg() != original.spread_arg() {
+        return invalid("the arguments of the body cannot be changed");
+    }
+    let mut range_checker = RangeChecker { num_locals: edited.locals().len(), error: None };
+    range_checker.visit_body(edited);
+    if let Some((span, msg)) = range_checker.error {
+        return Err((span.internal(tables, tcx), msg));
+    }
+    for block in &edited.blocks {
+        if block.terminator.successors().iter().any(|&target| target >= edited.blocks.len()) {
+            let span = block.terminator.span.internal(tables, tcx);
+            return Err((span, "jump to a basic block that does not exist".to_string()));
+        }
+    }
+
+    let mut scopes = FxHashMap::default();
+    for block in body.basic_blocks.iter() {
+        for statement in &block.statements {
+            scopes.entry(statement.source_info.span).or_insert(statement.source_info.scope);
+        }
+        let source_info = block.terminator().source_info;
+        scopes.entry(source_info.span).or_insert(source_info.scope);
+    }
+    let mut cx = InternalBodyCx { tables, tcx, body, original, scopes };
+
+    let is_cleanup = cleanup_blocks(&edited.blocks);
+    let basic_blocks = edited
+        .blocks
+        .iter()
+        .zip(is_cleanup)
+        .map(|(block, is_cleanup)| cx.basic_block(block, is_cleanup))
+        .collect::<ConversionResult<_>>()?;
+    let local_decls = edited
+        .locals()
+        .iter()
+        .enumerate()
+        .map(|(local, decl)| {
+            let ty = decl.ty.internal(cx.tables, tcx);
+            let source_info = cx.source_info(decl.span);
+            let mut new_decl = body
+                .local_decls
+                .get(mir::Local::from_usize(local))
+                .cloned()
+                .unwrap_or_else(|| mir::LocalDecl::with_source_info(ty, source_info));
+            new_decl.ty = ty;
+            new_decl.mutability = decl.mutability.internal(cx.tables, tcx);
+            new_decl.source_info.span = source_info.span;
+            new_decl
+        })
+        .collect();
+    let var_debug_info = edited.var_debug_info.iter().map(|info| cx.var_debug_info(info)).collect();
+
+    let mut new_body = body.clone();
+    *new_body.basic_blocks_mut() = basic_blocks;
+    new_body.local_decls = local_decls;
+    new_body.var_debug_info = var_debug_info;
+    Ok(new_body)
+}
+
+/// Returns whether each block is reachable from an unwind edge, and thus a cleanup block.
+fn cleanup_blocks(blocks: &[BasicBlock]) -> Vec<bool> {
+    let mut is_cleanup = vec![false; blocks.len()];
+    let mut stack: Vec<_> = blocks
+        .iter()
+        .filter_map(|block| match block.terminator.kind.unwind() {
+            Some(UnwindAction::Cleanup(target)) => Some(*target),
+            _ => None,
+        })
+        .collect();
+    while let Some(block) = stack.pop() {
+        if !std::mem::replace(&mut is_cleanup[block], true) {
+            stack.extend(blocks[block].terminator.successors());
+        }
+    }
+    is_cleanup
+}
+
+/// Finds the first use of a local that is not declared in the body.
+struct RangeChecker {
+    num_locals: usize,
+    error: Option<(stable_mir::ty::Span, String)>,
+}
+
+impl MirVisitor for RangeChecker {
+    fn visit_local(&mut self, local: &Local, _ptx: PlaceContext, location: Location) {
+        if *local >= self.num_locals && self.error.is_none() {
+            self.error = Some((location.span(), format!("use of undeclared local `_{local}`")));
+        }
+    }
+}
+
+struct InternalBodyCx<'a, 'b, 'tcx> {
+    tables: &'a mut Tables<'b>,
+    tcx: TyCtxt<'tcx>,
+    body: &'a mir::Body<'tcx>,
+    original: &'a Body,
+    /// The source scope of each span in `body`.
+    scopes: FxHashMap<rustc_span::Span, mir::SourceScope>,
+}
+
+impl<'tcx> InternalBodyCx<'_, '_, 'tcx> {
+    fn source_info(&mut self, span: stable_mir::ty::Span) -> mir::SourceInfo {
+        let span = span.internal(self.tables, self.tcx);
+        let scope = self.scopes.get(&span).copied().unwrap_or(mir::OUTERMOST_SOURCE_SCOPE);
+        mir::SourceInfo { span, scope }
+    }
+
+    fn basic_block(
+        &mut self,
+        block: &BasicBlock,
+        is_cleanup: bool,
+    ) -> ConversionResult<mir::BasicBlockData<'tcx>> {
+        let statements = block
+            .statements
+            .iter()
+            .map(|statement| self.statement(statement))
+            .collect::<ConversionResult<_>>()?;
+        let terminator = self.terminator(&block.terminator, is_cleanup)?;
+        Ok(mir::BasicBlockData { statements, terminator: Some(terminator), is_cleanup })
+    }
+
+    fn statement(&mut self, statement: &Statement) -> ConversionResult<mir::Statement<'tcx>> {
+        let (tables, tcx) = (&mut *self.tables, self.tcx);
+        let kind = match &statement.kind {
+            StatementKind::Assign(place, rvalue) => mir::StatementKind::Assign(Box::new((
+                place.internal(tables, tcx),
+                self.rvalue(rvalue, statement.span)?,
+            ))),
+            StatementKind::SetDiscriminant { place, variant_index } => {
+                mir::StatementKind::SetDiscriminant {
+                    place: Box::new(place.internal(tables, tcx)),
+                    variant_index: variant_index.internal(tables, tcx),
+                }
+            }
+            StatementKind::Deinit(place) => {
+                mir::StatementKind::Deinit(Box::new(place.internal(tables, tcx)))
+            }
+            StatementKind::StorageLive(local) => {
+                mir::StatementKind::StorageLive(mir::Local::from_usize(*local))
+            }
+            StatementKind::StorageDead(local) => {
+                mir::StatementKind::StorageDead(mir::Local::from_usize(*local))
+            }
+            StatementKind::Retag(kind, place) => mir::StatementKind::Retag(
+                kind.internal(tables, tcx),
+                Box::new(place.internal(tables, tcx)),
+            ),
+            StatementKind::PlaceMention(place) => {
+                mir::StatementKind::PlaceMention(Box::new(place.internal(tables, tcx)))
+            }
+            StatementKind::Intrinsic(intrinsic) => {
+                mir::StatementKind::Intrinsic(Box::new(intrinsic.internal(tables, tcx)))
+            }
+            StatementKind::ConstEvalCounter => mir::StatementKind::ConstEvalCounter,
+            StatementKind::Nop => mir::StatementKind::Nop,
+            StatementKind::FakeRead(..)
+            | StatementKind::AscribeUserType { .. }
+            | StatementKind::Coverage(..) => {
+                let original =
+                    self.original.blocks.iter().zip(self.body.basic_blocks.iter()).find_map(
+                        |(stable_block, block)| {
+                            let index =
+                                stable_block.statements.iter().position(|s| s == statement)?;
+                            Some(&block.statements[index])
+                        },
+                    );
+                return original.cloned().ok_or_else(|| {
+                    let span = statement.span.internal(self.tables, self.tcx);
+                    (span, format!("`{:?}` statements cannot be created", statement.kind))
+                });
+            }
+        };
+        Ok(mir::Statement { source_info: self.source_info(statement.span), kind })
+    }
+
+    fn rvalue(
+        &mut self,
+        rvalue: &Rvalue,
+        span: stable_mir::ty::Span,
+    ) -> ConversionResult<mir::Rvalue<'tcx>> {
+        let (tables, tcx) = (&mut *self.tables, self.tcx);
+        Ok(match rvalue {
+            Rvalue::AddressOf(mutability, place) => {
+                mir::Rvalue::RawPtr(mutability.internal(tables, tcx), place.internal(tables, tcx))
+            }
+            Rvalue::Aggregate(kind, operands) => mir::Rvalue::Aggregate(
+                Box::new(kind.internal(tables, tcx)),
+                operands.iter().map(|operand| operand.internal(tables, tcx)).collect(),
+            ),
+            Rvalue::BinaryOp(bin_op, lhs, rhs) => mir::Rvalue::BinaryOp(
+                bin_op.internal(tables, tcx),
+                Box::new((lhs.internal(tables, tcx), rhs.internal(tables, tcx))),
+            ),
+            Rvalue::Cast(kind, operand, ty) => mir::Rvalue::Cast(
+                kind.internal(tables, tcx),
+                operand.internal(tables, tcx),
+                ty.internal(tables, tcx),
+            ),
+            Rvalue::CheckedBinaryOp(bin_op, lhs, rhs) => {
+                let Some(checked) = bin_op.internal(tables, tcx).wrapping_to_overflowing() else {
+                    let msg = format!("`{bin_op:?}` cannot be checked for overflow");
+                    return Err((span.internal(tables, tcx), msg));
+                };
+                mir::Rvalue::BinaryOp(
+                    checked,
+                    Box::new((lhs.internal(tables, tcx), rhs.internal(tables, tcx))),
+                )
+            }
+            Rvalue::CopyForDeref(place) => mir::Rvalue::CopyForDeref(place.internal(tables, tcx)),
+            Rvalue::Discriminant(place) => mir::Rvalue::Discriminant(place.internal(tables, tcx)),
+            Rvalue::Len(place) => mir::Rvalue::Len(place.internal(tables, tcx)),
+            Rvalue::Ref(region, kind, place) => mir::Rvalue::Ref(
+                region.internal(tables, tcx),
+                kind.internal(tables, tcx),
+                place.internal(tables, tcx),
+            ),
+            Rvalue::Repeat(operand, count) => {
+                mir::Rvalue::Repeat(operand.internal(tables, tcx), count.internal(tables, tcx))
+            }
+            Rvalue::ShallowInitBox(operand, ty) => {
+                mir::Rvalue::ShallowInitBox(operand.internal(tables, tcx), ty.internal(tables, tcx))
+            }
+            Rvalue::ThreadLocalRef(item) => mir::Rvalue::ThreadLocalRef(item.internal(tables, tcx)),
+            Rvalue::NullaryOp(null_op, ty) => {
+                mir::Rvalue::NullaryOp(null_op.internal(tables, tcx), ty.internal(tables, tcx))
+            }
+            Rvalue::UnaryOp(un_op, operand) => {
+                mir::Rvalue::UnaryOp(un_op.internal(tables, tcx), operand.internal(tables, tcx))
+            }
+            Rvalue::Use(operand) => mir::Rvalue::Use(operand.internal(tables, tcx)),
+        })
+    }
+
+    fn terminator(
+        &mut self,
+        terminator: &Terminator,
+        is_cleanup: bool,
+    ) -> ConversionResult<mir::Terminator<'tcx>> {
+        let source_info = self.source_info(terminator.span);
+        let (tables, tcx) = (&mut *self.tables, self.tcx);
+        let block = mir::BasicBlock::from_usize;
+        let terminate_reason = if is_cleanup {
+            mir::UnwindTerminateReason::InCleanup
+        } else {
+            mir::UnwindTerminateReason::Abi
+        };
+        let unwind = |unwind: &UnwindAction| match *unwind {
+            UnwindAction::Continue => mir::UnwindAction::Continue,
+            UnwindAction::Unreachable => mir::UnwindAction::Unreachable,
+            UnwindAction::Terminate => mir::UnwindAction::Terminate(terminate_reason),
+            UnwindAction::Cleanup(target) => mir::UnwindAction::Cleanup(block(target)),
+        };
+        let kind = match &terminator.kind {
+            TerminatorKind::Goto { target } => mir::TerminatorKind::Goto { target: block(*target) },
+            TerminatorKind::SwitchInt { discr, targets } => mir::TerminatorKind::SwitchInt {
+                discr: discr.internal(tables, tcx),
+                targets: mir::SwitchTargets::new(
+                    targets.branches().map(|(value, target)| (value, block(target))),
+                    block(targets.otherwise()),
+                ),
+            },
+            TerminatorKind::Resume => mir::TerminatorKind::UnwindResume,
+            TerminatorKind::Abort => mir::TerminatorKind::UnwindTerminate(terminate_reason),
+            TerminatorKind::Return => mir::TerminatorKind::Return,
+            TerminatorKind::Unreachable => mir::TerminatorKind::Unreachable,
+            TerminatorKind::Drop { place, target, unwind: drop_unwind } => {
+                mir::TerminatorKind::Drop {
+                    place: place.internal(tables, tcx),
+                    target: block(*target),
+                    unwind: unwind(drop_unwind),
+                    replace: false,
+                }
+            }
+            TerminatorKind::Call { func, args, destination, target, unwind: call_unwind } => {
+                mir::TerminatorKind::Call {
+                    func: func.internal(tables, tcx),
+                    args: args
+                        .iter()
+                        .map(|arg| Spanned {
+                            node: arg.internal(tables, tcx),
+                            span: source_info.span,
+                        })
+                        .collect(),
+                    destination: destination.internal(tables, tcx),
+                    target: target.map(block),
+                    unwind: unwind(call_unwind),
+                    call_source: mir::CallSource::Normal,
+                    fn_span: source_info.span,
+                }
+            }
+            TerminatorKind::Assert { cond, expected, msg, target, unwind: assert_unwind } => {
+                mir::TerminatorKind::Assert {
+                    cond: cond.internal(tables, tcx),
+                    expected: *expected,
+                    msg: Box::new(msg.internal(tables, tcx)),
+                    target: block(*target),
+                    unwind: unwind(assert_unwind),
+                }
+            }
+            TerminatorKind::InlineAsm { .. } => {
+                let original = self
+                    .original
+                    .blocks
+                    .iter()
+                    .position(|stable_block| stable_block.terminator == *terminator);
+                return original
+                    .map(|index| self.body.basic_blocks[block(index)].terminator().clone())
+                    .ok_or_else(|| {
+                        (source_info.span, "inline assembly cannot be created".to_string())
+                    });
+            }
+        };
+        Ok(mir::Terminator { source_info, kind })
+    }
+
+    fn var_debug_info(&mut self, info: &VarDebugInfo) -> mir::VarDebugInfo<'tcx> {
+        let (tables, tcx) = (&mut *self.tables, self.tcx);
+        mir::VarDebugInfo {
+            name: Symbol::intern(&info.name),
+            source_info: mir::SourceInfo {
+                span: info.source_info.span.internal(tables, tcx),
+                scope: mir::SourceScope::from_u32(info.source_info.scope),
+            },
+            composite: info.composite.as_ref().map(|fragment| {
+                Box::new(mir::VarDebugInfoFragment {
+                    ty: fragment.ty.internal(tables, tcx),
+                    projection: fragment.projection.internal(tables, tcx),
+                })
+            }),
+            value: match &info.value {
+                VarDebugInfoContents::Place(place) => {
+                    mir::VarDebugInfoContents::Place(place.internal(tables, tcx))
+                }
+                VarDebugInfoContents::Const(constant) => {
+                    mir::VarDebugInfoContents::Const(constant.internal(tables, tcx))
+                }
+            },
+            argument_index: info.argument_index,
+        }
+    }
+}
@@ -10,15 +10,22 @@
 
 use rustc_abi::HasDataLayout;
 use rustc_hir::LangItem;
+use rustc_middle::traits::BuiltinImplSource;
 use rustc_middle::ty::layout::{
     FnAbiOf, FnAbiOfHelpers, HasParamEnv, HasTyCtxt, LayoutOf, LayoutOfHelpers,
 };
 use rustc_middle::ty::print::{with_forced_trimmed_paths, with_no_trimmed_paths};
 use rustc_middle::ty::{
-    GenericPredicates, Instance, List, ParamEnv, ScalarInt, TyCtxt, TypeVisitableExt, ValTree,
+    AssocKind, GenericPredicates, Instance, List, ParamEnv, ScalarInt, TyCtxt, TypeVisitableExt,
+    ValTree,
 };
 use rustc_middle::{mir, ty};
 use rustc_span::def_id::LOCAL_CRATE;
+use rustc_span::symbol::Ident;
+use rustc_trait_selection::infer::TyCtxtInferExt;
+use rustc_trait_selection::traits::{
+    ImplSource, Obligation, ObligationCause, ObligationCtxt, SelectionContext, SelectionError,
+};
 use stable_mir::abi::{FnAbi, Layout, LayoutShape};
 use stable_mir::compiler_interface::Context;
 use stable_mir::mir::alloc::GlobalAlloc;
@@ -27,8 +34,8 @@
 use stable_mir::target::{MachineInfo, MachineSize};
 use stable_mir::ty::{
     AdtDef, AdtKind, Allocation, ClosureDef, ClosureKind, FieldDef, FnDef, ForeignDef,
-    ForeignItemKind, GenericArgs, IntrinsicDef, LineInfo, MirConst, PolyFnSig, RigidTy, Span, Ty,
-    TyConst, TyKind, UintTy, VariantDef,
+    ForeignItemKind, GenericArgs, IntrinsicDef, LineInfo, MirConst, PolyFnSig, RigidTy, Span,
+    TraitRef, TraitResolution, Ty, TyConst, TyKind, UintTy, VariantDef,
 };
 use stable_mir::{Crate, CrateDef, CrateItem, CrateNum, DefId, Error, Filename, ItemKind, Symbol};
 
@@ -151,6 +158,13 @@ fn trait_impl(&self, impl_def: &stable_mir::ty::ImplDef) -> stable_mir::ty::Impl
         impl_trait.stable(&mut *tables)
     }
 
+    fn impls_of_trait(&self, trait_def: &stable_mir::ty::TraitDef) -> stable_mir::ImplTraitDecls {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let def_id = tables[trait_def.0];
+        tcx.all_impls(def_id).map(|impl_def_id| tables.impl_def(impl_def_id)).collect()
+    }
+
     fn generics_of(&self, def_id: stable_mir::DefId) -> stable_mir::ty::Generics {
         let mut tables = self.0.borrow_mut();
         let def_id = tables[def_id];
@@ -674,6 +688,58 @@ fn resolve_closure(
         )
     }
 
+    fn resolve_trait(&self, trait_ref: &TraitRef) -> TraitResolution {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let trait_ref = trait_ref.internal(&mut *tables, tcx);
+        let param_env = ParamEnv::reveal_all();
+        let Ok(trait_ref) = tcx.try_normalize_erasing_regions(param_env, trait_ref) else {
+            // One of the projections in the trait reference cannot be normalized, so the trait
+            // it projects from is not implemented.
+            return TraitResolution::NotImplemented;
+        };
+        match select_impl_source(tcx, param_env, trait_ref) {
+            Ok(ImplSource::UserDefined(data)) => TraitResolution::Impl {
+                def: tables.impl_def(data.impl_def_id),
+                args: data.args.stable(&mut *tables),
+            },
+            Ok(ImplSource::Builtin(BuiltinImplSource::Object(_), _)) => TraitResolution::Object,
+            Ok(ImplSource::Builtin(..)) => TraitResolution::Builtin,
+            // The environment has no where clauses that could prove the trait reference, but if
+            // one did, there would be no impl to point to, so the resolution is unknown.
+            Ok(ImplSource::Param(_)) => TraitResolution::Ambiguous,
+            Err(resolution) => resolution,
+        }
+    }
+
+    fn assoc_ty(&self, trait_ref: &TraitRef, name: &str) -> Option<Ty> {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let trait_ref = trait_ref.internal(&mut *tables, tcx);
+        let item = tcx.associated_items(trait_ref.def_id).find_by_name_and_kind(
+            tcx,
+            Ident::from_str(name),
+            AssocKind::Type,
+            trait_ref.def_id,
+        )?;
+        // Generic associated types need more arguments than the trait reference provides.
+        if !tcx.generics_of(item.def_id).own_params.is_empty() {
+            return None;
+        }
+        let projection = ty::Ty::new_projection_from_args(tcx, item.def_id, trait_ref.args);
+        Some(projection.stable(&mut *tables))
+    }
+
+    fn normalize_ty(&self, ty: Ty) -> Result<Ty, Error> {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let internal_ty = ty.internal(&mut *tables, tcx);
+        let normalized = tcx
+            .try_normalize_erasing_regions(ParamEnv::reveal_all(), internal_ty)
+            .map_err(|_| Error::new(format!("Failed to normalize `{internal_ty}`")))?;
+        Ok(normalized.stable(&mut *tables))
+    }
+
     fn eval_instance(&self, def: InstanceDef, const_ty: Ty) -> Result<Allocation, Error> {
         let mut tables = self.0.borrow_mut();
         let instance = tables.instances[def];
@@ -786,6 +852,36 @@ fn unop_ty(&self, un_op: UnOp, arg: Ty) -> Ty {
 
 pub struct TablesWrapper<'tcx>(pub RefCell<Tables<'tcx>>);
 
+/// Select the implementation of `trait_ref` and check that its nested obligations, e.g. the where
+/// clauses of an impl, hold as well. This is what `codegen_select_candidate` does, except that
+/// `trait_ref` doesn't have to be implemented.
+fn select_impl_source<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    param_env: ParamEnv<'tcx>,
+    trait_ref: ty::TraitRef<'tcx>,
+) -> Result<ImplSource<'tcx, ()>, TraitResolution> {
+    let infcx = tcx.infer_ctxt().ignoring_regions().build();
+    let mut selcx = SelectionContext::new(&infcx);
+    let obligation = Obligation::new(tcx, ObligationCause::dummy(), param_env, trait_ref);
+    let selection = match selcx.select(&obligation) {
+        Ok(Some(selection)) => selection,
+        Ok(None) | Err(SelectionError::Overflow(_)) => return Err(TraitResolution::Ambiguous),
+        Err(_) => return Err(TraitResolution::NotImplemented),
+    };
+
+    let ocx = ObligationCtxt::new(&infcx);
+    let impl_source = selection.map(|obligation| ocx.register_obligation(obligation));
+    let errors = ocx.select_all_or_error();
+    if errors.iter().any(|error| error.is_true_error()) {
+        return Err(TraitResolution::NotImplemented);
+    } else if !errors.is_empty() {
+        return Err(TraitResolution::Ambiguous);
+    }
+
+    let impl_source = tcx.erase_regions(infcx.resolve_vars_if_possible(impl_source));
+    if impl_source.has_infer() { Err(TraitResolution::Ambiguous) } else { Ok(impl_source) }
+}
+
 /// Implement error handling for extracting function ABI information.
 impl<'tcx> FnAbiOfHelpers<'tcx> for Tables<'tcx> {
     type FnAbiOfResult = Result<&'tcx rustc_target::abi::call::FnAbi<'tcx, ty::Ty<'tcx>>, Error>;
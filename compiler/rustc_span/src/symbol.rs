@@ -580,6 +580,8 @@
         cmp_partialord_lt,
         cmpxchg16b_target_feature,
         cmse_nonsecure_entry,
+        codegen_backend,
+        codegen_backend_attribute,
         coerce_unsized,
         cold,
         collapse_debuginfo,
@@ -1117,6 +1119,8 @@
         linker,
         lint_reasons,
         literal,
+        lldb_debugger_visualizer,
+        lldb_script_file,
         load,
         loaded_from_disk,
         local,
@@ -109,6 +109,7 @@
 mod v0;
 
 pub mod errors;
+pub mod symbol_path;
 pub mod test;
 
 /// This function computes the symbol name for the given `instance` and the
@@ -0,0 +1,432 @@
+//! Recovers the path of the item a symbol was generated for, so that the symbols of a linked
+//! binary can be grouped by crate and module, e.g. for `-Z print-symbol-sizes`.
+//!
+//! Symbols of the v0 scheme (see `v0.rs`) are parsed structurally: their path spells out the
+//! crate, the modules, impls and closures the item is nested in, and whether it is an
+//! instantiation of a generic item. Symbols of the legacy scheme only contain the demangled path
+//! of the item followed by a hash, so the path is split on `::` instead.
+
+/// The item a symbol belongs to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SymbolPath {
+    /// The name of the crate that defines the item.
+    pub crate_name: String,
+    /// The modules between the crate root and the item, e.g. `["vec", "into_iter"]`.
+    pub module: Vec<String>,
+    /// The path of the item within its module, e.g. `["{impl#12}", "next"]`, or
+    /// `["main", "{closure#0}"]` for a closure.
+    pub item: Vec<String>,
+    /// The demangled symbol without hashes, including the generic arguments of instantiations.
+    pub demangled: String,
+    /// Whether the symbol is an instantiation of a generic item (only known for v0 symbols).
+    pub is_instantiation: bool,
+}
+
+/// Returns the item that the given symbol refers to, or `None` if it is not a Rust symbol.
+pub fn symbol_path(symbol: &str) -> Option<SymbolPath> {
+    let demangled = rustc_demangle::try_demangle(symbol).ok()?;
+    let demangled = format!("{demangled:#}");
+
+    // The suffixes LLVM appends to the symbols it clones, e.g. `.llvm.1234`, are not mangled.
+    let mangled = symbol.split('.').next().unwrap();
+    let v0 = ["_R", "R", "__R"].iter().find_map(|prefix| mangled.strip_prefix(prefix));
+    match v0 {
+        Some(v0) => parse_v0(v0.as_bytes(), demangled),
+        None => Some(split_legacy(demangled)),
+    }
+}
+
+fn parse_v0(sym: &[u8], demangled: String) -> Option<SymbolPath> {
+    let mut parser = Parser { sym, next: 0, depth: 0 };
+    let mut path = PathSegments::default();
+    parser.path(&mut path)?;
+
+    // The item starts at the last segment if all segments are in the type namespace.
+    let item_start = path.item_start.unwrap_or(path.segments.len().saturating_sub(1)).max(1) - 1;
+    let mut segments = path.segments.into_iter();
+    let crate_name = segments.next()?;
+    let mut module: Vec<String> = segments.collect();
+    let item = module.split_off(item_start.min(module.len()));
+    Some(SymbolPath {
+        crate_name,
+        module,
+        item,
+        demangled,
+        is_instantiation: path.is_instantiation,
+    })
+}
+
+/// Splits a demangled legacy symbol, such as `std::io::stdio::_print` or
+/// `<alloc::vec::Vec<T,A> as core::ops::drop::Drop>::drop`, into its crate, module and item.
+/// Trait impls are grouped with the module of their self type.
+fn split_legacy(demangled: String) -> SymbolPath {
+    let segments = split_path(&demangled);
+    let (mut module, item) = match segments.split_first() {
+        Some((qualified, rest)) if qualified.starts_with('<') => {
+            let self_ty = qualified[1..].split(" as ").next().unwrap().trim_end_matches('>');
+            let self_ty = self_ty.trim_start_matches(['&', '*', '[', '(']);
+            let self_ty = self_ty
+                .strip_prefix("mut ")
+                .or_else(|| self_ty.strip_prefix("const "))
+                .or_else(|| self_ty.strip_prefix("dyn "))
+                .unwrap_or(self_ty);
+            let mut module = split_path(self_ty);
+            // The last segment is the self type itself.
+            module.pop();
+            let item = std::iter::once(*qualified).chain(rest.iter().copied()).collect();
+            (module, item)
+        }
+        _ => {
+            let split = segments.len().saturating_sub(1);
+            (segments[..split].to_vec(), segments[split..].to_vec())
+        }
+    };
+    let crate_name = if module.is_empty() { String::new() } else { module.remove(0).to_string() };
+    SymbolPath {
+        crate_name,
+        module: module.into_iter().map(String::from).collect(),
+        item: item.into_iter().map(String::from).collect(),
+        demangled,
+        is_instantiation: false,
+    }
+}
+
+/// Splits a path on the `::` that are not nested in generic arguments, tuples or arrays.
+fn split_path(path: &str) -> Vec<&str> {
+    let mut segments = vec![];
+    let mut depth = 0usize;
+    let mut start = 0;
+    let bytes = path.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'<' | b'(' | b'[' => depth += 1,
+            b'>' | b')' | b']' => depth = depth.saturating_sub(1),
+            b':' if depth == 0 && bytes.get(i + 1) == Some(&b':') => {
+                segments.push(&path[start..i]);
+                start = i + 2;
+                i += 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    // Strip the generic arguments of the last segment, e.g. of a self type.
+    let last = &path[start..];
+    segments.push(if last.starts_with('<') { last } else { last.split('<').next().unwrap() });
+    segments
+}
+
+#[derive(Default)]
+struct PathSegments {
+    segments: Vec<String>,
+    /// The index of the first segment that is not in the type namespace, i.e. that is not a
+    /// module (or a type, if it is followed by the impl of a nested item).
+    item_start: Option<usize>,
+    is_instantiation: bool,
+}
+
+impl PathSegments {
+    fn push(&mut self, segment: String, is_item: bool) {
+        if is_item && self.item_start.is_none() {
+            self.item_start = Some(self.segments.len());
+        }
+        self.segments.push(segment);
+    }
+}
+
+/// Symbols can come from any object file, so the nesting of their paths and types is bounded.
+const MAX_DEPTH: u32 = 500;
+
+/// A parser for the grammar of v0 symbols, that only records the path of the item and skips
+/// over the types and constants it contains. See the "Symbol Mangling" chapter of the rustc
+/// book for the grammar.
+struct Parser<'s> {
+    sym: &'s [u8],
+    next: usize,
+    depth: u32,
+}
+
+impl<'s> Parser<'s> {
+    fn peek(&self) -> Option<u8> {
+        self.sym.get(self.next).copied()
+    }
+
+    fn next(&mut self) -> Option<u8> {
+        let b = self.peek()?;
+        self.next += 1;
+        Some(b)
+    }
+
+    fn eat(&mut self, b: u8) -> bool {
+        let eaten = self.peek() == Some(b);
+        if eaten {
+            self.next += 1;
+        }
+        eaten
+    }
+
+    fn enter(&mut self) -> Option<()> {
+        self.depth += 1;
+        (self.depth <= MAX_DEPTH).then_some(())
+    }
+
+    /// `<base-62-number>`, terminated by `_`.
+    fn base62(&mut self) -> Option<u64> {
+        if self.eat(b'_') {
+            return Some(0);
+        }
+        let mut x: u64 = 0;
+        loop {
+            let d = match self.next()? {
+                b'_' => return x.checked_add(1),
+                c @ b'0'..=b'9' => c - b'0',
+                c @ b'a'..=b'z' => 10 + c - b'a',
+                c @ b'A'..=b'Z' => 36 + c - b'A',
+                _ => return None,
+            };
+            x = x.checked_mul(62)?.checked_add(d.into())?;
+        }
+    }
+
+    /// `[s <base-62-number>]`, which is 0 when absent.
+    fn disambiguator(&mut self) -> Option<u64> {
+        if self.eat(b's') { self.base62()?.checked_add(1) } else { Some(0) }
+    }
+
+    /// `<undisambiguated-identifier>`. Punycode identifiers are returned in their encoded form.
+    fn ident(&mut self) -> Option<&'s str> {
+        self.eat(b'u');
+        let start = self.next;
+        while self.peek().is_some_and(|b| b.is_ascii_digit()) {
+            self.next += 1;
+        }
+        let len: usize = std::str::from_utf8(&self.sym[start..self.next]).ok()?.parse().ok()?;
+        self.eat(b'_');
+        let end = self.next.checked_add(len).filter(|&end| end <= self.sym.len())?;
+        let ident = std::str::from_utf8(&self.sym[self.next..end]).ok()?;
+        self.next = end;
+        Some(ident)
+    }
+
+    /// `B <base-62-number>`, with the `B` already consumed: parses the backreferenced position
+    /// with `f`, then continues after the backreference.
+    fn backref<T>(&mut self, f: impl FnOnce(&mut Self) -> Option<T>) -> Option<T> {
+        let backref_start = self.next - 1;
+        let target = usize::try_from(self.base62()?).ok()?;
+        if target >= backref_start {
+            return None;
+        }
+        let after = std::mem::replace(&mut self.next, target);
+        let result = f(self);
+        self.next = after;
+        result
+    }
+
+    fn path(&mut self, out: &mut PathSegments) -> Option<()> {
+        self.enter()?;
+        match self.next()? {
+            b'C' => {
+                self.disambiguator()?;
+                out.push(self.ident()?.to_string(), false);
+            }
+            b'N' => {
+                let ns = self.next()?;
+                self.path(out)?;
+                let dis = self.disambiguator()?;
+                let name = self.ident()?;
+                let segment = match ns {
+                    // Namespaces that are used by the language, e.g. `t` for types and `v` for
+                    // values, show up as plain names.
+                    b'a'..=b'z' => name.to_string(),
+                    b'C' => format!("{{closure{}#{dis}}}", optional_name(name)),
+                    b'S' => format!("{{shim{}#{dis}}}", optional_name(name)),
+                    ns => format!("{{{}{}#{dis}}}", ns as char, optional_name(name)),
+                };
+                out.push(segment, ns != b't');
+            }
+            b'M' | b'X' => {
+                let is_trait_impl = self.sym[self.next - 1] == b'X';
+                let dis = if self.eat(b'I') {
+                    // The generic parameters of the impl, which wrap its parent path in an `I`
+                    // namespace holding the disambiguator.
+                    if !(self.eat(b'N') && self.eat(b'I')) {
+                        return None;
+                    }
+                    self.path(out)?;
+                    let dis = self.disambiguator()?;
+                    self.ident()?;
+                    self.generic_args()?;
+                    out.is_instantiation = true;
+                    dis
+                } else {
+                    let dis = self.disambiguator()?;
+                    self.path(out)?;
+                    dis
+                };
+                out.push(format!("{{impl#{dis}}}"), true);
+                self.skip_type()?;
+                if is_trait_impl {
+                    self.path(&mut PathSegments::default())?;
+                }
+            }
+            b'Y' => {
+                self.skip_type()?;
+                self.path(out)?;
+            }
+            b'I' => {
+                self.path(out)?;
+                self.generic_args()?;
+                out.is_instantiation = true;
+            }
+            b'B' => self.backref(|this| this.path(out))?,
+            _ => return None,
+        }
+        self.depth -= 1;
+        Some(())
+    }
+
+    /// `{<generic-arg>} E`, after the opening `I`.
+    fn generic_args(&mut self) -> Option<()> {
+        while !self.eat(b'E') {
+            if self.eat(b'L') {
+                self.base62()?;
+            } else if self.eat(b'K') {
+                self.skip_const()?;
+            } else {
+                self.skip_type()?;
+            }
+        }
+        Some(())
+    }
+
+    fn skip_type(&mut self) -> Option<()> {
+        self.enter()?;
+        match self.peek()? {
+            // The basic types, and the placeholder `p`.
+            b'a'..=b'z' => self.next += 1,
+            _ => match self.next()? {
+                b'A' => {
+                    self.skip_type()?;
+                    self.skip_const()?;
+                }
+                b'S' | b'P' | b'O' => self.skip_type()?,
+                b'T' => {
+                    while !self.eat(b'E') {
+                        self.skip_type()?;
+                    }
+                }
+                b'R' | b'Q' => {
+                    if self.eat(b'L') {
+                        self.base62()?;
+                    }
+                    self.skip_type()?;
+                }
+                b'F' => {
+                    self.skip_binder()?;
+                    self.eat(b'U');
+                    if self.eat(b'K') && !self.eat(b'C') {
+                        self.ident()?;
+                    }
+                    while !self.eat(b'E') {
+                        self.skip_type()?;
+                    }
+                    self.skip_type()?;
+                }
+                b'D' => {
+                    self.skip_binder()?;
+                    while !self.eat(b'E') {
+                        self.path(&mut PathSegments::default())?;
+                        while self.eat(b'p') {
+                            self.ident()?;
+                            self.skip_type()?;
+                        }
+                    }
+                    if !self.eat(b'L') {
+                        return None;
+                    }
+                    self.base62()?;
+                }
+                b'B' => self.backref(|this| this.skip_type())?,
+                _ => {
+                    self.next -= 1;
+                    self.path(&mut PathSegments::default())?;
+                }
+            },
+        }
+        self.depth -= 1;
+        Some(())
+    }
+
+    /// `[G <base-62-number>]`
+    fn skip_binder(&mut self) -> Option<()> {
+        if self.eat(b'G') {
+            self.base62()?;
+        }
+        Some(())
+    }
+
+    fn skip_const(&mut self) -> Option<()> {
+        self.enter()?;
+        match self.next()? {
+            b'p' => {}
+            b'B' => self.backref(|this| this.skip_const())?,
+            b'R' | b'Q' => {
+                if self.eat(b'e') {
+                    self.skip_hex()?;
+                } else {
+                    self.skip_const()?;
+                }
+            }
+            b'A' | b'T' => {
+                while !self.eat(b'E') {
+                    self.skip_const()?;
+                }
+            }
+            b'V' => {
+                self.path(&mut PathSegments::default())?;
+                match self.next()? {
+                    b'U' => {}
+                    b'T' => {
+                        while !self.eat(b'E') {
+                            self.skip_const()?;
+                        }
+                    }
+                    b'S' => {
+                        while !self.eat(b'E') {
+                            self.disambiguator()?;
+                            self.ident()?;
+                            self.skip_const()?;
+                        }
+                    }
+                    _ => return None,
+                }
+            }
+            // The integer, `bool` and `char` constants, which start with their type.
+            b'a'..=b'z' => {
+                self.eat(b'n');
+                self.skip_hex()?;
+            }
+            _ => return None,
+        }
+        self.depth -= 1;
+        Some(())
+    }
+
+    /// `{<hex-digit>} _`
+    fn skip_hex(&mut self) -> Option<()> {
+        while !self.eat(b'_') {
+            if !self.next()?.is_ascii_hexdigit() {
+                return None;
+            }
+        }
+        Some(())
+    }
+}
+
+fn optional_name(name: &str) -> String {
+    if name.is_empty() { String::new() } else { format!(":{name}") }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,89 @@
+use super::{symbol_path, SymbolPath};
+
+fn path(crate_name: &str, module: &[&str], item: &[&str], is_instantiation: bool) -> SymbolPath {
+    SymbolPath {
+        crate_name: crate_name.to_string(),
+        module: module.iter().map(|s| s.to_string()).collect(),
+        item: item.iter().map(|s| s.to_string()).collect(),
+        demangled: String::new(),
+        is_instantiation,
+    }
+}
+
+#[track_caller]
+fn check(symbol: &str, demangled: &str, expected: SymbolPath) {
+    let actual = symbol_path(symbol).unwrap();
+    assert_eq!(actual.demangled, demangled);
+    assert_eq!(SymbolPath { demangled: String::new(), ..actual }, expected);
+}
+
+#[test]
+fn test_v0_function() {
+    check("_RNvCs32gj3y4fnmh_4main4main", "main::main", path("main", &[], &["main"], false));
+}
+
+#[test]
+fn test_v0_inherent_method() {
+    check(
+        "_RNvMNtCs32gj3y4fnmh_4main6shapesNtB2_6Circle4area",
+        "<main::shapes::Circle>::area",
+        path("main", &["shapes"], &["{impl#0}", "area"], false),
+    );
+}
+
+#[test]
+fn test_v0_instantiation() {
+    check(
+        "_RINvNtCs32gj3y4fnmh_4main6shapes7largestmEB4_",
+        "main::shapes::largest::<u32>",
+        path("main", &["shapes"], &["largest"], true),
+    );
+}
+
+#[test]
+fn test_v0_closure() {
+    check(
+        "_RNCINvNtCsjrHSEGnQ3l9_3std2rt10lang_startuE0Cs32gj3y4fnmh_4main",
+        "std::rt::lang_start::<()>::{closure#0}",
+        path("std", &["rt"], &["lang_start", "{closure#0}"], true),
+    );
+}
+
+#[test]
+fn test_v0_llvm_suffix() {
+    check(
+        "_RNvCs32gj3y4fnmh_4main4main.llvm.1234",
+        "main::main",
+        path("main", &[], &["main"], false),
+    );
+}
+
+#[test]
+fn test_v0_crate_root() {
+    // There is no segment after the crate, which must not underflow.
+    check("_RC4main", "main", path("main", &[], &[], false));
+}
+
+#[test]
+fn test_legacy() {
+    check(
+        "_ZN3std2io5stdio6_print17h6e5d2c5f4e1b6b1aE",
+        "std::io::stdio::_print",
+        path("std", &["io", "stdio"], &["_print"], false),
+    );
+}
+
+#[test]
+fn test_legacy_trait_impl() {
+    check(
+        "_ZN70_$LT$alloc..vec..Vec$LT$T$C$A$GT$$u20$as$u20$core..ops..drop..Drop$GT$4drop17h6e5d2c5f4e1b6b1aE",
+        "<alloc::vec::Vec<T,A> as core::ops::drop::Drop>::drop",
+        path("alloc", &["vec"], &["<alloc::vec::Vec<T,A> as core::ops::drop::Drop>", "drop"], false),
+    );
+}
+
+#[test]
+fn test_not_rust() {
+    assert_eq!(symbol_path("main"), None);
+    assert_eq!(symbol_path("_ZN4main"), None);
+}
@@ -15,7 +15,7 @@
     AdtDef, AdtKind, Allocation, ClosureDef, ClosureKind, FieldDef, FnDef, ForeignDef,
     ForeignItemKind, ForeignModule, ForeignModuleDef, GenericArgs, GenericPredicates, Generics,
     ImplDef, ImplTrait, IntrinsicDef, LineInfo, MirConst, PolyFnSig, RigidTy, Span, TraitDecl,
-    TraitDef, Ty, TyConst, TyConstId, TyKind, UintTy, VariantDef,
+    TraitDef, TraitRef, TraitResolution, Ty, TyConst, TyConstId, TyKind, UintTy, VariantDef,
 };
 use crate::{
     mir, Crate, CrateItem, CrateItems, CrateNum, DefId, Error, Filename, ImplTraitDecls, ItemKind,
@@ -42,6 +42,8 @@ pub trait Context {
     fn all_trait_impls(&self) -> ImplTraitDecls;
     fn trait_impls(&self, crate_num: CrateNum) -> ImplTraitDecls;
     fn trait_impl(&self, trait_impl: &ImplDef) -> ImplTrait;
+    /// Retrieve all implementations of a trait in the local crate and its dependencies.
+    fn impls_of_trait(&self, trait_def: &TraitDef) -> ImplTraitDecls;
     fn generics_of(&self, def_id: DefId) -> Generics;
     fn predicates_of(&self, def_id: DefId) -> GenericPredicates;
     fn explicit_predicates_of(&self, def_id: DefId) -> GenericPredicates;
@@ -205,6 +207,15 @@ fn resolve_closure(
         kind: ClosureKind,
     ) -> Option<Instance>;
 
+    /// Find the implementation that satisfies a trait reference.
+    fn resolve_trait(&self, trait_ref: &TraitRef) -> TraitResolution;
+
+    /// Build the projection of a trait's associated type.
+    fn assoc_ty(&self, trait_ref: &TraitRef, name: &str) -> Option<Ty>;
+
+    /// Normalize a type and erase its regions.
+    fn normalize_ty(&self, ty: Ty) -> Result<Ty, Error>;
+
     /// Evaluate a static's initializer.
     fn eval_static_initializer(&self, def: StaticDef) -> Result<Allocation, Error>;
 
@@ -260,6 +271,20 @@ pub fn run<F, T>(context: &dyn Context, f: F) -> Result<T, Error>
     }
 }
 
+/// Like [run], but may be called while StableMIR is already running, in which case `context`
+/// replaces the running context until `f` returns.
+///
+/// This is meant for compiler callbacks that can be invoked from within a StableMIR call, such as
+/// query providers. Items from one context must not be used in the other.
+#[doc(hidden)]
+pub fn run_nested<F, T>(context: &dyn Context, f: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    let ptr: *const () = std::ptr::addr_of!(context) as _;
+    TLV.set(&Cell::new(ptr), f)
+}
+
 /// Execute the given function with access the compiler [Context].
 ///
 /// I.e., This function will load the current context and calls a function with it.
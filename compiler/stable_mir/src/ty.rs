@@ -10,7 +10,7 @@
 use crate::mir::alloc::{read_target_int, read_target_uint, AllocId};
 use crate::mir::mono::StaticDef;
 use crate::target::MachineInfo;
-use crate::{Filename, Opaque};
+use crate::{Filename, ImplTraitDecls, Opaque};
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Serialize)]
 pub struct Ty(usize);
@@ -92,6 +92,11 @@ pub fn unsigned_ty(inner: UintTy) -> Ty {
     pub fn layout(self) -> Result<Layout, Error> {
         with(|cx| cx.ty_layout(self))
     }
+
+    /// Normalize the associated types and other aliases in this type, and erase its regions.
+    pub fn normalize(self) -> Result<Ty, Error> {
+        with(|cx| cx.normalize_ty(self))
+    }
 }
 
 impl Ty {
@@ -895,6 +900,11 @@ impl TraitDef {
     pub fn declaration(trait_def: &TraitDef) -> TraitDecl {
         with(|cx| cx.trait_decl(trait_def))
     }
+
+    /// Retrieve all implementations of this trait in the local crate and its dependencies.
+    pub fn impls(&self) -> ImplTraitDecls {
+        with(|cx| cx.impls_of_trait(self))
+    }
 }
 
 crate_def! {
@@ -1382,6 +1392,48 @@ pub fn self_ty(&self) -> Ty {
         };
         self_ty
     }
+
+    /// Find which implementation satisfies this trait reference.
+    ///
+    /// The trait reference is resolved after normalizing it and erasing its regions. Generic
+    /// parameters are not assumed to satisfy any bounds, so this is meant to be used with
+    /// monomorphic types.
+    pub fn resolve(&self) -> TraitResolution {
+        with(|cx| cx.resolve_trait(self))
+    }
+
+    /// Build the projection `<Self as Trait>::name` of the associated type called `name`.
+    ///
+    /// Return `None` if the trait doesn't have such an associated type, or if it is generic.
+    /// Use [Ty::normalize] to get the type the projection is defined as.
+    pub fn assoc_ty(&self, name: &str) -> Option<Ty> {
+        with(|cx| cx.assoc_ty(self, name))
+    }
+}
+
+/// The result of resolving a trait reference with [TraitRef::resolve].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub enum TraitResolution {
+    /// The trait is implemented by an impl item, instantiated with the given arguments.
+    Impl { def: ImplDef, args: GenericArgs },
+    /// The trait is implemented by a trait object, and its methods are dispatched dynamically.
+    Object,
+    /// The trait is implemented by the compiler, e.g. `Copy` for tuples or `Fn` for closures.
+    Builtin,
+    /// The trait is not implemented.
+    NotImplemented,
+    /// It cannot be decided whether the trait is implemented, e.g. because of overflow.
+    Ambiguous,
+}
+
+impl TraitResolution {
+    /// Whether the trait is known to be implemented.
+    pub fn is_implemented(&self) -> bool {
+        matches!(
+            self,
+            TraitResolution::Impl { .. } | TraitResolution::Object | TraitResolution::Builtin
+        )
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize)]
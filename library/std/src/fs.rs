@@ -14,7 +14,7 @@
 use crate::ffi::OsString;
 use crate::fmt;
 use crate::io::{self, BorrowedCursor, IoSlice, IoSliceMut, Read, Seek, SeekFrom, Write};
-use crate::path::{Path, PathBuf};
+use crate::path::{Component, Path, PathBuf};
 use crate::sealed::Sealed;
 use crate::sync::Arc;
 use crate::sys::fs as fs_imp;
@@ -229,6 +229,49 @@ pub struct DirBuilder {
     recursive: bool,
 }
 
+/// A handle to an open directory, relative to which files and directories can
+/// be opened, created, removed and renamed.
+///
+/// Paths given to the methods of a `Dir` are resolved relative to the
+/// directory itself, not to its path: they keep referring to its children if
+/// the directory is renamed or moved, and a directory replaced at its path by
+/// another one is not affected. This avoids the time-of-check to time-of-use
+/// bugs of operations on paths that are built by joining a directory path with
+/// the names of its children.
+///
+/// By default, the symbolic links at the end of the paths given to the methods
+/// of a `Dir` are followed, as with the functions of this module. This can be
+/// refused with [`Dir::set_follow_symlinks`].
+///
+/// # Platform-specific behavior
+///
+/// This type uses the `openat`, `mkdirat`, `unlinkat`, `renameat` and
+/// `fstatat` functions on Unix, and `NtCreateFile` with a root directory handle
+/// on Windows. Paths relative to a `Dir` cannot contain prefixes, root
+/// directories, `.` or `..` components on Windows. This type is not supported
+/// on other platforms.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(dir_handle)]
+/// use std::fs::Dir;
+/// use std::io::Write;
+///
+/// fn main() -> std::io::Result<()> {
+///     let dir = Dir::open("/var/lib/app")?;
+///     let mut file = dir.create_file("state.tmp")?;
+///     file.write_all(b"ready")?;
+///     dir.rename("state.tmp", &dir, "state")?;
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "dir_handle", issue = "none")]
+pub struct Dir {
+    inner: fs_imp::DirHandle,
+    follow_symlinks: bool,
+}
+
 /// Reads the entire contents of a file into a bytes vector.
 ///
 /// This is a convenience function for using [`File::open`] and [`read_to_end`]
@@ -537,6 +580,184 @@ pub fn sync_data(&self) -> io::Result<()> {
         self.inner.datasync()
     }
 
+    /// Acquires an exclusive advisory lock on the file, blocking until it can be acquired.
+    ///
+    /// This acquires an exclusive advisory lock; no other file handle to this file may acquire
+    /// another lock.
+    ///
+    /// Advisory locks only exclude the processes and file handles that lock the file too: they do
+    /// not prevent reading or writing the file. The lock is tied to the underlying file
+    /// description, so it is shared with the handles created by [`try_clone`], and is released
+    /// when all of them are closed or when [`unlock`] is called.
+    ///
+    /// If this file handle, or a clone of it, already holds a lock, the exact behavior is
+    /// unspecified and platform dependent, including the possibility that it will deadlock.
+    /// However, if this method returns, then an exclusive lock is held.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix with the `LOCK_EX`
+    /// flag, and the `LockFileEx` function on Windows with the `LOCKFILE_EXCLUSIVE_LOCK` flag.
+    /// Note that, this [may change in the future][changes].
+    ///
+    /// On other platforms, and on Unix platforms without `flock`, this returns an error of kind
+    /// [`ErrorKind::Unsupported`](io::ErrorKind::Unsupported).
+    ///
+    /// [changes]: io#platform-specific-behavior
+    /// [`try_clone`]: File::try_clone
+    /// [`unlock`]: File::unlock
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::create("foo.lock")?;
+    ///     f.lock()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    pub fn lock(&self) -> io::Result<()> {
+        self.inner.lock()
+    }
+
+    /// Acquires a shared advisory lock on the file, blocking until it can be acquired.
+    ///
+    /// This acquires a shared advisory lock; more than one file handle may hold a shared lock,
+    /// but none may hold an exclusive lock at the same time.
+    ///
+    /// See [`lock`] for the semantics of advisory locks, and for what happens if this file
+    /// handle already holds a lock.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix with the `LOCK_SH`
+    /// flag, and the `LockFileEx` function on Windows. Note that, this
+    /// [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    /// [`lock`]: File::lock
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::open("foo.lock")?;
+    ///     f.lock_shared()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.inner.lock_shared()
+    }
+
+    /// Tries to acquire an exclusive advisory lock on the file, without blocking.
+    ///
+    /// This returns an error of kind [`ErrorKind::WouldBlock`](io::ErrorKind::WouldBlock) if
+    /// another file handle holds a lock on the file, and otherwise behaves like [`lock`].
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix with the `LOCK_EX`
+    /// and `LOCK_NB` flags, and the `LockFileEx` function on Windows with the
+    /// `LOCKFILE_EXCLUSIVE_LOCK` and `LOCKFILE_FAIL_IMMEDIATELY` flags. Note that, this
+    /// [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    /// [`lock`]: File::lock
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    /// use std::io::ErrorKind;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::create("foo.lock")?;
+    ///     match f.try_lock() {
+    ///         Ok(()) => println!("acquired the lock"),
+    ///         Err(e) if e.kind() == ErrorKind::WouldBlock => println!("the file is locked"),
+    ///         Err(e) => return Err(e),
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    pub fn try_lock(&self) -> io::Result<()> {
+        self.inner.try_lock()
+    }
+
+    /// Tries to acquire a shared advisory lock on the file, without blocking.
+    ///
+    /// This returns an error of kind [`ErrorKind::WouldBlock`](io::ErrorKind::WouldBlock) if
+    /// another file handle holds an exclusive lock on the file, and otherwise behaves like
+    /// [`lock_shared`].
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix with the `LOCK_SH`
+    /// and `LOCK_NB` flags, and the `LockFileEx` function on Windows with the
+    /// `LOCKFILE_FAIL_IMMEDIATELY` flag. Note that, this [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    /// [`lock_shared`]: File::lock_shared
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::open("foo.lock")?;
+    ///     f.try_lock_shared()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    pub fn try_lock_shared(&self) -> io::Result<()> {
+        self.inner.try_lock_shared()
+    }
+
+    /// Releases the advisory lock that this file handle holds on the file.
+    ///
+    /// Locks are also released when all the handles to the underlying file description are
+    /// closed, so calling this method is only needed to release a lock early.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix with the `LOCK_UN`
+    /// flag, and the `UnlockFile` function on Windows. Note that, this
+    /// [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::open("foo.lock")?;
+    ///     f.lock()?;
+    ///     f.unlock()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    pub fn unlock(&self) -> io::Result<()> {
+        self.inner.unlock()
+    }
+
     /// Truncates or extends the underlying file, updating the size of
     /// this file to become `size`.
     ///
@@ -2722,6 +2943,252 @@ fn as_inner_mut(&mut self) -> &mut fs_imp::DirBuilder {
     }
 }
 
+impl Dir {
+    /// Opens the directory at `path`.
+    ///
+    /// The symbolic links of `path` are followed.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `path` does not exist or is not
+    /// a directory, or if the user lacks the permission to open it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(dir_handle)]
+    /// use std::fs::Dir;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let dir = Dir::open("/var/lib/app")?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "dir_handle", issue = "none")]
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Dir> {
+        fs_imp::DirHandle::open(path.as_ref(), true)
+            .map(|inner| Dir { inner, follow_symlinks: true })
+    }
+
+    /// Sets whether the symbolic links at the end of the paths given to the
+    /// methods of this handle are followed.
+    ///
+    /// When they are not, opening a symbolic link as a file or a directory
+    /// fails, and [`Dir::metadata`] queries the symbolic link itself. Since
+    /// the other components of a path would be resolved, symbolic links
+    /// included, all the methods that take a path then only accept the names
+    /// of the children of the directory, and fail with
+    /// [`io::ErrorKind::InvalidInput`] for paths with several components, `..`
+    /// or a root. For [`Dir::rename`], `to` is checked against the setting of
+    /// `to_dir`. The directories opened with [`Dir::open_dir`] inherit this
+    /// setting.
+    ///
+    /// This option defaults to `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(dir_handle)]
+    /// use std::fs::Dir;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let mut dir = Dir::open("/tmp/upload")?;
+    ///     dir.set_follow_symlinks(false);
+    ///     // Fails if `data` is a symbolic link planted by another user.
+    ///     let file = dir.open_file("data")?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "dir_handle", issue = "none")]
+    pub fn set_follow_symlinks(&mut self, follow_symlinks: bool) {
+        self.follow_symlinks = follow_symlinks;
+    }
+
+    /// Returns whether the symbolic links at the end of the paths given to the
+    /// methods of this handle are followed, see [`Dir::set_follow_symlinks`].
+    #[unstable(feature = "dir_handle", issue = "none")]
+    #[must_use]
+    pub fn follow_symlinks(&self) -> bool {
+        self.follow_symlinks
+    }
+
+    /// Opens the directory at `path` relative to this one.
+    ///
+    /// The new handle follows symbolic links if this one does.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `path` does not exist or is not
+    /// a directory, or if it is a symbolic link that this handle does not
+    /// follow.
+    #[unstable(feature = "dir_handle", issue = "none")]
+    pub fn open_dir<P: AsRef<Path>>(&self, path: P) -> io::Result<Dir> {
+        self.check_nofollow_path(path.as_ref())?;
+        self.inner
+            .open_dir(path.as_ref(), self.follow_symlinks)
+            .map(|inner| Dir { inner, follow_symlinks: self.follow_symlinks })
+    }
+
+    /// Opens the file at `path` relative to this directory in read-only mode,
+    /// see [`File::open`].
+    #[unstable(feature = "dir_handle", issue = "none")]
+    pub fn open_file<P: AsRef<Path>>(&self, path: P) -> io::Result<File> {
+        self.open_file_with(path, OpenOptions::new().read(true))
+    }
+
+    /// Opens a file in write-only mode at `path` relative to this directory,
+    /// creating it if it does not exist and truncating it if it does, see
+    /// [`File::create`].
+    #[unstable(feature = "dir_handle", issue = "none")]
+    pub fn create_file<P: AsRef<Path>>(&self, path: P) -> io::Result<File> {
+        self.open_file_with(path, OpenOptions::new().write(true).create(true).truncate(true))
+    }
+
+    /// Opens the file at `path` relative to this directory with the options
+    /// specified by `options`, see [`OpenOptions::open`].
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// On Windows, the custom flags and security quality of service flags of
+    /// `options` are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(dir_handle)]
+    /// use std::fs::{Dir, OpenOptions};
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let dir = Dir::open("/var/log/app")?;
+    ///     let log = dir.open_file_with("app.log", OpenOptions::new().append(true).create(true))?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "dir_handle", issue = "none")]
+    pub fn open_file_with<P: AsRef<Path>>(
+        &self,
+        path: P,
+        options: &OpenOptions,
+    ) -> io::Result<File> {
+        self.check_nofollow_path(path.as_ref())?;
+        self.inner
+            .open_file(path.as_ref(), &options.0, self.follow_symlinks)
+            .map(|inner| File { inner })
+    }
+
+    /// Creates a new, empty directory at `path` relative to this directory,
+    /// see [`create_dir`].
+    #[unstable(feature = "dir_handle", issue = "none")]
+    pub fn create_dir<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.check_nofollow_path(path.as_ref())?;
+        self.inner.create_dir(path.as_ref())
+    }
+
+    /// Returns an iterator over the entries of this directory, see
+    /// [`read_dir`].
+    ///
+    /// The paths of the entries are joined to the path this directory was
+    /// opened with, and may no longer refer to them if the directory has been
+    /// moved since. On Windows, they are joined to the current path of the
+    /// directory instead.
+    #[unstable(feature = "dir_handle", issue = "none")]
+    pub fn read_dir(&self) -> io::Result<ReadDir> {
+        self.inner.read_dir().map(ReadDir)
+    }
+
+    /// Removes the file at `path` relative to this directory, see
+    /// [`remove_file`].
+    ///
+    /// If `path` is a symbolic link, the link itself is removed.
+    #[unstable(feature = "dir_handle", issue = "none")]
+    pub fn remove_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.check_nofollow_path(path.as_ref())?;
+        self.inner.remove_file(path.as_ref())
+    }
+
+    /// Removes the empty directory at `path` relative to this directory, see
+    /// [`remove_dir`].
+    #[unstable(feature = "dir_handle", issue = "none")]
+    pub fn remove_dir<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.check_nofollow_path(path.as_ref())?;
+        self.inner.remove_dir(path.as_ref())
+    }
+
+    /// Renames the file or directory at `from` relative to this directory to
+    /// `to` relative to `to_dir`, replacing the original file if `to` already
+    /// exists, see [`rename`].
+    ///
+    /// If `from` is a symbolic link, the link itself is renamed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(dir_handle)]
+    /// use std::fs::Dir;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let incoming = Dir::open("/srv/incoming")?;
+    ///     let done = Dir::open("/srv/done")?;
+    ///     incoming.rename("job", &done, "job")?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "dir_handle", issue = "none")]
+    pub fn rename<P: AsRef<Path>, Q: AsRef<Path>>(
+        &self,
+        from: P,
+        to_dir: &Dir,
+        to: Q,
+    ) -> io::Result<()> {
+        self.check_nofollow_path(from.as_ref())?;
+        to_dir.check_nofollow_path(to.as_ref())?;
+        self.inner.rename(from.as_ref(), &to_dir.inner, to.as_ref())
+    }
+
+    /// Queries the metadata of the file at `path` relative to this directory,
+    /// see [`metadata`].
+    ///
+    /// If this handle does not follow symbolic links, this queries the
+    /// metadata of a symbolic link at `path` itself, like
+    /// [`Dir::symlink_metadata`].
+    #[unstable(feature = "dir_handle", issue = "none")]
+    pub fn metadata<P: AsRef<Path>>(&self, path: P) -> io::Result<Metadata> {
+        self.check_nofollow_path(path.as_ref())?;
+        self.inner.metadata(path.as_ref(), self.follow_symlinks).map(Metadata)
+    }
+
+    /// Queries the metadata of the file at `path` relative to this directory
+    /// without following symbolic links, see [`symlink_metadata`].
+    #[unstable(feature = "dir_handle", issue = "none")]
+    pub fn symlink_metadata<P: AsRef<Path>>(&self, path: P) -> io::Result<Metadata> {
+        self.check_nofollow_path(path.as_ref())?;
+        self.inner.metadata(path.as_ref(), false).map(Metadata)
+    }
+
+    /// Refuses the paths that are not the name of a child of this directory
+    /// if it does not follow symbolic links: only the last component of a
+    /// path can be opened without following a symbolic link.
+    fn check_nofollow_path(&self, path: &Path) -> io::Result<()> {
+        let mut components = path.components();
+        if !self.follow_symlinks
+            && !matches!((components.next(), components.next()), (Some(Component::Normal(_)), None))
+        {
+            return Err(io::const_io_error!(
+                io::ErrorKind::InvalidInput,
+                "paths relative to a directory that does not follow symbolic links must be file names",
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[unstable(feature = "dir_handle", issue = "none")]
+impl fmt::Debug for Dir {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
 /// Returns `Ok(true)` if the path points at an existing entity.
 ///
 /// This function will traverse symbolic links to query information about the
@@ -2,7 +2,7 @@
 
 #[cfg(target_os = "macos")]
 use crate::ffi::{c_char, c_int};
-use crate::fs::{self, File, FileTimes, OpenOptions};
+use crate::fs::{self, Dir, File, FileTimes, OpenOptions};
 use crate::io::prelude::*;
 use crate::io::{BorrowedBuf, ErrorKind, SeekFrom};
 use crate::mem::MaybeUninit;
@@ -264,6 +264,200 @@ fn file_test_io_eof() {
     check!(fs::remove_file(&filename));
 }
 
+#[test]
+#[cfg(any(
+    windows,
+    target_os = "freebsd",
+    target_os = "illumos",
+    target_os = "linux",
+    target_os = "netbsd",
+    target_vendor = "apple",
+))]
+fn file_lock_multiple_shared() {
+    let tmpdir = tmpdir();
+    let filename = &tmpdir.join("file_lock_multiple_shared_test.txt");
+    let f1 = check!(File::create(filename));
+    let f2 = check!(OpenOptions::new().write(true).open(filename));
+
+    // Check that shared locks don't exclude each other, but exclude exclusive locks.
+    check!(f1.lock_shared());
+    check!(f2.lock_shared());
+    assert_eq!(f1.try_lock().unwrap_err().kind(), ErrorKind::WouldBlock);
+    check!(f1.unlock());
+    check!(f2.unlock());
+    check!(f1.try_lock_shared());
+    check!(f2.try_lock_shared());
+}
+
+#[test]
+#[cfg(any(
+    windows,
+    target_os = "freebsd",
+    target_os = "illumos",
+    target_os = "linux",
+    target_os = "netbsd",
+    target_vendor = "apple",
+))]
+fn file_lock_blocking() {
+    let tmpdir = tmpdir();
+    let filename = &tmpdir.join("file_lock_blocking_test.txt");
+    let f1 = check!(File::create(filename));
+    let f2 = check!(OpenOptions::new().write(true).open(filename));
+
+    // Check that an exclusive lock excludes both shared and exclusive locks.
+    check!(f1.lock());
+    assert_eq!(f2.try_lock().unwrap_err().kind(), ErrorKind::WouldBlock);
+    assert_eq!(f2.try_lock_shared().unwrap_err().kind(), ErrorKind::WouldBlock);
+    check!(f1.unlock());
+    check!(f2.try_lock());
+
+    // Check that a blocked lock is acquired once the file is unlocked.
+    let f1 = Arc::new(f1);
+    let t = thread::spawn({
+        let f1 = f1.clone();
+        move || check!(f1.lock())
+    });
+    thread::sleep(Duration::from_millis(100));
+    check!(f2.unlock());
+    t.join().unwrap();
+    assert_eq!(f2.try_lock_shared().unwrap_err().kind(), ErrorKind::WouldBlock);
+}
+
+#[test]
+#[cfg(any(
+    windows,
+    target_os = "freebsd",
+    target_os = "illumos",
+    target_os = "linux",
+    target_os = "netbsd",
+    target_vendor = "apple",
+))]
+fn file_lock_drop() {
+    let tmpdir = tmpdir();
+    let filename = &tmpdir.join("file_lock_drop_test.txt");
+    let f1 = check!(File::create(filename));
+    let f2 = check!(OpenOptions::new().write(true).open(filename));
+
+    // Check that closing the file releases its lock.
+    check!(f1.lock());
+    drop(f1);
+    check!(f2.try_lock());
+}
+
+#[test]
+#[cfg(windows)]
+fn file_lock_overlapped() {
+    use crate::os::windows::fs::OpenOptionsExt;
+    use crate::sys::c;
+
+    let tmpdir = tmpdir();
+    let filename = &tmpdir.join("file_lock_overlapped_test.txt");
+    let open = || {
+        check!(OpenOptions::new()
+            .create(true)
+            .write(true)
+            .custom_flags(c::FILE_FLAG_OVERLAPPED)
+            .open(filename))
+    };
+    let f1 = open();
+    let f2 = open();
+
+    // Check that locking a file opened for overlapped I/O waits for the lock to be taken.
+    check!(f1.lock());
+    assert_eq!(f2.try_lock().unwrap_err().kind(), ErrorKind::WouldBlock);
+    assert_eq!(f2.try_lock_shared().unwrap_err().kind(), ErrorKind::WouldBlock);
+    let f1 = Arc::new(f1);
+    let t = thread::spawn({
+        let f1 = f1.clone();
+        move || {
+            thread::sleep(Duration::from_millis(100));
+            check!(f1.unlock());
+        }
+    });
+    check!(f2.lock());
+    t.join().unwrap();
+    assert_eq!(f1.try_lock_shared().unwrap_err().kind(), ErrorKind::WouldBlock);
+    check!(f2.unlock());
+    check!(f1.try_lock_shared());
+}
+
+#[test]
+#[cfg(any(unix, windows))]
+fn dir_handle() {
+    let tmpdir = tmpdir();
+    let dir = check!(Dir::open(tmpdir.path()));
+
+    check!(dir.create_dir("sub"));
+    let sub = check!(dir.open_dir("sub"));
+    check!(check!(sub.create_file("file")).write_all(b"hello"));
+    let mut contents = String::new();
+    check!(check!(dir.open_file("sub/file")).read_to_string(&mut contents));
+    assert_eq!(contents, "hello");
+    assert!(check!(sub.metadata("file")).is_file());
+    assert!(check!(dir.metadata("sub")).is_dir());
+
+    let names: Vec<_> = check!(sub.read_dir()).map(|entry| check!(entry).file_name()).collect();
+    assert_eq!(names, ["file"]);
+
+    // Check that paths stay relative to the directory when it is moved.
+    check!(fs::rename(tmpdir.join("sub"), tmpdir.join("moved")));
+    check!(sub.rename("file", &dir, "renamed"));
+    assert!(tmpdir.join("renamed").is_file());
+    check!(dir.create_dir("sub"));
+    assert_eq!(sub.remove_dir("file").unwrap_err().kind(), ErrorKind::NotFound);
+
+    check!(dir.remove_file("renamed"));
+    check!(dir.remove_dir("moved"));
+    check!(dir.remove_dir("sub"));
+    assert!(check!(dir.read_dir()).next().is_none());
+}
+
+#[test]
+#[cfg(any(unix, windows))]
+fn dir_handle_nofollow() {
+    let tmpdir = tmpdir();
+    if !got_symlink_permission(&tmpdir) {
+        return;
+    };
+    check!(fs::create_dir(tmpdir.join("target")));
+    check!(File::create(tmpdir.join("target/file")));
+    check!(symlink_dir("target", tmpdir.join("dir_link")));
+    check!(symlink_file(Path::new("target").join("file"), tmpdir.join("file_link")));
+
+    let mut dir = check!(Dir::open(tmpdir.path()));
+    check!(dir.open_dir("dir_link"));
+    check!(dir.open_file("file_link"));
+    assert!(check!(dir.metadata("file_link")).is_file());
+    assert!(check!(dir.symlink_metadata("file_link")).is_symlink());
+
+    dir.set_follow_symlinks(false);
+    assert!(dir.open_dir("dir_link").is_err());
+    assert!(dir.open_file("file_link").is_err());
+    assert!(check!(dir.metadata("file_link")).is_symlink());
+    let target = check!(dir.open_dir("target"));
+    assert!(!target.follow_symlinks());
+
+    // Symlinks in the middle of a path would be followed, so such paths are refused.
+    check!(target.open_file("file"));
+    for path in [Path::new("dir_link/file"), Path::new("target/file"), Path::new("..")] {
+        assert_eq!(dir.open_file(path).unwrap_err().kind(), ErrorKind::InvalidInput);
+    }
+    assert_eq!(dir.open_dir(tmpdir.path()).unwrap_err().kind(), ErrorKind::InvalidInput);
+    // So are they for the methods that create, remove or rename entries.
+    let nested = Path::new("dir_link/new");
+    assert_eq!(dir.create_dir(nested).unwrap_err().kind(), ErrorKind::InvalidInput);
+    assert_eq!(dir.remove_file("dir_link/file").unwrap_err().kind(), ErrorKind::InvalidInput);
+    assert_eq!(dir.remove_dir(nested).unwrap_err().kind(), ErrorKind::InvalidInput);
+    assert_eq!(dir.rename(nested, &dir, "new").unwrap_err().kind(), ErrorKind::InvalidInput);
+    assert_eq!(dir.rename("target", &dir, nested).unwrap_err().kind(), ErrorKind::InvalidInput);
+    assert_eq!(dir.symlink_metadata(nested).unwrap_err().kind(), ErrorKind::InvalidInput);
+    assert!(tmpdir.join("target/file").exists());
+
+    // Removing a symlink removes the link, not its target.
+    check!(dir.remove_file("file_link"));
+    assert!(tmpdir.join("target/file").exists());
+}
+
 #[test]
 #[cfg(unix)]
 fn file_test_io_read_write_at() {
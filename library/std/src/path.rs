@@ -2092,6 +2092,17 @@ pub struct Path {
 #[stable(since = "1.7.0", feature = "strip_prefix")]
 pub struct StripPrefixError(());
 
+/// An error returned from [`Path::relative_to`] if there is no relative path
+/// from the base to the path.
+///
+/// This `struct` is created by the [`relative_to`] method on [`Path`].
+/// See its documentation for more.
+///
+/// [`relative_to`]: Path::relative_to
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[unstable(feature = "path_lexical", issue = "none")]
+pub struct RelativePathError(());
+
 impl Path {
     // The following (private!) function allows construction of a path from a u8
     // slice, which is only safe when it is known to follow the OsStr encoding.
@@ -2468,6 +2479,150 @@ fn _strip_prefix(&self, base: &Path) -> Result<&Path, StripPrefixError> {
             .ok_or(StripPrefixError(()))
     }
 
+    /// Normalizes the path lexically, without accessing the filesystem.
+    ///
+    /// This removes the `.` components, and the `..` components along with
+    /// the normal components that precede them. The `..` components that
+    /// follow the root directory are removed, since it is its own parent, and
+    /// the leading `..` components of a relative path are kept. A path that
+    /// normalizes to nothing becomes `.`, unless it is empty. The prefix and
+    /// root directory of the path are preserved.
+    ///
+    /// Unlike [`canonicalize`], this does not resolve symbolic links, so
+    /// the normalized path may refer to another file than the original one
+    /// if a component preceding a `..` is a symbolic link.
+    ///
+    /// [`canonicalize`]: Path::canonicalize
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// On Windows, verbatim paths (which start with `\\?\`) are returned
+    /// unchanged, since Windows does not interpret `.` and `..` in them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(path_lexical)]
+    /// use std::path::Path;
+    ///
+    /// assert_eq!(Path::new("/a/./b/../c").normalize_lexically(), Path::new("/a/c"));
+    /// assert_eq!(Path::new("/../a").normalize_lexically(), Path::new("/a"));
+    /// assert_eq!(Path::new("a/../../b").normalize_lexically(), Path::new("../b"));
+    /// assert_eq!(Path::new("a/..").normalize_lexically(), Path::new("."));
+    /// ```
+    #[unstable(feature = "path_lexical", issue = "none")]
+    #[must_use = "this returns the result of the operation, without modifying the original"]
+    pub fn normalize_lexically(&self) -> PathBuf {
+        let mut normalized = PathBuf::new();
+        let mut has_root = false;
+        // The number of normal components at the end of `normalized`.
+        let mut depth = 0;
+        for component in self.components() {
+            match component {
+                Component::Prefix(prefix) if prefix.kind().is_verbatim() => {
+                    return self.to_path_buf();
+                }
+                Component::Prefix(_) | Component::RootDir => {
+                    has_root |= component == Component::RootDir;
+                    normalized.push(component);
+                }
+                Component::CurDir => {}
+                Component::ParentDir if depth > 0 => {
+                    normalized.pop();
+                    depth -= 1;
+                }
+                Component::ParentDir if has_root => {}
+                Component::ParentDir => normalized.push(component),
+                Component::Normal(_) => {
+                    normalized.push(component);
+                    depth += 1;
+                }
+            }
+        }
+        if normalized.as_os_str().is_empty() && !self.as_os_str().is_empty() {
+            normalized.push(Component::CurDir);
+        }
+        normalized
+    }
+
+    /// Returns a relative path that, when joined onto `base`, refers to the
+    /// same path as `self`, without accessing the filesystem.
+    ///
+    /// Both paths are [normalized lexically] first, and the result can
+    /// contain `..` components. It is `.` if the paths are the same.
+    ///
+    /// [normalized lexically]: Path::normalize_lexically
+    ///
+    /// # Errors
+    ///
+    /// If only one of `self` and `base` has a root directory, if they have
+    /// different prefixes, or if `base` has `..` components that `self` does
+    /// not have at the same position, returns [`Err`]. In the latter case,
+    /// the name of the directory that `..` refers to is unknown.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// On Windows, the prefixes of the paths must have the same [kind], which
+    /// means that `C:\a` cannot be made relative to `\\?\C:\`, and
+    /// [`absolute`] can be used to make sure that two paths have a root
+    /// directory. Components are compared case-sensitively on all platforms.
+    ///
+    /// [kind]: PrefixComponent::kind
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(path_lexical)]
+    /// use std::path::Path;
+    ///
+    /// let path = Path::new("/usr/lib/rustlib/src");
+    ///
+    /// assert_eq!(path.relative_to("/usr/lib"), Ok("rustlib/src".into()));
+    /// assert_eq!(path.relative_to("/usr/share/doc"), Ok("../../lib/rustlib/src".into()));
+    /// assert_eq!(path.relative_to("/usr/lib/../lib/rustlib/src"), Ok(".".into()));
+    ///
+    /// assert!(path.relative_to("usr/lib").is_err());
+    /// assert!(Path::new("a").relative_to("../b").is_err());
+    /// ```
+    #[unstable(feature = "path_lexical", issue = "none")]
+    pub fn relative_to<P: AsRef<Path>>(&self, base: P) -> Result<PathBuf, RelativePathError> {
+        self._relative_to(base.as_ref())
+    }
+
+    fn _relative_to(&self, base: &Path) -> Result<PathBuf, RelativePathError> {
+        let path = self.normalize_lexically();
+        let base = base.normalize_lexically();
+        let mut path = path.components().filter(|c| *c != Component::CurDir).peekable();
+        let mut base = base.components().filter(|c| *c != Component::CurDir).peekable();
+
+        // Skip the common components, including the prefix and root directory, which must match.
+        loop {
+            match (path.peek(), base.peek()) {
+                (Some(a), Some(b)) if a == b => {}
+                (Some(Component::Prefix(_) | Component::RootDir), _)
+                | (_, Some(Component::Prefix(_) | Component::RootDir)) => {
+                    return Err(RelativePathError(()));
+                }
+                _ => break,
+            }
+            path.next();
+            base.next();
+        }
+
+        let mut relative = PathBuf::new();
+        for component in base {
+            match component {
+                Component::Normal(_) => relative.push(Component::ParentDir),
+                _ => return Err(RelativePathError(())),
+            }
+        }
+        relative.extend(path);
+        if relative.as_os_str().is_empty() {
+            relative.push(Component::CurDir);
+        }
+        Ok(relative)
+    }
+
     /// Determines whether `base` is a prefix of `self`.
     ///
     /// Only considers whole path components to match.
@@ -3443,6 +3598,16 @@ fn description(&self) -> &str {
     }
 }
 
+#[unstable(feature = "path_lexical", issue = "none")]
+impl fmt::Display for RelativePathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("no relative path from the base to the path")
+    }
+}
+
+#[unstable(feature = "path_lexical", issue = "none")]
+impl Error for RelativePathError {}
+
 /// Makes the path absolute without accessing the filesystem.
 ///
 /// If the path is relative, the current directory is used as the base directory.
@@ -1924,6 +1924,78 @@ macro_rules! unchanged(
     assert_eq!(absolute(r"COM1").unwrap().as_os_str(), Path::new(r"\\.\COM1").as_os_str());
 }
 
+#[test]
+fn test_normalize_lexically() {
+    macro_rules! tn(
+        ($path:expr, $expected:expr) => {
+            assert_eq!(Path::new($path).normalize_lexically().as_os_str(), $expected);
+        }
+    );
+
+    tn!("", "");
+    tn!(".", ".");
+    tn!("./a/./b/", "a/b");
+    tn!("a/b/../c", "a/c");
+    tn!("a/..", ".");
+    tn!("a/../..", "..");
+    tn!("../a/../../b", "../../b");
+    tn!("/a/b/../../..", "/");
+    tn!("/../a", "/a");
+    tn!("//a//b/.", "/a/b");
+
+    if cfg!(windows) {
+        tn!(r"C:\..\..", r"C:\");
+        tn!(r"C:a\..\..", r"C:..");
+        tn!(r"C:a\..", "C:");
+        tn!(r"\..\..", r"\");
+        tn!(r"\\server\share\..\..", r"\\server\share\");
+        tn!(r"\\server\share\a\..\b", r"\\server\share\b");
+        tn!(r"\\.\pipe\a\..", r"\\.\pipe\");
+        tn!(r"\\?\C:\..", r"\\?\C:\..");
+        tn!(r"\\?\UNC\server\share\a\.\..", r"\\?\UNC\server\share\a\.\..");
+        tn!(r"a/b\../c", r"a\c");
+    }
+}
+
+#[test]
+fn test_relative_to() {
+    macro_rules! tr(
+        ($path:expr, $base:expr, $expected:expr) => {
+            let relative = Path::new($path).relative_to($base).map(PathBuf::into_os_string);
+            let expected: Result<&str, RelativePathError> = $expected;
+            assert_eq!(relative, expected.map(OsString::from));
+        }
+    );
+
+    tr!("/a/b/c", "/a", Ok("b/c"));
+    tr!("/a/b/c", "/a/b/c/", Ok("."));
+    tr!("/a/b/c", "/a/d/e", Ok("../../b/c"));
+    tr!("/a", "/a/b/c", Ok("../.."));
+    tr!("/", "/a", Ok(".."));
+    tr!("a/b", "a/./c/../d", Ok("../b"));
+    tr!("a", "", Ok("a"));
+    tr!("", ".", Ok("."));
+    tr!("../a", "../b", Ok("../a"));
+    tr!("../a", "b", Ok("../../a"));
+    tr!("a", "../b", Err(RelativePathError(())));
+    tr!("/a", "a", Err(RelativePathError(())));
+    tr!("a", "/a", Err(RelativePathError(())));
+
+    if cfg!(windows) {
+        tr!(r"C:\a\b", r"C:\a\c", Ok(r"..\b"));
+        tr!(r"C:", r"C:\c", Err(RelativePathError(())));
+        tr!(r"C:", r"D:", Err(RelativePathError(())));
+        tr!(r"C:", r"C:a", Ok(r".."));
+        tr!(r"C:", r"", Err(RelativePathError(())));
+        tr!(r"C:\", r"\\?\C:\", Err(RelativePathError(())));
+        tr!(r"\\?\C:\a", r"\\?\C:\", Ok(r"a"));
+        tr!(r"C:a", r"C:b", Ok(r"..\a"));
+        tr!(r"\\server\share", r"\\server\share", Ok(r"."));
+        tr!(r"\\server\share\a", r"\\server\share\b\c", Ok(r"..\..\a"));
+        tr!(r"\\server\share", r"\\server\other", Err(RelativePathError(())));
+    }
+}
+
 #[test]
 #[should_panic = "path separator"]
 fn test_extension_path_sep() {
@@ -162,6 +162,7 @@
 #[stable(feature = "command_access", since = "1.57.0")]
 pub use crate::sys_common::process::CommandEnvs;
 use crate::sys_common::{AsInner, AsInnerMut, FromInner, IntoInner};
+use crate::time::Duration;
 use crate::{fmt, fs, str};
 
 /// Representation of a running or exited child process.
@@ -2216,6 +2217,57 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         Ok(self.handle.try_wait()?.map(ExitStatus))
     }
 
+    /// Waits for at most `timeout` for the child to exit, and collects its
+    /// exit status if it did.
+    ///
+    /// If the child has exited, then `Ok(Some(status))` is returned, and on
+    /// Unix the process ID is reaped. If it is still running once the timeout
+    /// has elapsed, then `Ok(None)` is returned. If an error occurs, then that
+    /// error is returned. Like [`try_wait`], this function is guaranteed to
+    /// repeatedly return a successful exit status once the child has exited.
+    ///
+    /// Note that unlike [`wait`], this function will not attempt to drop
+    /// stdin, since the child may keep reading it after a timeout.
+    ///
+    /// [`try_wait`]: Child::try_wait
+    /// [`wait`]: Child::wait
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// On Linux, this function waits for a [pidfd] of the child to become
+    /// readable with `poll`, using the one created by
+    /// [`CommandExt::create_pidfd`] if any. On Windows, it uses
+    /// `WaitForSingleObject`. Otherwise, and if pidfds are not supported by
+    /// the kernel, it polls the child with an increasing delay of up to 50ms
+    /// between attempts. No signal handler is installed for `SIGCHLD`.
+    ///
+    /// [pidfd]: crate::os::linux::process::PidFd
+    /// [`CommandExt::create_pidfd`]: crate::os::linux::process::CommandExt::create_pidfd
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(child_wait_timeout)]
+    /// use std::process::Command;
+    /// use std::time::Duration;
+    ///
+    /// let mut child = Command::new("sleep").arg("60").spawn().unwrap();
+    ///
+    /// match child.wait_timeout(Duration::from_secs(5)) {
+    ///     Ok(Some(status)) => println!("exited with: {status}"),
+    ///     Ok(None) => {
+    ///         println!("still running after 5 seconds, killing it");
+    ///         child.kill().unwrap();
+    ///         child.wait().unwrap();
+    ///     }
+    ///     Err(e) => println!("error attempting to wait: {e}"),
+    /// }
+    /// ```
+    #[unstable(feature = "child_wait_timeout", issue = "none")]
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        Ok(self.handle.wait_timeout(timeout)?.map(ExitStatus))
+    }
+
     /// Simultaneously waits for the child to exit and collect all remaining
     /// output on the stdout/stderr handles, returning an `Output`
     /// instance.
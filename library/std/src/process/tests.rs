@@ -229,6 +229,23 @@ fn test_finish_twice() {
     assert!(prog.wait().unwrap().code() == Some(1));
 }
 
+#[test]
+#[cfg_attr(any(target_os = "vxworks"), ignore)]
+fn test_wait_timeout() {
+    use crate::time::Duration;
+
+    // The child reads its stdin until it is closed.
+    let mut prog = if cfg!(target_os = "windows") {
+        Command::new("cmd").args(&["/C", "set /p line="]).stdin(Stdio::piped()).spawn().unwrap()
+    } else {
+        shell_cmd().arg("-c").arg("read line").stdin(Stdio::piped()).spawn().unwrap()
+    };
+    assert!(prog.wait_timeout(Duration::from_millis(10)).unwrap().is_none());
+    drop(prog.stdin.take());
+    let status = prog.wait_timeout(Duration::from_secs(60)).unwrap().unwrap();
+    assert_eq!(prog.wait_timeout(Duration::ZERO).unwrap(), Some(status));
+}
+
 #[test]
 #[cfg_attr(any(target_os = "vxworks"), ignore)]
 fn test_wait_with_output_once() {
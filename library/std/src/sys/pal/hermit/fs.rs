@@ -364,6 +364,26 @@ pub fn datasync(&self) -> io::Result<()> {
         self.fsync()
     }
 
+    pub fn lock(&self) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn lock_shared(&self) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn try_lock(&self) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        unsupported()
+    }
+
     pub fn truncate(&self, _size: u64) -> io::Result<()> {
         Err(Error::from_raw_os_error(22))
     }
@@ -489,6 +509,57 @@ unsafe fn from_raw_fd(raw_fd: RawFd) -> Self {
     }
 }
 
+pub struct DirHandle(!);
+
+impl DirHandle {
+    pub fn open(_path: &Path, _follow_symlinks: bool) -> io::Result<DirHandle> {
+        unsupported()
+    }
+
+    pub fn open_dir(&self, _path: &Path, _follow_symlinks: bool) -> io::Result<DirHandle> {
+        self.0
+    }
+
+    pub fn open_file(
+        &self,
+        _path: &Path,
+        _opts: &OpenOptions,
+        _follow_symlinks: bool,
+    ) -> io::Result<File> {
+        self.0
+    }
+
+    pub fn create_dir(&self, _path: &Path) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn read_dir(&self) -> io::Result<ReadDir> {
+        self.0
+    }
+
+    pub fn remove_file(&self, _path: &Path) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn remove_dir(&self, _path: &Path) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn rename(&self, _from: &Path, _to_dir: &DirHandle, _to: &Path) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn metadata(&self, _path: &Path, _follow_symlinks: bool) -> io::Result<FileAttr> {
+        self.0
+    }
+}
+
+impl fmt::Debug for DirHandle {
+    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0
+    }
+}
+
 pub fn readdir(path: &Path) -> io::Result<ReadDir> {
     let fd_raw = run_path_with_cstr(path, &|path| {
         cvt(unsafe { hermit_abi::open(path.as_ptr(), O_RDONLY | O_DIRECTORY, 0) })
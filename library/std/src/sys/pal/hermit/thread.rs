@@ -105,3 +105,19 @@ pub fn into_id(self) -> Tid {
 pub fn available_parallelism() -> io::Result<NonZero<usize>> {
     unsafe { Ok(NonZero::new_unchecked(hermit_abi::available_parallelism())) }
 }
+
+pub fn set_affinity(_cpus: &[usize]) -> io::Result<()> {
+    super::unsupported()
+}
+
+pub fn affinity() -> io::Result<Vec<usize>> {
+    super::unsupported()
+}
+
+pub fn set_priority(_priority: i32) -> io::Result<()> {
+    super::unsupported()
+}
+
+pub fn priority() -> io::Result<i32> {
+    super::unsupported()
+}
@@ -352,3 +352,19 @@ unsafe fn terminate_and_delete_current_task() -> ! {
 pub fn available_parallelism() -> io::Result<NonZero<usize>> {
     super::unsupported()
 }
+
+pub fn set_affinity(_cpus: &[usize]) -> io::Result<()> {
+    super::unsupported()
+}
+
+pub fn affinity() -> io::Result<Vec<usize>> {
+    super::unsupported()
+}
+
+pub fn set_priority(_priority: i32) -> io::Result<()> {
+    super::unsupported()
+}
+
+pub fn priority() -> io::Result<i32> {
+    super::unsupported()
+}
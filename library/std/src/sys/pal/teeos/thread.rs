@@ -138,6 +138,22 @@ pub fn available_parallelism() -> io::Result<NonZero<usize>> {
     Err(io::Error::UNKNOWN_THREAD_COUNT)
 }
 
+pub fn set_affinity(_cpus: &[usize]) -> io::Result<()> {
+    super::unsupported()
+}
+
+pub fn affinity() -> io::Result<Vec<usize>> {
+    super::unsupported()
+}
+
+pub fn set_priority(_priority: i32) -> io::Result<()> {
+    super::unsupported()
+}
+
+pub fn priority() -> io::Result<i32> {
+    super::unsupported()
+}
+
 fn min_stack_size(_: *const libc::pthread_attr_t) -> usize {
     libc::PTHREAD_STACK_MIN.try_into().expect("Infallible")
 }
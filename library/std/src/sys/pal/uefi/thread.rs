@@ -48,3 +48,19 @@ pub fn available_parallelism() -> io::Result<NonZero<usize>> {
     // UEFI is single threaded
     Ok(NonZero::new(1).unwrap())
 }
+
+pub fn set_affinity(_cpus: &[usize]) -> io::Result<()> {
+    unsupported()
+}
+
+pub fn affinity() -> io::Result<Vec<usize>> {
+    unsupported()
+}
+
+pub fn set_priority(_priority: i32) -> io::Result<()> {
+    unsupported()
+}
+
+pub fn priority() -> io::Result<i32> {
+    unsupported()
+}
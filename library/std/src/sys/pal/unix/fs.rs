@@ -96,6 +96,13 @@
 
 pub struct File(FileDesc);
 
+/// The lock taken or released by `File::flock`.
+enum FlockOperation {
+    Exclusive,
+    Shared,
+    Unlock,
+}
+
 // FIXME: This should be available on Linux with all `target_env`.
 // But currently only glibc exposes `statx` fn and structs.
 // We don't want to import unverified raw C structs here directly.
@@ -1230,6 +1237,64 @@ unsafe fn os_datasync(fd: c_int) -> c_int {
         }
     }
 
+    #[cfg(any(
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "fuchsia",
+        target_os = "illumos",
+        target_os = "linux",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_vendor = "apple",
+    ))]
+    fn flock(&self, operation: FlockOperation, nonblocking: bool) -> io::Result<()> {
+        let mut operation = match operation {
+            FlockOperation::Exclusive => libc::LOCK_EX,
+            FlockOperation::Shared => libc::LOCK_SH,
+            FlockOperation::Unlock => libc::LOCK_UN,
+        };
+        if nonblocking {
+            // `flock` then fails with `EWOULDBLOCK` if the file is locked.
+            operation |= libc::LOCK_NB;
+        }
+        cvt_r(|| unsafe { libc::flock(self.as_raw_fd(), operation) })?;
+        Ok(())
+    }
+
+    #[cfg(not(any(
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "fuchsia",
+        target_os = "illumos",
+        target_os = "linux",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_vendor = "apple",
+    )))]
+    fn flock(&self, _operation: FlockOperation, _nonblocking: bool) -> io::Result<()> {
+        Err(io::const_io_error!(io::ErrorKind::Unsupported, "file locking is not supported"))
+    }
+
+    pub fn lock(&self) -> io::Result<()> {
+        self.flock(FlockOperation::Exclusive, false)
+    }
+
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.flock(FlockOperation::Shared, false)
+    }
+
+    pub fn try_lock(&self) -> io::Result<()> {
+        self.flock(FlockOperation::Exclusive, true)
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<()> {
+        self.flock(FlockOperation::Shared, true)
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        self.flock(FlockOperation::Unlock, false)
+    }
+
     pub fn truncate(&self, size: u64) -> io::Result<()> {
         let size: off64_t =
             size.try_into().map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
@@ -1962,7 +2027,7 @@ pub fn chroot(dir: &Path) -> io::Result<()> {
     Err(io::const_io_error!(io::ErrorKind::Unsupported, "chroot not supported by vxworks"))
 }
 
-pub use remove_dir_impl::remove_dir_all;
+pub use dir_impl::{remove_dir_all, DirHandle};
 
 // Fallback for REDOX, ESP-ID, Horizon, Vita, Vxworks and Miri
 #[cfg(any(
@@ -1974,11 +2039,66 @@ pub fn chroot(dir: &Path) -> io::Result<()> {
     target_os = "vxworks",
     miri
 ))]
-mod remove_dir_impl {
+mod dir_impl {
+    use super::{File, FileAttr, OpenOptions, ReadDir};
+    use crate::path::Path;
+    use crate::sys::unsupported;
     pub use crate::sys_common::fs::remove_dir_all;
+    use crate::{fmt, io};
+
+    pub struct DirHandle(!);
+
+    impl DirHandle {
+        pub fn open(_path: &Path, _follow_symlinks: bool) -> io::Result<DirHandle> {
+            unsupported()
+        }
+
+        pub fn open_dir(&self, _path: &Path, _follow_symlinks: bool) -> io::Result<DirHandle> {
+            self.0
+        }
+
+        pub fn open_file(
+            &self,
+            _path: &Path,
+            _opts: &OpenOptions,
+            _follow_symlinks: bool,
+        ) -> io::Result<File> {
+            self.0
+        }
+
+        pub fn create_dir(&self, _path: &Path) -> io::Result<()> {
+            self.0
+        }
+
+        pub fn read_dir(&self) -> io::Result<ReadDir> {
+            self.0
+        }
+
+        pub fn remove_file(&self, _path: &Path) -> io::Result<()> {
+            self.0
+        }
+
+        pub fn remove_dir(&self, _path: &Path) -> io::Result<()> {
+            self.0
+        }
+
+        pub fn rename(&self, _from: &Path, _to_dir: &DirHandle, _to: &Path) -> io::Result<()> {
+            self.0
+        }
+
+        pub fn metadata(&self, _path: &Path, _follow_symlinks: bool) -> io::Result<FileAttr> {
+            self.0
+        }
+    }
+
+    impl fmt::Debug for DirHandle {
+        fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0
+        }
+    }
 }
 
-// Modern implementation using openat(), unlinkat() and fdopendir()
+// Modern implementation using openat(), unlinkat(), fdopendir() and the other `*at` functions
 #[cfg(not(any(
     target_os = "redox",
     target_os = "espidf",
@@ -1988,34 +2108,56 @@ mod remove_dir_impl {
     target_os = "vxworks",
     miri
 )))]
-mod remove_dir_impl {
+mod dir_impl {
+    #[cfg(not(any(
+        all(target_os = "linux", not(target_env = "musl")),
+        target_os = "emscripten",
+        target_os = "l4re",
+        target_os = "hurd"
+    )))]
+    use libc::fstatat as fstatat64;
+    #[cfg(any(
+        all(target_os = "linux", not(target_env = "musl")),
+        target_os = "emscripten",
+        target_os = "l4re",
+        target_os = "hurd"
+    ))]
+    use libc::fstatat64;
     #[cfg(not(all(target_os = "linux", target_env = "gnu")))]
-    use libc::{fdopendir, openat, unlinkat};
+    use libc::{fdopendir, mkdirat, openat, renameat, unlinkat};
     #[cfg(all(target_os = "linux", target_env = "gnu"))]
-    use libc::{fdopendir, openat64 as openat, unlinkat};
+    use libc::{fdopendir, mkdirat, openat64 as openat, renameat, unlinkat};
 
-    use super::{lstat, Dir, DirEntry, InnerReadDir, ReadDir};
+    use super::{lstat, stat64, Dir, DirEntry, File, FileAttr, InnerReadDir, OpenOptions, ReadDir};
     use crate::ffi::CStr;
-    use crate::io;
     use crate::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd};
     use crate::os::unix::prelude::{OwnedFd, RawFd};
     use crate::path::{Path, PathBuf};
     use crate::sys::common::small_c_string::run_path_with_cstr;
+    use crate::sys::fd::FileDesc;
     use crate::sys::{cvt, cvt_r};
     use crate::sys_common::ignore_notfound;
+    use crate::{fmt, io, mem};
 
     pub fn openat_nofollow_dironly(parent_fd: Option<RawFd>, p: &CStr) -> io::Result<OwnedFd> {
-        let fd = cvt_r(|| unsafe {
-            openat(
-                parent_fd.unwrap_or(libc::AT_FDCWD),
-                p.as_ptr(),
-                libc::O_CLOEXEC | libc::O_RDONLY | libc::O_NOFOLLOW | libc::O_DIRECTORY,
-            )
-        })?;
+        openat_dironly(parent_fd, p, false)
+    }
+
+    fn openat_dironly(
+        parent_fd: Option<RawFd>,
+        p: &CStr,
+        follow_symlinks: bool,
+    ) -> io::Result<OwnedFd> {
+        let mut flags = libc::O_CLOEXEC | libc::O_RDONLY | libc::O_DIRECTORY;
+        if !follow_symlinks {
+            flags |= libc::O_NOFOLLOW;
+        }
+        let fd =
+            cvt_r(|| unsafe { openat(parent_fd.unwrap_or(libc::AT_FDCWD), p.as_ptr(), flags) })?;
         Ok(unsafe { OwnedFd::from_raw_fd(fd) })
     }
 
-    fn fdreaddir(dir_fd: OwnedFd) -> io::Result<(ReadDir, RawFd)> {
+    fn fdreaddir(dir_fd: OwnedFd, root: PathBuf) -> io::Result<(ReadDir, RawFd)> {
         let ptr = unsafe { fdopendir(dir_fd.as_raw_fd()) };
         if ptr.is_null() {
             return Err(io::Error::last_os_error());
@@ -2023,10 +2165,7 @@ fn fdreaddir(dir_fd: OwnedFd) -> io::Result<(ReadDir, RawFd)> {
         let dirp = Dir(ptr);
         // file descriptor is automatically closed by libc::closedir() now, so give up ownership
         let new_parent_fd = dir_fd.into_raw_fd();
-        // a valid root is not needed because we do not call any functions involving the full path
-        // of the `DirEntry`s.
-        let dummy_root = PathBuf::new();
-        let inner = InnerReadDir { dirp, root: dummy_root };
+        let inner = InnerReadDir { dirp, root };
         Ok((ReadDir::new(inner), new_parent_fd))
     }
 
@@ -2085,7 +2224,9 @@ fn remove_dir_all_recursive(parent_fd: Option<RawFd>, path: &CStr) -> io::Result
         };
 
         // open the directory passing ownership of the fd
-        let (dir, fd) = fdreaddir(fd)?;
+        // a valid root is not needed because we do not call any functions involving the full path
+        // of the `DirEntry`s.
+        let (dir, fd) = fdreaddir(fd, PathBuf::new())?;
         for child in dir {
             let child = child?;
             let child_name = child.name_cstr();
@@ -2136,4 +2277,118 @@ fn remove_dir_all_modern(p: &Path) -> io::Result<()> {
     pub fn remove_dir_all(p: &Path) -> io::Result<()> {
         remove_dir_all_modern(p)
     }
+
+    pub struct DirHandle {
+        fd: OwnedFd,
+        /// The path the directory was opened with, which the entries of `read_dir` are joined to.
+        root: PathBuf,
+    }
+
+    impl DirHandle {
+        pub fn open(path: &Path, follow_symlinks: bool) -> io::Result<DirHandle> {
+            let fd = run_path_with_cstr(path, &|p| openat_dironly(None, p, follow_symlinks))?;
+            Ok(DirHandle { fd, root: path.to_path_buf() })
+        }
+
+        pub fn open_dir(&self, path: &Path, follow_symlinks: bool) -> io::Result<DirHandle> {
+            let fd = run_path_with_cstr(path, &|p| {
+                openat_dironly(Some(self.fd.as_raw_fd()), p, follow_symlinks)
+            })?;
+            Ok(DirHandle { fd, root: self.root.join(path) })
+        }
+
+        pub fn open_file(
+            &self,
+            path: &Path,
+            opts: &OpenOptions,
+            follow_symlinks: bool,
+        ) -> io::Result<File> {
+            let mut flags = libc::O_CLOEXEC
+                | opts.get_access_mode()?
+                | opts.get_creation_mode()?
+                | (opts.custom_flags as libc::c_int & !libc::O_ACCMODE);
+            if !follow_symlinks {
+                flags |= libc::O_NOFOLLOW;
+            }
+            run_path_with_cstr(path, &|p| {
+                // See `File::open_c` for the type of the mode.
+                let fd = cvt_r(|| unsafe {
+                    openat(self.fd.as_raw_fd(), p.as_ptr(), flags, opts.mode as libc::c_int)
+                })?;
+                Ok(File(unsafe { FileDesc::from_raw_fd(fd) }))
+            })
+        }
+
+        pub fn create_dir(&self, path: &Path) -> io::Result<()> {
+            run_path_with_cstr(path, &|p| {
+                cvt(unsafe { mkdirat(self.fd.as_raw_fd(), p.as_ptr(), 0o777) }).map(drop)
+            })
+        }
+
+        pub fn read_dir(&self) -> io::Result<ReadDir> {
+            // The stream takes ownership of its file descriptor, and moves its offset.
+            let fd = openat_nofollow_dironly(Some(self.fd.as_raw_fd()), c".")?;
+            let (dir, _) = fdreaddir(fd, self.root.clone())?;
+            Ok(dir)
+        }
+
+        pub fn remove_file(&self, path: &Path) -> io::Result<()> {
+            self.unlink(path, 0)
+        }
+
+        pub fn remove_dir(&self, path: &Path) -> io::Result<()> {
+            self.unlink(path, libc::AT_REMOVEDIR)
+        }
+
+        fn unlink(&self, path: &Path, flags: libc::c_int) -> io::Result<()> {
+            run_path_with_cstr(path, &|p| {
+                cvt(unsafe { unlinkat(self.fd.as_raw_fd(), p.as_ptr(), flags) }).map(drop)
+            })
+        }
+
+        pub fn rename(&self, from: &Path, to_dir: &DirHandle, to: &Path) -> io::Result<()> {
+            run_path_with_cstr(from, &|from| {
+                run_path_with_cstr(to, &|to| {
+                    cvt(unsafe {
+                        renameat(
+                            self.fd.as_raw_fd(),
+                            from.as_ptr(),
+                            to_dir.fd.as_raw_fd(),
+                            to.as_ptr(),
+                        )
+                    })
+                    .map(drop)
+                })
+            })
+        }
+
+        pub fn metadata(&self, path: &Path, follow_symlinks: bool) -> io::Result<FileAttr> {
+            let flags = if follow_symlinks { 0 } else { libc::AT_SYMLINK_NOFOLLOW };
+            run_path_with_cstr(path, &|p| {
+                cfg_has_statx! {
+                    if let Some(ret) = unsafe { super::try_statx(
+                        self.fd.as_raw_fd(),
+                        p.as_ptr(),
+                        flags | libc::AT_STATX_SYNC_AS_STAT,
+                        libc::STATX_ALL,
+                    ) } {
+                        return ret;
+                    }
+                }
+
+                let mut stat: stat64 = unsafe { mem::zeroed() };
+                cvt(unsafe { fstatat64(self.fd.as_raw_fd(), p.as_ptr(), &mut stat, flags) })?;
+                Ok(FileAttr::from_stat64(stat))
+            })
+        }
+    }
+
+    impl fmt::Debug for DirHandle {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("Dir")
+                .field("fd", &self.fd.as_raw_fd())
+                .field("path", &self.root)
+                .finish()
+        }
+    }
 }
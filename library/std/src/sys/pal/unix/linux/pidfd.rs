@@ -4,6 +4,7 @@
 use crate::sys::pal::unix::fd::FileDesc;
 use crate::sys::process::ExitStatus;
 use crate::sys_common::{AsInner, FromInner, IntoInner};
+use crate::time::{Duration, Instant};
 
 #[cfg(test)]
 mod tests;
@@ -12,6 +13,13 @@
 pub(crate) struct PidFd(FileDesc);
 
 impl PidFd {
+    /// Opens a pidfd for the process `pid`, which must not have been reaped yet if it is a child.
+    pub fn open(pid: libc::pid_t) -> io::Result<PidFd> {
+        // pidfd_open sets CLOEXEC by default
+        let fd = cvt(unsafe { libc::syscall(libc::SYS_pidfd_open, pid, 0) } as libc::c_int)?;
+        Ok(unsafe { PidFd::from_raw_fd(fd) })
+    }
+
     pub fn kill(&self) -> io::Result<()> {
         return cvt(unsafe {
             libc::syscall(
@@ -49,6 +57,36 @@ pub fn try_wait(&self) -> io::Result<Option<ExitStatus>> {
         }
         return Ok(Some(ExitStatus::from_waitid_siginfo(siginfo)));
     }
+
+    pub fn wait_timeout(&self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        if self.wait_exit(timeout)? { self.try_wait() } else { Ok(None) }
+    }
+
+    /// Waits for at most `timeout` for the process to exit, without reaping it, and returns
+    /// whether it exited.
+    pub fn wait_exit(&self, timeout: Duration) -> io::Result<bool> {
+        let deadline = Instant::now().checked_add(timeout);
+        let mut remaining = timeout;
+        loop {
+            // A pidfd becomes readable once the process exits. Round the timeout up so as not to
+            // return early, and wait repeatedly if it does not fit.
+            let mut pollfd =
+                libc::pollfd { fd: self.0.as_raw_fd(), events: libc::POLLIN, revents: 0 };
+            let timeout_ms = remaining.as_nanos().div_ceil(1_000_000).min(libc::c_int::MAX as u128);
+            match cvt(unsafe { libc::poll(&mut pollfd, 1, timeout_ms as libc::c_int) }) {
+                Ok(0) => {}
+                Ok(_) => return Ok(true),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+            if let Some(deadline) = deadline {
+                remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    return Ok(false);
+                }
+            }
+        }
+    }
 }
 
 impl AsInner<FileDesc> for PidFd {
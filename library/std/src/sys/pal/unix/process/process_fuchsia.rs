@@ -3,6 +3,7 @@
 use crate::num::NonZero;
 use crate::sys::process::process_common::*;
 use crate::sys::process::zircon::{zx_handle_t, Handle};
+use crate::time::Duration;
 use crate::{fmt, io, mem, ptr};
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -229,6 +230,25 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         }
         Ok(Some(ExitStatus(proc_info.return_code)))
     }
+
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        use crate::sys::process::zircon::*;
+
+        let timeout = timeout.as_nanos().try_into().unwrap_or(zx_duration_t::MAX);
+        let status = unsafe {
+            zx_object_wait_one(
+                self.handle.raw(),
+                ZX_TASK_TERMINATED,
+                zx_deadline_after(timeout),
+                ptr::null_mut(),
+            )
+        };
+        if status == ERR_TIMED_OUT {
+            return Ok(None);
+        }
+        zx_cvt(status)?;
+        self.try_wait()
+    }
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
@@ -16,14 +16,13 @@
 #[cfg(target_os = "linux")]
 use crate::sys::pal::unix::linux::pidfd::PidFd;
 use crate::sys::process::process_common::*;
-use crate::{fmt, mem, sys};
+use crate::time::{Duration, Instant};
+use crate::{fmt, mem, sys, thread};
 
 cfg_if::cfg_if! {
     // This workaround is only needed for QNX 7.0 and 7.1. The bug should have been fixed in 8.0
     if #[cfg(any(target_env = "nto70", target_env = "nto71"))] {
-        use crate::thread;
         use libc::{c_char, posix_spawn_file_actions_t, posix_spawnattr_t};
-        use crate::time::Duration;
         use crate::sync::LazyLock;
         // Get smallest amount of time we can sleep.
         // Return a common value if it cannot be determined.
@@ -965,6 +964,46 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
             Ok(Some(ExitStatus::new(status)))
         }
     }
+
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        if let Some(status) = self.try_wait()? {
+            return Ok(Some(status));
+        }
+        #[cfg(target_os = "linux")]
+        {
+            // Wait for a pidfd to become readable, opening one if the child was spawned without.
+            let exited = match self.pidfd.as_ref() {
+                Some(pid_fd) => Some(pid_fd.wait_exit(timeout)?),
+                None => match PidFd::open(self.pid) {
+                    Ok(pid_fd) => Some(pid_fd.wait_exit(timeout)?),
+                    Err(_) => None,
+                },
+            };
+            match exited {
+                Some(true) => return self.try_wait(),
+                Some(false) => return Ok(None),
+                None => {}
+            }
+        }
+        // Otherwise, poll the child with an exponential backoff. Waiting for `SIGCHLD` would
+        // require a signal handler, which would interfere with those of the program.
+        let deadline = Instant::now().checked_add(timeout);
+        let mut delay = Duration::from_millis(1);
+        loop {
+            let remaining = match deadline {
+                Some(deadline) => deadline.saturating_duration_since(Instant::now()),
+                None => delay,
+            };
+            if remaining.is_zero() {
+                return Ok(None);
+            }
+            thread::sleep(delay.min(remaining));
+            delay = (delay * 2).min(Duration::from_millis(50));
+            if let Some(status) = self.try_wait()? {
+                return Ok(Some(status));
+            }
+        }
+    }
 }
 
 /// Unix exit statuses
@@ -6,6 +6,7 @@
 use crate::sys::cvt;
 use crate::sys::pal::unix::thread;
 use crate::sys::process::process_common::*;
+use crate::time::{Duration, Instant};
 use crate::{fmt, sys};
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -180,6 +181,26 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
             Ok(Some(ExitStatus::new(status)))
         }
     }
+
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        // Poll the child with an exponential backoff, see `process_unix.rs`.
+        let deadline = Instant::now().checked_add(timeout);
+        let mut delay = Duration::from_millis(1);
+        loop {
+            if let Some(status) = self.try_wait()? {
+                return Ok(Some(status));
+            }
+            let remaining = match deadline {
+                Some(deadline) => deadline.saturating_duration_since(Instant::now()),
+                None => delay,
+            };
+            if remaining.is_zero() {
+                return Ok(None);
+            }
+            thread::Thread::sleep(delay.min(remaining));
+            delay = (delay * 2).min(Duration::from_millis(50));
+        }
+    }
 }
 
 /// Unix exit statuses
@@ -16,6 +16,8 @@
 pub type zx_time_t = i64;
 pub const ZX_TIME_INFINITE: zx_time_t = i64::MAX;
 
+pub type zx_duration_t = i64;
+
 pub type zx_signals_t = u32;
 
 pub const ZX_OBJECT_SIGNAL_3: zx_signals_t = 1 << 3;
@@ -76,6 +78,8 @@ pub struct zx_info_process_t {
 }
 
 extern "C" {
+    pub fn zx_deadline_after(nanoseconds: zx_duration_t) -> zx_time_t;
+
     pub fn zx_job_default() -> zx_handle_t;
 
     pub fn zx_task_kill(handle: zx_handle_t) -> zx_status_t;
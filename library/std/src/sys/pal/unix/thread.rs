@@ -507,6 +507,92 @@ pub fn available_parallelism() -> io::Result<NonZero<usize>> {
     }
 }
 
+/// The number of CPUs that fit in a `cpu_set_t`.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+const CPU_SETSIZE: usize = mem::size_of::<libc::cpu_set_t>() * 8;
+
+pub fn set_affinity(cpus: &[usize]) -> io::Result<()> {
+    cfg_if::cfg_if! {
+        if #[cfg(any(target_os = "android", target_os = "linux"))] {
+            let mut set: libc::cpu_set_t = unsafe { mem::zeroed() };
+            for &cpu in cpus {
+                if cpu >= CPU_SETSIZE {
+                    return Err(io::const_io_error!(
+                        io::ErrorKind::InvalidInput,
+                        "CPU index out of range of the affinity mask",
+                    ));
+                }
+                unsafe { libc::CPU_SET(cpu, &mut set) };
+            }
+            // A pid of 0 refers to the calling thread.
+            crate::sys::cvt(unsafe { libc::sched_setaffinity(0, mem::size_of::<libc::cpu_set_t>(), &set) })?;
+            Ok(())
+        } else {
+            let _ = cpus;
+            Err(io::Error::UNSUPPORTED_PLATFORM)
+        }
+    }
+}
+
+pub fn affinity() -> io::Result<Vec<usize>> {
+    cfg_if::cfg_if! {
+        if #[cfg(any(target_os = "android", target_os = "linux"))] {
+            let mut set: libc::cpu_set_t = unsafe { mem::zeroed() };
+            crate::sys::cvt(unsafe { libc::sched_getaffinity(0, mem::size_of::<libc::cpu_set_t>(), &mut set) })?;
+            Ok((0..CPU_SETSIZE).filter(|&cpu| unsafe { libc::CPU_ISSET(cpu, &set) }).collect())
+        } else {
+            Err(io::Error::UNSUPPORTED_PLATFORM)
+        }
+    }
+}
+
+pub fn set_priority(priority: i32) -> io::Result<()> {
+    cfg_if::cfg_if! {
+        if #[cfg(any(target_os = "android", target_os = "linux"))] {
+            // Linux keeps the nice value per thread, so this only affects the calling thread.
+            // `gettid` is only declared by glibc 2.30 and later.
+            let tid = unsafe { libc::syscall(libc::SYS_gettid) };
+            crate::sys::cvt(unsafe { libc::setpriority(libc::PRIO_PROCESS, tid as libc::id_t, priority) })?;
+            Ok(())
+        } else if #[cfg(any(target_os = "freebsd", target_vendor = "apple"))] {
+            let mut policy = 0;
+            let mut param: libc::sched_param = unsafe { mem::zeroed() };
+            unsafe {
+                crate::sys::cvt_nz(libc::pthread_getschedparam(libc::pthread_self(), &mut policy, &mut param))?;
+                param.sched_priority = priority;
+                crate::sys::cvt_nz(libc::pthread_setschedparam(libc::pthread_self(), policy, &param))
+            }
+        } else {
+            let _ = priority;
+            Err(io::Error::UNSUPPORTED_PLATFORM)
+        }
+    }
+}
+
+pub fn priority() -> io::Result<i32> {
+    cfg_if::cfg_if! {
+        if #[cfg(any(target_os = "android", target_os = "linux"))] {
+            let tid = unsafe { libc::syscall(libc::SYS_gettid) };
+            // -1 is a valid nice value, so errors can only be told apart through errno.
+            os::set_errno(0);
+            let priority = unsafe { libc::getpriority(libc::PRIO_PROCESS, tid as libc::id_t) };
+            if priority == -1 && os::errno() != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(priority)
+        } else if #[cfg(any(target_os = "freebsd", target_vendor = "apple"))] {
+            let mut policy = 0;
+            let mut param: libc::sched_param = unsafe { mem::zeroed() };
+            crate::sys::cvt_nz(unsafe {
+                libc::pthread_getschedparam(libc::pthread_self(), &mut policy, &mut param)
+            })?;
+            Ok(param.sched_priority)
+        } else {
+            Err(io::Error::UNSUPPORTED_PLATFORM)
+        }
+    }
+}
+
 #[cfg(any(target_os = "android", target_os = "linux"))]
 mod cgroups {
     //! Currently not covered
@@ -198,6 +198,26 @@ pub fn datasync(&self) -> io::Result<()> {
         self.0
     }
 
+    pub fn lock(&self) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn try_lock(&self) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        self.0
+    }
+
     pub fn truncate(&self, _size: u64) -> io::Result<()> {
         self.0
     }
@@ -267,6 +287,57 @@ fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+pub struct DirHandle(!);
+
+impl DirHandle {
+    pub fn open(_path: &Path, _follow_symlinks: bool) -> io::Result<DirHandle> {
+        unsupported()
+    }
+
+    pub fn open_dir(&self, _path: &Path, _follow_symlinks: bool) -> io::Result<DirHandle> {
+        self.0
+    }
+
+    pub fn open_file(
+        &self,
+        _path: &Path,
+        _opts: &OpenOptions,
+        _follow_symlinks: bool,
+    ) -> io::Result<File> {
+        self.0
+    }
+
+    pub fn create_dir(&self, _path: &Path) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn read_dir(&self) -> io::Result<ReadDir> {
+        self.0
+    }
+
+    pub fn remove_file(&self, _path: &Path) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn remove_dir(&self, _path: &Path) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn rename(&self, _from: &Path, _to_dir: &DirHandle, _to: &Path) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn metadata(&self, _path: &Path, _follow_symlinks: bool) -> io::Result<FileAttr> {
+        self.0
+    }
+}
+
+impl fmt::Debug for DirHandle {
+    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0
+    }
+}
+
 pub fn readdir(_p: &Path) -> io::Result<ReadDir> {
     unsupported()
 }
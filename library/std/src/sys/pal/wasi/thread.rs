@@ -189,3 +189,19 @@ pub fn join(self) {
 pub fn available_parallelism() -> io::Result<NonZero<usize>> {
     unsupported()
 }
+
+pub fn set_affinity(_cpus: &[usize]) -> io::Result<()> {
+    unsupported()
+}
+
+pub fn affinity() -> io::Result<Vec<usize>> {
+    unsupported()
+}
+
+pub fn set_priority(_priority: i32) -> io::Result<()> {
+    unsupported()
+}
+
+pub fn priority() -> io::Result<i32> {
+    unsupported()
+}
@@ -48,6 +48,22 @@ pub fn available_parallelism() -> io::Result<NonZero<usize>> {
     unsupported()
 }
 
+pub fn set_affinity(_cpus: &[usize]) -> io::Result<()> {
+    unsupported()
+}
+
+pub fn affinity() -> io::Result<Vec<usize>> {
+    unsupported()
+}
+
+pub fn set_priority(_priority: i32) -> io::Result<()> {
+    unsupported()
+}
+
+pub fn priority() -> io::Result<i32> {
+    unsupported()
+}
+
 pub mod guard {
     pub type Guard = !;
     pub unsafe fn current() -> Option<Guard> {
@@ -42,11 +42,18 @@ pub struct FileType {
 }
 
 pub struct ReadDir {
-    handle: FindNextFileHandle,
+    handle: ReadDirHandle,
     root: Arc<PathBuf>,
     first: Option<c::WIN32_FIND_DATAW>,
 }
 
+enum ReadDirHandle {
+    /// A search started by `FindFirstFileW`.
+    Find(FindNextFileHandle),
+    /// A directory handle listed by `GetFileInformationByHandleEx`, see `DirHandle::read_dir`.
+    Dir(DirHandleEntries),
+}
+
 struct FindNextFileHandle(c::HANDLE);
 
 unsafe impl Send for FindNextFileHandle {}
@@ -111,7 +118,20 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 impl Iterator for ReadDir {
     type Item = io::Result<DirEntry>;
     fn next(&mut self) -> Option<io::Result<DirEntry>> {
-        if self.handle.0 == c::INVALID_HANDLE_VALUE {
+        let handle = match &mut self.handle {
+            ReadDirHandle::Find(handle) => handle,
+            ReadDirHandle::Dir(entries) => loop {
+                match entries.next()? {
+                    Ok(wfd) => {
+                        if let Some(e) = DirEntry::new(&self.root, &wfd) {
+                            return Some(Ok(e));
+                        }
+                    }
+                    Err(e) => return Some(Err(e)),
+                }
+            },
+        };
+        if handle.0 == c::INVALID_HANDLE_VALUE {
             // This iterator was initialized with an `INVALID_HANDLE_VALUE` as its handle.
             // Simply return `None` because this is only the case when `FindFirstFileW` in
             // the construction of this iterator returns `ERROR_FILE_NOT_FOUND` which means
@@ -126,7 +146,7 @@ fn next(&mut self) -> Option<io::Result<DirEntry>> {
         unsafe {
             let mut wfd = mem::zeroed();
             loop {
-                if c::FindNextFileW(self.handle.0, &mut wfd) == 0 {
+                if c::FindNextFileW(handle.0, &mut wfd) == 0 {
                     match api::get_last_error() {
                         WinError::NO_MORE_FILES => return None,
                         WinError { code } => {
@@ -345,6 +365,72 @@ pub fn datasync(&self) -> io::Result<()> {
         self.fsync()
     }
 
+    fn acquire_lock(&self, flags: c::LOCK_FILE_FLAGS) -> io::Result<()> {
+        // If the file was opened for overlapped I/O, `LockFileEx` may return before the lock is
+        // taken and the `OVERLAPPED` has to outlive the request. The event is signaled once the
+        // request completes, which also happens when it fails because of
+        // `LOCKFILE_FAIL_IMMEDIATELY`.
+        let event = Handle::new_event(true, false)?;
+        unsafe {
+            let mut overlapped: c::OVERLAPPED = mem::zeroed();
+            overlapped.hEvent = event.as_raw_handle();
+            // Lock the whole file, even the bytes that are past its end.
+            let result = cvt(c::LockFileEx(
+                self.handle.as_raw_handle(),
+                flags,
+                0,
+                u32::MAX,
+                u32::MAX,
+                &mut overlapped,
+            ));
+            match result {
+                Err(err) if err.raw_os_error() == Some(c::ERROR_IO_PENDING as i32) => {
+                    let mut bytes = 0;
+                    cvt(c::GetOverlappedResult(
+                        self.handle.as_raw_handle(),
+                        &overlapped,
+                        &mut bytes,
+                        c::TRUE,
+                    ))?;
+                }
+                result => {
+                    result?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn try_acquire_lock(&self, flags: c::LOCK_FILE_FLAGS) -> io::Result<()> {
+        match self.acquire_lock(flags | c::LOCKFILE_FAIL_IMMEDIATELY) {
+            Err(err) if err.raw_os_error() == Some(c::ERROR_LOCK_VIOLATION as i32) => {
+                Err(io::const_io_error!(io::ErrorKind::WouldBlock, "the file is already locked"))
+            }
+            result => result,
+        }
+    }
+
+    pub fn lock(&self) -> io::Result<()> {
+        self.acquire_lock(c::LOCKFILE_EXCLUSIVE_LOCK)
+    }
+
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.acquire_lock(0)
+    }
+
+    pub fn try_lock(&self) -> io::Result<()> {
+        self.try_acquire_lock(c::LOCKFILE_EXCLUSIVE_LOCK)
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<()> {
+        self.try_acquire_lock(0)
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        cvt(unsafe { c::UnlockFile(self.handle.as_raw_handle(), 0, 0, u32::MAX, u32::MAX) })?;
+        Ok(())
+    }
+
     pub fn truncate(&self, size: u64) -> io::Result<()> {
         let info = c::FILE_END_OF_FILE_INFO { EndOfFile: size as i64 };
         api::set_file_information_by_handle(self.handle.as_raw_handle(), &info).io_result()
@@ -840,14 +926,8 @@ fn open_link_no_reparse(parent: &File, name: &[u16], access: u32) -> io::Result<
             crate::ptr::null_mut(),
             0,
         );
-        // Convert an NTSTATUS to the more familiar Win32 error codes (aka "DosError")
         if c::nt_success(status) {
             Ok(File::from_raw_handle(handle))
-        } else if status == c::STATUS_DELETE_PENDING {
-            // We make a special exception for `STATUS_DELETE_PENDING` because
-            // otherwise this will be mapped to `ERROR_ACCESS_DENIED` which is
-            // very unhelpful.
-            Err(io::Error::from_raw_os_error(c::ERROR_DELETE_PENDING as i32))
         } else if status == c::STATUS_INVALID_PARAMETER
             && ATTRIBUTES.load(Ordering::Relaxed) == c::OBJ_DONT_REPARSE
         {
@@ -855,11 +935,23 @@ fn open_link_no_reparse(parent: &File, name: &[u16], access: u32) -> io::Result<
             ATTRIBUTES.store(0, Ordering::Relaxed);
             open_link_no_reparse(parent, name, access)
         } else {
-            Err(io::Error::from_raw_os_error(c::RtlNtStatusToDosError(status) as _))
+            Err(nt_status_error(status))
         }
     }
 }
 
+/// Convert an NTSTATUS to the more familiar Win32 error codes (aka "DosError")
+fn nt_status_error(status: c::NTSTATUS) -> io::Error {
+    if status == c::STATUS_DELETE_PENDING {
+        // We make a special exception for `STATUS_DELETE_PENDING` because
+        // otherwise this will be mapped to `ERROR_ACCESS_DENIED` which is
+        // very unhelpful.
+        io::Error::from_raw_os_error(c::ERROR_DELETE_PENDING as i32)
+    } else {
+        io::Error::from_raw_os_error(unsafe { c::RtlNtStatusToDosError(status) } as _)
+    }
+}
+
 impl AsInner<Handle> for File {
     #[inline]
     fn as_inner(&self) -> &Handle {
@@ -1089,7 +1181,7 @@ pub fn readdir(p: &Path) -> io::Result<ReadDir> {
 
         if find_handle != c::INVALID_HANDLE_VALUE {
             Ok(ReadDir {
-                handle: FindNextFileHandle(find_handle),
+                handle: ReadDirHandle::Find(FindNextFileHandle(find_handle)),
                 root: Arc::new(root),
                 first: Some(wfd),
             })
@@ -1108,7 +1200,7 @@ pub fn readdir(p: &Path) -> io::Result<ReadDir> {
             let last_error = api::get_last_error();
             if last_error == WinError::FILE_NOT_FOUND {
                 return Ok(ReadDir {
-                    handle: FindNextFileHandle(find_handle),
+                    handle: ReadDirHandle::Find(FindNextFileHandle(find_handle)),
                     root: Arc::new(root),
                     first: None,
                 });
@@ -1575,3 +1667,305 @@ pub fn exists(path: &Path) -> io::Result<bool> {
         Ok(_) => Ok(true),
     }
 }
+
+/// A handle to a directory, relative to which files can be opened, created, removed and renamed.
+///
+/// Opening a path relative to a handle is only supported by the NT API, see
+/// `open_link_no_reparse`.
+pub struct DirHandle {
+    dir: File,
+}
+
+/// The access rights of directory handles, which allow opening and listing their children.
+const DIR_ACCESS: u32 = c::FILE_LIST_DIRECTORY | c::FILE_TRAVERSE | c::FILE_READ_ATTRIBUTES;
+
+impl DirHandle {
+    pub fn open(path: &Path, follow_symlinks: bool) -> io::Result<DirHandle> {
+        let mut opts = OpenOptions::new();
+        opts.access_mode(DIR_ACCESS);
+        // Backup semantics enables opening directories.
+        let mut flags = c::FILE_FLAG_BACKUP_SEMANTICS;
+        if !follow_symlinks {
+            flags |= c::FILE_FLAG_OPEN_REPARSE_POINT;
+        }
+        opts.custom_flags(flags);
+        DirHandle::new(File::open(path, &opts)?)
+    }
+
+    fn new(dir: File) -> io::Result<DirHandle> {
+        // Backup semantics also open files, and the symlinks that are not followed are opened
+        // rather than refused.
+        let file_type = dir.file_attr()?.file_type();
+        if file_type.is_symlink() {
+            return Err(io::Error::from_raw_os_error(c::ERROR_STOPPED_ON_SYMLINK as _));
+        } else if !file_type.is_dir() {
+            return Err(io::Error::from_raw_os_error(c::ERROR_DIRECTORY as _));
+        }
+        Ok(DirHandle { dir })
+    }
+
+    pub fn open_dir(&self, path: &Path, follow_symlinks: bool) -> io::Result<DirHandle> {
+        let options = c::FILE_DIRECTORY_FILE | reparse_option(follow_symlinks);
+        let dir = self.open_at(path, DIR_ACCESS, c::FILE_OPEN, options, 0)?;
+        DirHandle::new(dir)
+    }
+
+    pub fn open_file(
+        &self,
+        path: &Path,
+        opts: &OpenOptions,
+        follow_symlinks: bool,
+    ) -> io::Result<File> {
+        let disposition = match opts.get_creation_mode()? {
+            c::CREATE_NEW => c::FILE_CREATE,
+            c::OPEN_ALWAYS if opts.truncate => c::FILE_OVERWRITE_IF,
+            c::OPEN_ALWAYS => c::FILE_OPEN_IF,
+            c::TRUNCATE_EXISTING => c::FILE_OVERWRITE,
+            _ => c::FILE_OPEN,
+        };
+        let options = c::FILE_NON_DIRECTORY_FILE | reparse_option(follow_symlinks);
+        let access = opts.get_access_mode()? | c::FILE_READ_ATTRIBUTES;
+        let file = self.open_at(path, access, disposition, options, opts.attributes)?;
+        if !follow_symlinks && file.file_attr()?.file_type().is_symlink() {
+            return Err(io::Error::from_raw_os_error(c::ERROR_STOPPED_ON_SYMLINK as _));
+        }
+        Ok(file)
+    }
+
+    pub fn create_dir(&self, path: &Path) -> io::Result<()> {
+        self.open_at(
+            path,
+            c::FILE_READ_ATTRIBUTES,
+            c::FILE_CREATE,
+            c::FILE_DIRECTORY_FILE,
+            c::FILE_ATTRIBUTE_NORMAL,
+        )
+        .map(drop)
+    }
+
+    pub fn read_dir(&self) -> io::Result<ReadDir> {
+        // The position of the listing is kept by the handle, so a new handle to the directory is
+        // opened from an empty name relative to this one.
+        let dir =
+            self.open_name(&[], c::FILE_LIST_DIRECTORY, c::FILE_OPEN, c::FILE_DIRECTORY_FILE, 0)?;
+        let entries = DirHandleEntries { dir, buffer: DirBuff::new(), cursor: None };
+        Ok(ReadDir {
+            handle: ReadDirHandle::Dir(entries),
+            root: Arc::new(get_path(&self.dir)?),
+            first: None,
+        })
+    }
+
+    pub fn remove_file(&self, path: &Path) -> io::Result<()> {
+        self.remove(path, c::FILE_NON_DIRECTORY_FILE)
+    }
+
+    pub fn remove_dir(&self, path: &Path) -> io::Result<()> {
+        self.remove(path, c::FILE_DIRECTORY_FILE)
+    }
+
+    fn remove(&self, path: &Path, options: u32) -> io::Result<()> {
+        let options = options | c::FILE_OPEN_REPARSE_POINT;
+        let file = self.open_at(path, c::DELETE, c::FILE_OPEN, options, 0)?;
+        match file.posix_delete() {
+            // If POSIX delete is not supported for this filesystem then fallback to win32 delete.
+            Err(e)
+                if matches!(
+                    e.raw_os_error().map(|code| code as u32),
+                    Some(
+                        c::ERROR_NOT_SUPPORTED
+                            | c::ERROR_INVALID_FUNCTION
+                            | c::ERROR_INVALID_PARAMETER
+                    )
+                ) =>
+            {
+                file.win32_delete()
+            }
+            result => result,
+        }
+    }
+
+    pub fn rename(&self, from: &Path, to_dir: &DirHandle, to: &Path) -> io::Result<()> {
+        let file = self.open_at(from, c::DELETE, c::FILE_OPEN, c::FILE_OPEN_REPARSE_POINT, 0)?;
+        let to = relative_nt_path(to)?;
+
+        // `FILE_RENAME_INFO` ends with the new name, whose length is variable.
+        let name_size = to.len() * mem::size_of::<u16>();
+        let size = mem::offset_of!(c::FILE_RENAME_INFO, FileName) + name_size;
+        let (Ok(name_size), Ok(info_size)) = (u32::try_from(name_size), u32::try_from(size)) else {
+            return Err(io::const_io_error!(io::ErrorKind::InvalidFilename, "path too long"));
+        };
+        // A buffer of `u64`s is aligned enough for the structure.
+        let mut buffer = vec![0u64; size.div_ceil(mem::size_of::<u64>())];
+        let info = buffer.as_mut_ptr().cast::<c::FILE_RENAME_INFO>();
+        unsafe {
+            (*info).Anonymous.ReplaceIfExists = c::TRUE as _;
+            (*info).RootDirectory = to_dir.dir.handle.as_raw_handle();
+            (*info).FileNameLength = name_size;
+            ptr::addr_of_mut!((*info).FileName)
+                .cast::<u16>()
+                .copy_from_nonoverlapping(to.as_ptr(), to.len());
+            cvt(c::SetFileInformationByHandle(
+                file.handle.as_raw_handle(),
+                c::FileRenameInfo,
+                info.cast(),
+                info_size,
+            ))
+            .map(drop)
+        }
+    }
+
+    pub fn metadata(&self, path: &Path, follow_symlinks: bool) -> io::Result<FileAttr> {
+        let options = reparse_option(follow_symlinks);
+        let file = self.open_at(path, c::FILE_READ_ATTRIBUTES, c::FILE_OPEN, options, 0)?;
+        file.file_attr()
+    }
+
+    /// Opens or creates `path` relative to the directory, for synchronous I/O.
+    fn open_at(
+        &self,
+        path: &Path,
+        access: u32,
+        disposition: u32,
+        options: u32,
+        attributes: u32,
+    ) -> io::Result<File> {
+        self.open_name(&relative_nt_path(path)?, access, disposition, options, attributes)
+    }
+
+    fn open_name(
+        &self,
+        name: &[u16],
+        access: u32,
+        disposition: u32,
+        options: u32,
+        attributes: u32,
+    ) -> io::Result<File> {
+        unsafe {
+            let mut handle = ptr::null_mut();
+            let mut io_status = c::IO_STATUS_BLOCK::PENDING;
+            let mut name_str = c::UNICODE_STRING::from_ref(name);
+            let object = c::OBJECT_ATTRIBUTES {
+                ObjectName: &mut name_str,
+                RootDirectory: self.dir.handle.as_raw_handle(),
+                ..c::OBJECT_ATTRIBUTES::default()
+            };
+            let status = c::NtCreateFile(
+                &mut handle,
+                access | c::SYNCHRONIZE,
+                &object,
+                &mut io_status,
+                ptr::null_mut(),
+                attributes,
+                c::FILE_SHARE_DELETE | c::FILE_SHARE_READ | c::FILE_SHARE_WRITE,
+                disposition,
+                options | c::FILE_SYNCHRONOUS_IO_NONALERT,
+                ptr::null_mut(),
+                0,
+            );
+            if c::nt_success(status) {
+                Ok(File::from_raw_handle(handle))
+            } else {
+                Err(nt_status_error(status))
+            }
+        }
+    }
+}
+
+/// The entries of a directory handle, read with `File::fill_dir_buff`.
+struct DirHandleEntries {
+    dir: File,
+    buffer: DirBuff,
+    /// The offset of the next entry in `buffer`, or `None` if it must be refilled.
+    cursor: Option<usize>,
+}
+
+impl DirHandleEntries {
+    fn next(&mut self) -> Option<io::Result<c::WIN32_FIND_DATAW>> {
+        let cursor = match self.cursor {
+            Some(cursor) => cursor,
+            None => match self.dir.fill_dir_buff(&mut self.buffer, false) {
+                Ok(true) => 0,
+                Ok(false) => return None,
+                Err(e) => return Some(Err(e)),
+            },
+        };
+
+        // SAFETY: the buffer contains a `FILE_ID_BOTH_DIR_INFO` at `cursor`, followed by its
+        // name, see `DirBuffIter`.
+        let (wfd, next_entry) = unsafe {
+            let info = self.buffer.as_ref()[cursor..].as_ptr().cast::<c::FILE_ID_BOTH_DIR_INFO>();
+            let next_entry = ptr::addr_of!((*info).NextEntryOffset).read_unaligned() as usize;
+            let length = ptr::addr_of!((*info).FileNameLength).read_unaligned() as usize;
+            let name = from_maybe_unaligned(
+                ptr::addr_of!((*info).FileName).cast::<u16>(),
+                length / mem::size_of::<u16>(),
+            );
+            let mut wfd: c::WIN32_FIND_DATAW = mem::zeroed();
+            // The last character of the name is kept for its terminating null.
+            if name.len() >= wfd.cFileName.len() {
+                return Some(Err(io::const_io_error!(
+                    io::ErrorKind::InvalidFilename,
+                    "file name too long",
+                )));
+            }
+            wfd.cFileName[..name.len()].copy_from_slice(&name);
+            let filetime = |time: i64| c::FILETIME {
+                dwLowDateTime: time as u32,
+                dwHighDateTime: (time >> 32) as u32,
+            };
+            wfd.dwFileAttributes = ptr::addr_of!((*info).FileAttributes).read_unaligned();
+            wfd.ftCreationTime = filetime(ptr::addr_of!((*info).CreationTime).read_unaligned());
+            wfd.ftLastAccessTime = filetime(ptr::addr_of!((*info).LastAccessTime).read_unaligned());
+            wfd.ftLastWriteTime = filetime(ptr::addr_of!((*info).LastWriteTime).read_unaligned());
+            let size = ptr::addr_of!((*info).EndOfFile).read_unaligned() as u64;
+            wfd.nFileSizeHigh = (size >> 32) as u32;
+            wfd.nFileSizeLow = size as u32;
+            // The extended attribute size holds the reparse tag of reparse points, like the
+            // reserved field of `WIN32_FIND_DATAW`.
+            wfd.dwReserved0 = ptr::addr_of!((*info).EaSize).read_unaligned();
+            (wfd, next_entry)
+        };
+
+        self.cursor = if next_entry == 0 { None } else { Some(cursor + next_entry) };
+        Some(Ok(wfd))
+    }
+}
+
+impl fmt::Debug for DirHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("Dir");
+        b.field("handle", &self.dir.handle.as_raw_handle());
+        if let Ok(path) = get_path(&self.dir) {
+            b.field("path", &path);
+        }
+        b.finish()
+    }
+}
+
+/// The `NtCreateFile` option to open a symlink itself rather than its target.
+fn reparse_option(follow_symlinks: bool) -> u32 {
+    if follow_symlinks { 0 } else { c::FILE_OPEN_REPARSE_POINT }
+}
+
+/// Converts a path to the name of a file relative to a directory handle, which cannot contain
+/// prefixes, root directories, `.` or `..`, and whose separators must be backslashes.
+fn relative_nt_path(path: &Path) -> io::Result<Vec<u16>> {
+    let mut name = Vec::new();
+    for component in path.components() {
+        let crate::path::Component::Normal(component) = component else {
+            return Err(io::const_io_error!(
+                io::ErrorKind::InvalidInput,
+                "paths relative to a directory can only contain normal components on Windows",
+            ));
+        };
+        if !name.is_empty() {
+            name.push(b'\\' as u16);
+        }
+        name.extend(component.encode_wide());
+    }
+    if name.is_empty() {
+        return Err(io::const_io_error!(io::ErrorKind::InvalidInput, "empty path"));
+    }
+    Ok(name)
+}
@@ -24,6 +24,7 @@
 use crate::sys::{cvt, path, stdio};
 use crate::sys_common::process::{CommandEnv, CommandEnvs};
 use crate::sys_common::IntoInner;
+use crate::time::Duration;
 use crate::{cmp, env, fmt, mem, ptr};
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -696,6 +697,15 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         }
     }
 
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        let timeout = super::dur2timeout(timeout);
+        match unsafe { c::WaitForSingleObject(self.handle.as_raw_handle(), timeout) } {
+            c::WAIT_OBJECT_0 => self.try_wait(),
+            c::WAIT_TIMEOUT => Ok(None),
+            _ => Err(io::Error::last_os_error()),
+        }
+    }
+
     pub fn handle(&self) -> &Handle {
         &self.handle
     }
@@ -6,7 +6,7 @@
 use crate::num::NonZero;
 use crate::os::windows::io::{AsRawHandle, HandleOrNull};
 use crate::sys::handle::Handle;
-use crate::sys::{c, stack_overflow};
+use crate::sys::{c, cvt, stack_overflow};
 use crate::sys_common::FromInner;
 use crate::time::Duration;
 use crate::{io, ptr};
@@ -125,3 +125,46 @@ pub fn available_parallelism() -> io::Result<NonZero<usize>> {
         cpus => Ok(unsafe { NonZero::new_unchecked(cpus) }),
     }
 }
+
+// Affinity masks only cover the processors of the processor group of the thread, which is kept.
+pub fn set_affinity(cpus: &[usize]) -> io::Result<()> {
+    let mut mask = 0usize;
+    for &cpu in cpus {
+        if cpu >= usize::BITS as usize {
+            return Err(io::const_io_error!(
+                io::ErrorKind::InvalidInput,
+                "CPU index out of range of the affinity mask",
+            ));
+        }
+        mask |= 1 << cpu;
+    }
+    let mut affinity = group_affinity()?;
+    affinity.Mask = mask;
+    // An empty mask or CPUs the process may not use fail with `ERROR_INVALID_PARAMETER`.
+    cvt(unsafe { c::SetThreadGroupAffinity(c::GetCurrentThread(), &affinity, ptr::null_mut()) })?;
+    Ok(())
+}
+
+pub fn affinity() -> io::Result<Vec<usize>> {
+    let mask = group_affinity()?.Mask;
+    Ok((0..usize::BITS as usize).filter(|&cpu| mask & (1 << cpu) != 0).collect())
+}
+
+fn group_affinity() -> io::Result<c::GROUP_AFFINITY> {
+    let mut affinity = c::GROUP_AFFINITY { Mask: 0, Group: 0, Reserved: [0; 3] };
+    cvt(unsafe { c::GetThreadGroupAffinity(c::GetCurrentThread(), &mut affinity) })?;
+    Ok(affinity)
+}
+
+pub fn set_priority(priority: i32) -> io::Result<()> {
+    cvt(unsafe { c::SetThreadPriority(c::GetCurrentThread(), priority) })?;
+    Ok(())
+}
+
+pub fn priority() -> io::Result<i32> {
+    let priority = unsafe { c::GetThreadPriority(c::GetCurrentThread()) };
+    if priority == c::THREAD_PRIORITY_ERROR_RETURN as i32 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(priority)
+}
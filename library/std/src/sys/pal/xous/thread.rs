@@ -137,3 +137,19 @@ pub fn available_parallelism() -> io::Result<NonZero<usize>> {
     // We're unicore right now.
     Ok(unsafe { NonZero::new_unchecked(1) })
 }
+
+pub fn set_affinity(_cpus: &[usize]) -> io::Result<()> {
+    super::unsupported()
+}
+
+pub fn affinity() -> io::Result<Vec<usize>> {
+    super::unsupported()
+}
+
+pub fn set_priority(_priority: i32) -> io::Result<()> {
+    super::unsupported()
+}
+
+pub fn priority() -> io::Result<i32> {
+    super::unsupported()
+}
@@ -251,6 +251,10 @@ pub struct Builder {
     name: Option<String>,
     // The size of the stack for the spawned thread in bytes
     stack_size: Option<usize>,
+    // The CPUs the spawned thread may run on
+    affinity: Option<Vec<usize>>,
+    // The platform-specific scheduling priority of the spawned thread
+    priority: Option<i32>,
 }
 
 impl Builder {
@@ -274,7 +278,7 @@ impl Builder {
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn new() -> Builder {
-        Builder { name: None, stack_size: None }
+        Builder { name: None, stack_size: None, affinity: None, priority: None }
     }
 
     /// Names the thread-to-be. Currently the name is used for identification
@@ -330,6 +334,83 @@ pub fn stack_size(mut self, size: usize) -> Builder {
         self
     }
 
+    /// Restricts the new thread to run on the given CPUs, identified by their
+    /// zero-based index.
+    ///
+    /// The affinity is set by the new thread itself before it runs its closure,
+    /// and [`spawn`] returns an error if that fails, for example because none of
+    /// the CPUs are available to the process. [`spawn`] thus blocks until the new
+    /// thread has set its affinity. It can be queried from within the thread
+    /// with [`thread::affinity`].
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// On Linux and Android, this uses `sched_setaffinity`. On Windows, this
+    /// uses `SetThreadGroupAffinity`, and the indices are those of the
+    /// processors of the processor group of the thread, which must be lower
+    /// than the number of bits of `usize`. Spawning a thread with an affinity
+    /// fails on other platforms.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(thread_affinity)]
+    /// use std::thread;
+    ///
+    /// let handler = thread::Builder::new().affinity(&[0]).spawn(|| {
+    ///     assert_eq!(thread::affinity().unwrap(), [0]);
+    /// }).unwrap();
+    ///
+    /// handler.join().unwrap();
+    /// ```
+    ///
+    /// [`spawn`]: Builder::spawn
+    /// [`thread::affinity`]: affinity
+    #[unstable(feature = "thread_affinity", issue = "none")]
+    pub fn affinity(mut self, cpus: &[usize]) -> Builder {
+        self.affinity = Some(cpus.to_vec());
+        self
+    }
+
+    /// Sets the scheduling priority of the new thread.
+    ///
+    /// The meaning of the value is platform-specific. Like the [affinity], the
+    /// priority is set by the new thread itself before it runs its closure, and
+    /// [`spawn`] blocks until then to return an error if that fails. It can be
+    /// queried from within the thread with [`thread::priority`].
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// On Linux and Android, this is the nice value of the thread, as set by
+    /// `setpriority`: lowering it usually requires privileges. On FreeBSD and
+    /// Apple platforms, this is the `sched_priority` of the thread within its
+    /// current scheduling policy, as set by `pthread_setschedparam`. On
+    /// Windows, this is one of the `THREAD_PRIORITY_*` values accepted by
+    /// `SetThreadPriority`, where higher values mean a higher priority.
+    /// Spawning a thread with a priority fails on other platforms.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(thread_affinity)]
+    /// use std::thread;
+    ///
+    /// let handler = thread::Builder::new().priority(10).spawn(|| {
+    ///     assert_eq!(thread::priority().unwrap(), 10);
+    /// }).unwrap();
+    ///
+    /// handler.join().unwrap();
+    /// ```
+    ///
+    /// [affinity]: Builder::affinity
+    /// [`spawn`]: Builder::spawn
+    /// [`thread::priority`]: priority
+    #[unstable(feature = "thread_affinity", issue = "none")]
+    pub fn priority(mut self, priority: i32) -> Builder {
+        self.priority = Some(priority);
+        self
+    }
+
     /// Spawns a new thread by taking ownership of the `Builder`, and returns an
     /// [`io::Result`] to its [`JoinHandle`].
     ///
@@ -340,13 +421,19 @@ pub fn stack_size(mut self, size: usize) -> Builder {
     ///
     /// For a more complete documentation see [`thread::spawn`][`spawn`].
     ///
+    /// If an [affinity] or a [priority] is set, this method waits for the new
+    /// thread to apply them before returning. Otherwise, it returns as soon as
+    /// the thread is created.
+    ///
     /// # Errors
     ///
     /// Unlike the [`spawn`] free function, this method yields an
     /// [`io::Result`] to capture any failure to create the thread at
-    /// the OS level.
+    /// the OS level, or to apply its [affinity] or [priority].
     ///
     /// [`io::Result`]: crate::io::Result
+    /// [affinity]: Builder::affinity
+    /// [priority]: Builder::priority
     ///
     /// # Panics
     ///
@@ -452,7 +539,7 @@ unsafe fn spawn_unchecked_<'scope, F, T>(
         F: Send,
         T: Send,
     {
-        let Builder { name, stack_size } = self;
+        let Builder { name, stack_size, affinity, priority } = self;
 
         let stack_size = stack_size.unwrap_or_else(|| {
             static MIN: AtomicUsize = AtomicUsize::new(0);
@@ -508,6 +595,15 @@ fn drop(&mut self) {
             }
         }
 
+        // The new thread applies its scheduling settings itself, and reports whether that
+        // succeeded before this function returns.
+        let (scheduling_tx, scheduling_rx) = if affinity.is_some() || priority.is_some() {
+            let (tx, rx) = crate::sync::mpsc::sync_channel(1);
+            (Some(tx), Some(rx))
+        } else {
+            (None, None)
+        };
+
         let f = MaybeDangling::new(f);
         let main = move || {
             if let Some(name) = their_thread.cname() {
@@ -518,6 +614,17 @@ fn drop(&mut self) {
 
             let f = f.into_inner();
             set_current(their_thread);
+            if let Some(scheduling_tx) = scheduling_tx {
+                let result = set_scheduling(affinity.as_deref(), priority);
+                let failed = result.is_err();
+                let _ = scheduling_tx.send(result);
+                if failed {
+                    // The closure never runs, but it is dropped before `'scope` can end.
+                    drop(f);
+                    drop(their_packet);
+                    return;
+                }
+            }
             let try_result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
                 crate::sys::backtrace::__rust_begin_short_backtrace(f)
             }));
@@ -544,24 +651,30 @@ fn drop(&mut self) {
         let main =
             unsafe { Box::from_raw(Box::into_raw(main) as *mut (dyn FnOnce() + Send + 'static)) };
 
-        Ok(JoinInner {
-            // SAFETY:
-            //
-            // `imp::Thread::new` takes a closure with a `'static` lifetime, since it's passed
-            // through FFI or otherwise used with low-level threading primitives that have no
-            // notion of or way to enforce lifetimes.
-            //
-            // As mentioned in the `Safety` section of this function's documentation, the caller of
-            // this function needs to guarantee that the passed-in lifetime is sufficiently long
-            // for the lifetime of the thread.
-            //
-            // Similarly, the `sys` implementation must guarantee that no references to the closure
-            // exist after the thread has terminated, which is signaled by `Thread::join`
-            // returning.
-            native: unsafe { imp::Thread::new(stack_size, main)? },
-            thread: my_thread,
-            packet: my_packet,
-        })
+        // SAFETY:
+        //
+        // `imp::Thread::new` takes a closure with a `'static` lifetime, since it's passed
+        // through FFI or otherwise used with low-level threading primitives that have no
+        // notion of or way to enforce lifetimes.
+        //
+        // As mentioned in the `Safety` section of this function's documentation, the caller of
+        // this function needs to guarantee that the passed-in lifetime is sufficiently long
+        // for the lifetime of the thread.
+        //
+        // Similarly, the `sys` implementation must guarantee that no references to the closure
+        // exist after the thread has terminated, which is signaled by `Thread::join`
+        // returning.
+        let native = unsafe { imp::Thread::new(stack_size, main)? };
+
+        if let Some(scheduling_rx) = scheduling_rx {
+            if let Ok(Err(e)) = scheduling_rx.recv() {
+                // The thread exits right away without running the closure.
+                native.join();
+                return Err(e);
+            }
+        }
+
+        Ok(JoinInner { native, thread: my_thread, packet: my_packet })
     }
 }
 
@@ -1893,3 +2006,62 @@ fn _assert_both<T: Send + Sync>() {}
 pub fn available_parallelism() -> io::Result<NonZero<usize>> {
     imp::available_parallelism()
 }
+
+/// Applies the scheduling settings of a [`Builder`] to the current thread.
+fn set_scheduling(affinity: Option<&[usize]>, priority: Option<i32>) -> io::Result<()> {
+    if let Some(cpus) = affinity {
+        imp::set_affinity(cpus)?;
+    }
+    if let Some(priority) = priority {
+        imp::set_priority(priority)?;
+    }
+    Ok(())
+}
+
+/// Returns the CPUs the current thread may run on, identified by their
+/// zero-based index and in increasing order.
+///
+/// This is the affinity set with [`Builder::affinity`], or the one the thread
+/// inherited from the thread that spawned it.
+///
+/// # Errors
+///
+/// This function returns an error on platforms where [`Builder::affinity`] is
+/// not supported, or if the affinity of the thread cannot be queried.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(thread_affinity)]
+/// use std::thread;
+///
+/// let cpus = thread::affinity().unwrap();
+/// assert!(!cpus.is_empty());
+/// ```
+#[unstable(feature = "thread_affinity", issue = "none")]
+pub fn affinity() -> io::Result<Vec<usize>> {
+    imp::affinity()
+}
+
+/// Returns the platform-specific scheduling priority of the current thread.
+///
+/// See [`Builder::priority`] for the meaning of the value on each platform.
+///
+/// # Errors
+///
+/// This function returns an error on platforms where [`Builder::priority`] is
+/// not supported, or if the priority of the thread cannot be queried.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(thread_affinity)]
+/// use std::thread;
+///
+/// let priority = thread::priority().unwrap();
+/// println!("running with priority {priority}");
+/// ```
+#[unstable(feature = "thread_affinity", issue = "none")]
+pub fn priority() -> io::Result<i32> {
+    imp::priority()
+}
@@ -6,7 +6,7 @@
 use crate::sync::{Arc, Barrier};
 use crate::thread::{self, Scope, ThreadId};
 use crate::time::{Duration, Instant};
-use crate::{mem, result};
+use crate::{io, mem, result};
 
 // !!! These tests are dangerous. If something is buggy, they will hang, !!!
 // !!! instead of exiting cleanly. This might wedge the buildbots.       !!!
@@ -410,3 +410,39 @@ fn test_minimal_thread_stack() {
     assert_eq!(before, 0);
     assert_eq!(COUNT.load(Ordering::Relaxed), 1);
 }
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux", windows))]
+fn test_affinity() {
+    let cpus = thread::affinity().unwrap();
+    assert!(!cpus.is_empty());
+
+    let cpu = cpus[cpus.len() - 1];
+    let child = thread::Builder::new().affinity(&[cpu]).spawn(thread::affinity).unwrap();
+    assert_eq!(child.join().unwrap().unwrap(), [cpu]);
+    // The affinity of the spawning thread is left alone.
+    assert_eq!(thread::affinity().unwrap(), cpus);
+
+    let res = thread::Builder::new().affinity(&[usize::MAX]).spawn(|| {});
+    assert_eq!(res.unwrap_err().kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[cfg_attr(miri, ignore)] // Miri does not support `setpriority`
+fn test_priority() {
+    let priority = thread::priority().unwrap();
+    // Raising the nice value does not require privileges.
+    let lower = (priority + 1).min(19);
+    let child = thread::Builder::new().priority(lower).spawn(thread::priority).unwrap();
+    assert_eq!(child.join().unwrap().unwrap(), lower);
+    assert_eq!(thread::priority().unwrap(), priority);
+}
+
+#[test]
+#[cfg(windows)]
+fn test_priority_windows() {
+    // `THREAD_PRIORITY_BELOW_NORMAL`
+    let child = thread::Builder::new().priority(-1).spawn(thread::priority).unwrap();
+    assert_eq!(child.join().unwrap().unwrap(), -1);
+}
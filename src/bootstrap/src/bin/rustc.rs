@@ -126,6 +126,14 @@ fn main() {
                 cmd.arg("-Ztime-passes");
             }
         }
+
+        // Cargo names build scripts `build_script_build`, so the build scripts of the listed
+        // crates are still compiled with the default backend.
+        if let Ok(crates) = env::var("RUSTC_CODEGEN_BACKEND_FALLBACK_CRATES") {
+            if crates.split(',').any(|c| c == crate_name) {
+                cmd.arg("-Zcodegen-backend=llvm");
+            }
+        }
     }
 
     // Print backtrace in case of ICE
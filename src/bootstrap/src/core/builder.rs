@@ -1753,6 +1753,20 @@ fn cargo(
             Mode::Codegen => metadata.push_str("codegen"),
             _ => {}
         }
+        // Compile the crates that the default codegen backend does not support yet with LLVM,
+        // see `rust.codegen-backend-fallback-crates`. The stage 0 compiler always uses LLVM.
+        let fallback_crates = &self.config.rust_codegen_backend_fallback_crates;
+        if stage != 0
+            && !fallback_crates.is_empty()
+            && self.config.default_codegen_backend(target).unwrap_or_default() != "llvm"
+            && self.config.codegen_backends(target).iter().any(|backend| backend == "llvm")
+        {
+            let fallback_crates = fallback_crates.join(",");
+            // Cargo does not track the environment of the rustc wrapper, so make the list part of
+            // the metadata to rebuild the crates when it changes.
+            metadata.push_str(&format!("-fallback-{fallback_crates}"));
+            cargo.env("RUSTC_CODEGEN_BACKEND_FALLBACK_CRATES", fallback_crates);
+        }
         cargo.env("__CARGO_DEFAULT_LIB_METADATA", &metadata);
 
         if cmd_kind == Kind::Clippy {
@@ -278,6 +278,7 @@ pub struct Config {
     pub rust_optimize_tests: bool,
     pub rust_dist_src: bool,
     pub rust_codegen_backends: Vec<String>,
+    pub rust_codegen_backend_fallback_crates: Vec<String>,
     pub rust_verify_llvm_ir: bool,
     pub rust_thin_lto_import_instr_limit: Option<u32>,
     pub rust_remap_debuginfo: bool,
@@ -1116,6 +1117,7 @@ struct Rust {
         dist_src: Option<bool> = "dist-src",
         save_toolstates: Option<String> = "save-toolstates",
         codegen_backends: Option<Vec<String>> = "codegen-backends",
+        codegen_backend_fallback_crates: Option<Vec<String>> = "codegen-backend-fallback-crates",
         llvm_bitcode_linker: Option<bool> = "llvm-bitcode-linker",
         lld: Option<bool> = "lld",
         lld_mode: Option<LldMode> = "use-lld",
@@ -1641,6 +1643,7 @@ fn get_table(option: &str) -> Result<TomlConfig, toml::de::Error> {
                 dist_src,
                 save_toolstates,
                 codegen_backends,
+                codegen_backend_fallback_crates,
                 lld: lld_enabled_toml,
                 llvm_tools,
                 llvm_bitcode_linker,
@@ -1759,6 +1762,13 @@ fn get_table(option: &str) -> Result<TomlConfig, toml::de::Error> {
                 }).collect();
             }
 
+            if let Some(crates) = codegen_backend_fallback_crates {
+                if !config.rust_codegen_backends.iter().any(|backend| backend == "llvm") {
+                    panic!("'rust.codegen-backend-fallback-crates' requires 'llvm' to be one of 'rust.codegen-backends'.");
+                }
+                config.rust_codegen_backend_fallback_crates = crates;
+            }
+
             config.rust_codegen_units = codegen_units.map(threads_from_config);
             config.rust_codegen_units_std = codegen_units_std.map(threads_from_config);
             config.rust_profile_use = flags.rust_profile_use.or(profile_use);
@@ -2927,6 +2937,7 @@ macro_rules! warn {
         dist_src: _,
         save_toolstates: _,
         codegen_backends: _,
+        codegen_backend_fallback_crates: _,
         lld: _,
         deny_warnings: _,
         backtrace_on_ice: _,
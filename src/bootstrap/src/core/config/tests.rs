@@ -270,6 +270,25 @@ fn rust_lld() {
     assert!(matches!(parse("rust.use-lld = false").lld_mode, LldMode::Unused));
 }
 
+#[test]
+fn codegen_backend_fallback_crates() {
+    let config = parse(
+        "rust.codegen-backends = [\"cranelift\", \"llvm\"]\n\
+         rust.codegen-backend-fallback-crates = [\"std\", \"test\"]",
+    );
+    assert_eq!(config.rust_codegen_backend_fallback_crates, ["std", "test"]);
+    assert!(parse("").rust_codegen_backend_fallback_crates.is_empty());
+}
+
+#[test]
+#[should_panic]
+fn codegen_backend_fallback_crates_without_llvm() {
+    parse(
+        "rust.codegen-backends = [\"cranelift\"]\n\
+         rust.codegen-backend-fallback-crates = [\"std\"]",
+    );
+}
+
 #[test]
 #[should_panic]
 fn parse_config_with_unknown_field() {
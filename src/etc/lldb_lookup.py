@@ -1,3 +1,6 @@
+import sys
+import types
+
 import lldb
 
 from lldb_providers import *
@@ -122,3 +125,66 @@ def synthetic_lookup(valobj, dict):
         return StdRefSyntheticProvider(valobj, dict, is_cell=True)
 
     return DefaultSyntheticProvider(valobj, dict)
+
+
+# The section in which rustc embeds the formatters from
+# `#[debugger_visualizer(lldb_script_file = "...")]`.
+EMBEDDED_SCRIPTS_SECTION = ".debug_lldb_scripts"
+
+# The names of the embedded scripts that were loaded, so that loading them twice is a no-op.
+loaded_embedded_scripts = set()
+
+
+def parse_embedded_scripts(contents):
+    # type: (bytes) -> list
+    """Splits the contents of the embedded scripts section into (name, source) pairs.
+    Each script is stored as the byte 4, its name, a newline, its source and the byte 0."""
+    scripts = []
+    for entry in bytearray(contents).split(b"\0"):
+        if not entry.startswith(b"\x04"):
+            continue
+        name, _, source = entry[1:].partition(b"\n")
+        scripts.append((name.decode("utf-8"), source.decode("utf-8")))
+    return scripts
+
+
+def load_embedded_scripts(debugger, command, result, internal_dict):
+    # type: (SBDebugger, str, SBCommandReturnObject, dict) -> None
+    """Loads the formatters embedded in the modules of the selected target.
+
+    Each script is loaded as a Python module, like `command script import` would do, so it
+    registers its formatters in `__lldb_init_module(debugger, internal_dict)`, referring to
+    its own functions through `__name__`."""
+    target = debugger.GetSelectedTarget()
+    if not target:
+        return
+    for module in target.module_iter():
+        section = module.FindSection(EMBEDDED_SCRIPTS_SECTION)
+        if not section:
+            continue
+        data = section.GetSectionData()
+        error = lldb.SBError()
+        contents = data.ReadRawData(error, 0, data.GetByteSize())
+        if error.Fail():
+            result.AppendWarning("could not read %s of %s: %s"
+                                 % (EMBEDDED_SCRIPTS_SECTION, module.GetFileSpec(), error))
+            continue
+        for name, source in parse_embedded_scripts(contents):
+            if name in loaded_embedded_scripts:
+                continue
+            loaded_embedded_scripts.add(name)
+            script = types.ModuleType(name)
+            try:
+                exec(compile(source, name, "exec"), script.__dict__)
+                sys.modules[name] = script
+                internal_dict[name] = script
+                if hasattr(script, "__lldb_init_module"):
+                    script.__lldb_init_module(debugger, internal_dict)
+            except Exception as e:
+                result.AppendWarning("could not load embedded script %s: %s" % (name, e))
+
+
+def __lldb_init_module(debugger, internal_dict):
+    debugger.HandleCommand(
+        "command script add -f lldb_lookup.load_embedded_scripts rust-load-embedded-scripts"
+    )
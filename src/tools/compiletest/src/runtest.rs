@@ -1155,6 +1155,8 @@ fn run_debuginfo_lldb_test_no_opt(&self) {
         File::open(rust_pp_module_abs_path.join("lldb_commands"))
             .and_then(|mut file| file.read_to_string(&mut script_str))
             .expect("Failed to read lldb_commands");
+        // Load the formatters embedded in the test executable, as `rust-lldb` does
+        script_str.push_str("rust-load-embedded-scripts\n");
 
         // Set breakpoints on every line that contains the string "#break"
         let source_file_name = self.testpaths.file.file_name().unwrap().to_string_lossy();
@@ -174,6 +174,8 @@ pub enum BlockReason {
     InitOnce(InitOnceId),
     /// Blocked on epoll.
     Epoll,
+    /// Waiting to retry acquiring a file lock with `flock`.
+    Flock,
 }
 
 /// The state of a thread.
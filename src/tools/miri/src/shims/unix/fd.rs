@@ -7,6 +7,7 @@
 use std::ops::Deref;
 use std::rc::Rc;
 use std::rc::Weak;
+use std::time::Duration;
 
 use rustc_target::abi::Size;
 
@@ -14,6 +15,9 @@
 use crate::shims::unix::*;
 use crate::*;
 
+/// How long a thread that is blocked on `flock` waits before trying to acquire the lock again.
+const FLOCK_RETRY_INTERVAL: Duration = Duration::from_millis(10);
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub(crate) enum FlockOp {
     SharedLock { nonblocking: bool },
@@ -89,6 +93,8 @@ fn close<'tcx>(
         throw_unsup_format!("cannot close {}", self.name());
     }
 
+    /// Applies or removes an advisory lock. This never blocks: if the lock is held by another file
+    /// description, this returns an error of kind `WouldBlock`, even if `op` is blocking.
     fn flock<'tcx>(
         &self,
         _communicate_allowed: bool,
@@ -409,10 +415,11 @@ fn dup2(&mut self, old_fd: i32, new_fd: i32) -> InterpResult<'tcx, Scalar> {
         Ok(Scalar::from_i32(new_fd))
     }
 
-    fn flock(&mut self, fd: i32, op: i32) -> InterpResult<'tcx, Scalar> {
+    fn flock(&mut self, fd: i32, op: i32, dest: &MPlaceTy<'tcx>) -> InterpResult<'tcx> {
         let this = self.eval_context_mut();
         let Some(file_descriptor) = this.machine.fds.get(fd) else {
-            return Ok(Scalar::from_i32(this.fd_not_found()?));
+            let result = this.fd_not_found()?;
+            return this.write_scalar(Scalar::from_i32(result), dest);
         };
 
         // We need to check that there aren't unsupported options in `op`.
@@ -438,9 +445,36 @@ fn flock(&mut self, fd: i32, op: i32) -> InterpResult<'tcx, Scalar> {
 
         let result = file_descriptor.flock(this.machine.communicate(), parsed_op)?;
         drop(file_descriptor);
+        if let Err(err) = &result
+            && err.kind() == ErrorKind::WouldBlock
+            && matches!(
+                parsed_op,
+                SharedLock { nonblocking: false } | ExclusiveLock { nonblocking: false }
+            )
+        {
+            // The lock is held by another file description, which may belong to another thread
+            // of the program or to another process, so we cannot know when it will be released:
+            // try again later.
+            let dest = dest.clone();
+            this.block_thread(
+                BlockReason::Flock,
+                Some((TimeoutClock::Monotonic, TimeoutAnchor::Relative, FLOCK_RETRY_INTERVAL)),
+                callback!(
+                    @capture<'tcx> {
+                        fd: i32,
+                        op: i32,
+                        dest: MPlaceTy<'tcx>,
+                    }
+                    @unblock = |_this| { panic!("flock retry unblocked before its timeout") }
+                    @timeout = |this| { this.flock(fd, op, &dest) }
+                ),
+            );
+            return Ok(());
+        }
         // return `0` if flock is successful
         let result = result.map(|()| 0i32);
-        Ok(Scalar::from_i32(this.try_unwrap_io_result(result)?))
+        let result = this.try_unwrap_io_result(result)?;
+        this.write_scalar(Scalar::from_i32(result), dest)
     }
 
     fn fcntl(&mut self, args: &[OpTy<'tcx>]) -> InterpResult<'tcx, Scalar> {
@@ -174,8 +174,7 @@ fn emulate_foreign_item_inner(
                 let [fd, op] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
                 let fd = this.read_scalar(fd)?.to_i32()?;
                 let op = this.read_scalar(op)?.to_i32()?;
-                let result = this.flock(fd, op)?;
-                this.write_scalar(result, dest)?;
+                this.flock(fd, op, dest)?;
             }
 
             // File and file system access
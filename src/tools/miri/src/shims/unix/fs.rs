@@ -145,23 +145,17 @@ fn flock<'tcx>(
 
             use FlockOp::*;
             // We always use non-blocking call to prevent interpreter from being blocked
-            let (host_op, lock_nb) = match op {
-                SharedLock { nonblocking } => (libc::LOCK_SH | libc::LOCK_NB, nonblocking),
-                ExclusiveLock { nonblocking } => (libc::LOCK_EX | libc::LOCK_NB, nonblocking),
-                Unlock => (libc::LOCK_UN, false),
+            let host_op = match op {
+                SharedLock { .. } => libc::LOCK_SH | libc::LOCK_NB,
+                ExclusiveLock { .. } => libc::LOCK_EX | libc::LOCK_NB,
+                Unlock => libc::LOCK_UN,
             };
 
             let fd = self.file.as_raw_fd();
             let ret = unsafe { libc::flock(fd, host_op) };
             let res = match ret {
                 0 => Ok(()),
-                -1 => {
-                    let err = io::Error::last_os_error();
-                    if !lock_nb && err.kind() == io::ErrorKind::WouldBlock {
-                        throw_unsup_format!("blocking `flock` is not currently supported");
-                    }
-                    Err(err)
-                }
+                -1 => Err(io::Error::last_os_error()),
                 ret => panic!("Unexpected return value from flock: {ret}"),
             };
             Ok(res)
@@ -179,20 +173,16 @@ fn flock<'tcx>(
             let fh = self.file.as_raw_handle() as HANDLE;
 
             use FlockOp::*;
-            let (ret, lock_nb) = match op {
-                SharedLock { nonblocking } | ExclusiveLock { nonblocking } => {
+            let ret = match op {
+                SharedLock { .. } | ExclusiveLock { .. } => {
                     // We always use non-blocking call to prevent interpreter from being blocked
                     let mut flags = LOCKFILE_FAIL_IMMEDIATELY;
                     if matches!(op, ExclusiveLock { .. }) {
                         flags |= LOCKFILE_EXCLUSIVE_LOCK;
                     }
-                    let ret = unsafe { LockFileEx(fh, flags, 0, !0, !0, &mut std::mem::zeroed()) };
-                    (ret, nonblocking)
-                }
-                Unlock => {
-                    let ret = unsafe { UnlockFile(fh, 0, 0, !0, !0) };
-                    (ret, false)
+                    unsafe { LockFileEx(fh, flags, 0, !0, !0, &mut std::mem::zeroed()) }
                 }
+                Unlock => unsafe { UnlockFile(fh, 0, 0, !0, !0) },
             };
 
             let res = match ret {
@@ -201,14 +191,10 @@ fn flock<'tcx>(
                     let mut err = io::Error::last_os_error();
                     let code: u32 = err.raw_os_error().unwrap().try_into().unwrap();
                     if matches!(code, ERROR_IO_PENDING | ERROR_LOCK_VIOLATION) {
-                        if lock_nb {
-                            // The io error mapping does not know about these error codes,
-                            // so we translate it to `WouldBlock` manually.
-                            let desc = format!("LockFileEx wouldblock error: {err}");
-                            err = io::Error::new(io::ErrorKind::WouldBlock, desc);
-                        } else {
-                            throw_unsup_format!("blocking `flock` is not currently supported");
-                        }
+                        // The io error mapping does not know about these error codes,
+                        // so we translate it to `WouldBlock` manually.
+                        let desc = format!("LockFileEx wouldblock error: {err}");
+                        err = io::Error::new(io::ErrorKind::WouldBlock, desc);
                     }
                     Err(err)
                 }
@@ -1,6 +1,7 @@
 //@ignore-target-windows: File handling is not implemented yet
 //@compile-flags: -Zmiri-disable-isolation
 
+#![feature(file_lock)]
 #![feature(io_error_more)]
 #![feature(io_error_uncategorized)]
 
@@ -12,6 +13,8 @@
 };
 use std::io::{Error, ErrorKind, IsTerminal, Read, Result, Seek, SeekFrom, Write};
 use std::path::Path;
+use std::thread;
+use std::time::Duration;
 
 #[path = "../../utils/mod.rs"]
 mod utils;
@@ -25,6 +28,8 @@ fn main() {
     test_metadata();
     test_file_set_len();
     test_file_sync();
+    #[cfg(not(any(target_os = "illumos", target_os = "solaris")))]
+    test_file_lock();
     test_errors();
     test_rename();
     test_directory();
@@ -196,6 +201,40 @@ fn test_file_sync() {
     remove_file(&path).unwrap();
 }
 
+#[cfg(not(any(target_os = "illumos", target_os = "solaris")))]
+fn test_file_lock() {
+    let path = utils::prepare_with_content("miri_test_fs_lock.txt", b"");
+    let file1 = File::open(&path).unwrap();
+    let file2 = File::open(&path).unwrap();
+
+    // Test that shared locks coexist, and exclude exclusive locks.
+    file1.lock_shared().unwrap();
+    file2.try_lock_shared().unwrap();
+    assert_eq!(file1.try_lock().unwrap_err().kind(), ErrorKind::WouldBlock);
+    file1.unlock().unwrap();
+    file2.unlock().unwrap();
+
+    // Test that an exclusive lock excludes other locks.
+    file1.lock().unwrap();
+    assert_eq!(file2.try_lock_shared().unwrap_err().kind(), ErrorKind::WouldBlock);
+
+    // Test that a blocking lock waits until the file is unlocked.
+    let waiter = thread::spawn(move || {
+        file2.lock().unwrap();
+        file2
+    });
+    thread::sleep(Duration::from_millis(100));
+    file1.unlock().unwrap();
+    let file2 = waiter.join().unwrap();
+    assert_eq!(file1.try_lock().unwrap_err().kind(), ErrorKind::WouldBlock);
+
+    // Test that closing a file releases its lock.
+    drop(file2);
+    file1.try_lock().unwrap();
+
+    remove_file(&path).unwrap();
+}
+
 fn test_errors() {
     let bytes = b"Hello, World!\n";
     let path = utils::prepare("miri_test_fs_errors.txt");
@@ -0,0 +1,27 @@
+//@only-target-linux: thread affinity is only supported on Linux and Android
+//@compile-flags: -Zmiri-num-cpus=4
+#![feature(thread_affinity)]
+
+use std::thread;
+
+fn main() {
+    // All CPUs are available by default.
+    assert_eq!(thread::affinity().unwrap(), [0, 1, 2, 3]);
+
+    // The spawned thread runs on the CPUs it was given...
+    let child = thread::Builder::new().affinity(&[1, 3]).spawn(thread::affinity).unwrap();
+    assert_eq!(child.join().unwrap().unwrap(), [1, 3]);
+
+    // ... and its own threads inherit them.
+    let child = thread::Builder::new()
+        .affinity(&[2])
+        .spawn(|| thread::spawn(thread::affinity).join().unwrap())
+        .unwrap();
+    assert_eq!(child.join().unwrap().unwrap(), [2]);
+
+    // CPUs that do not exist cannot be used.
+    let err = thread::Builder::new().affinity(&[4]).spawn(|| {}).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+    assert_eq!(thread::affinity().unwrap(), [0, 1, 2, 3]);
+}
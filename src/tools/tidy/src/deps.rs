@@ -403,6 +403,7 @@
     "tinystr",
     "tinyvec",
     "tinyvec_macros",
+    "toml",
     "tracing",
     "tracing-attributes",
     "tracing-core",
@@ -0,0 +1,52 @@
+// Checks that `-Zmerge-identical-instances` emits instances that only differ by the signedness of
+// their integers as aliases, and keeps the instances whose code or types depend on it.
+
+//@ compile-flags: -C no-prepopulate-passes -Ccodegen-units=1 -Zshare-generics=no
+//@ compile-flags: -Zmerge-identical-instances
+
+#![crate_type = "lib"]
+
+// CHECK: @{{.*}}5first{{.*}} = internal {{.*}}alias {{.*}}@{{.*}}5first
+// CHECK-NOT: alias
+
+#[inline(never)]
+fn first<T: Copy>(xs: &[T]) -> Option<T> {
+    xs.first().copied()
+}
+
+#[inline(never)]
+fn divide<T: std::ops::Div<Output = T>>(a: T, b: T) -> T {
+    a / b
+}
+
+pub fn use_first(a: &[u32], b: &[i32]) -> (Option<u32>, Option<i32>) {
+    (first(a), first(b))
+}
+
+pub fn use_divide(a: u32, b: i32) -> (u32, i32) {
+    (divide(a, 2), divide(b, 2))
+}
+
+pub trait Assoc {
+    type Ty;
+}
+
+impl Assoc for u32 {
+    type Ty = u64;
+}
+
+impl Assoc for i32 {
+    type Ty = u8;
+}
+
+pub struct Wrapper<T: Assoc>(T::Ty);
+
+// `Wrapper<u32>` and `Wrapper<i32>` have different layouts.
+#[inline(never)]
+fn size<T: Assoc>() -> usize {
+    std::mem::size_of::<Wrapper<T>>()
+}
+
+pub fn use_size() -> (usize, usize) {
+    (size::<u32>(), size::<i32>())
+}
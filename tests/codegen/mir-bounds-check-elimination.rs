@@ -0,0 +1,45 @@
+// Checks that MIR bounds-check elimination removes the checks it can prove redundant, including
+// with facts about the return value of a callee that is not inlined.
+//@ compile-flags: -Copt-level=0 -Zmir-opt-level=2 -Zmir-enable-passes=+BoundsCheckElimination
+
+#![crate_type = "lib"]
+
+#[inline(never)]
+fn bucket(hash: usize) -> usize {
+    hash % 16
+}
+
+// CHECK-LABEL: @masked
+#[no_mangle]
+pub fn masked(table: &[u32; 16], hash: usize) -> u32 {
+    // CHECK-NOT: panic_bounds_check
+    // CHECK: ret i32
+    table[hash & 15]
+}
+
+// CHECK-LABEL: @callee_range
+#[no_mangle]
+pub fn callee_range(table: &[u32; 16], hash: usize) -> u32 {
+    // CHECK-NOT: panic_bounds_check
+    // CHECK: ret i32
+    table[bucket(hash)]
+}
+
+// CHECK-LABEL: @checked_index
+#[no_mangle]
+pub fn checked_index(v: &[u32], i: usize) -> u32 {
+    // CHECK-NOT: panic_bounds_check
+    // CHECK: ret i32
+    if i < v.len() {
+        v[i]
+    } else {
+        0
+    }
+}
+
+// CHECK-LABEL: @unknown_index
+#[no_mangle]
+pub fn unknown_index(v: &[u32], i: usize) -> u32 {
+    // CHECK: panic_bounds_check
+    v[i]
+}
@@ -0,0 +1,20 @@
+def point_summary(valobj, internal_dict):
+    x = valobj.GetChildMemberWithName("x").GetValueAsSigned()
+    y = valobj.GetChildMemberWithName("y").GetValueAsSigned()
+    return "({}, {})".format(x, y)
+
+
+def line_summary(valobj, internal_dict):
+    a = point_summary(valobj.GetChildMemberWithName("a"), internal_dict)
+    b = point_summary(valobj.GetChildMemberWithName("b"), internal_dict)
+    return "{} -> {}".format(a, b)
+
+
+def __lldb_init_module(debugger, internal_dict):
+    # The script is loaded as a module whose name is chosen by the compiler.
+    debugger.HandleCommand(
+        "type summary add -F {}.point_summary embedded_lldb_visualizer::Point".format(__name__)
+    )
+    debugger.HandleCommand(
+        "type summary add -F {}.line_summary embedded_lldb_visualizer::Line".format(__name__)
+    )
@@ -0,0 +1,39 @@
+//@ compile-flags:-g
+//@ ignore-gdb
+//@ ignore-cdb
+//@ ignore-apple: the formatters are only embedded for targets that embed `.debug_gdb_scripts`
+//@ ignore-windows: the formatters are only embedded for targets that embed `.debug_gdb_scripts`
+
+// === LLDB TESTS ==================================================================================
+
+// lldb-command:run
+
+// lldb-command:v point
+// lldb-check:[...] point = (5, 8)[...]
+// lldb-command:v line
+// lldb-check:[...] line = (0, 0) -> (5, 8)[...]
+
+#![feature(lldb_debugger_visualizer)]
+#![debugger_visualizer(lldb_script_file = "embedded-lldb-visualizer.py")]
+#![allow(unused_variables)]
+
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+pub struct Line {
+    a: Point,
+    b: Point,
+}
+
+fn main() {
+    let point = Point { x: 5, y: 8 };
+    let line = Line { a: Point { x: 0, y: 0 }, b: Point { x: 5, y: 8 } };
+
+    zzz(); // #break
+}
+
+fn zzz() {
+    ()
+}
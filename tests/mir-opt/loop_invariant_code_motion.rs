@@ -0,0 +1,240 @@
+//@ test-mir-pass: LoopInvariantCodeMotion
+
+#![feature(custom_mir, core_intrinsics)]
+#![crate_type = "lib"]
+
+use std::intrinsics::mir::*;
+
+// EMIT_MIR loop_invariant_code_motion.invariant.LoopInvariantCodeMotion.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn invariant(a: u32, b: u32) -> u32 {
+    // CHECK-LABEL: fn invariant(
+    // CHECK: bb0: {
+    // CHECK:     goto -> [[preheader:bb.*]];
+    // CHECK: bb2: {
+    // CHECK-NOT: Mul(
+    // CHECK:     {{_.*}} = Add({{.*}}, copy [[sum:_.*]]);
+    // CHECK: [[preheader]]: {
+    // CHECK-NEXT: [[prod:_.*]] = Mul(copy _1, copy _2);
+    // CHECK-NEXT: [[sum]] = Add(copy [[prod]], const 1_u32);
+    // CHECK-NEXT: goto -> bb1;
+    mir! {
+        let acc: u32;
+        let i: u32;
+        let prod: u32;
+        let sum: u32;
+        {
+            acc = 0;
+            i = 0;
+            Goto(header)
+        }
+        header = {
+            match i {
+                10 => exit,
+                _ => body,
+            }
+        }
+        body = {
+            // Both statements are hoisted, the second one once the first one has been.
+            prod = Mul(a, b);
+            sum = Add(prod, 1);
+            acc = Add(acc, sum);
+            i = Add(i, 1);
+            Goto(header)
+        }
+        exit = {
+            RET = acc;
+            Return()
+        }
+    }
+}
+
+// EMIT_MIR loop_invariant_code_motion.variant.LoopInvariantCodeMotion.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn variant(a: u32, b: u32) -> u32 {
+    // CHECK-LABEL: fn variant(
+    // CHECK-NOT: nop;
+    mir! {
+        let acc: u32;
+        let i: u32;
+        let quot: u32;
+        let prod: u32;
+        {
+            acc = 0;
+            i = 0;
+            Goto(header)
+        }
+        header = {
+            match i {
+                10 => exit,
+                _ => body,
+            }
+        }
+        body = {
+            // Division by zero is UB, so it cannot be executed if the loop does not run.
+            quot = Div(a, b);
+            // `i` is modified by the loop.
+            prod = Mul(i, quot);
+            acc = Add(acc, prod);
+            i = Add(i, 1);
+            Goto(header)
+        }
+        exit = {
+            RET = acc;
+            Return()
+        }
+    }
+}
+
+// EMIT_MIR loop_invariant_code_motion.nested.LoopInvariantCodeMotion.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn nested(a: u32, b: u32) -> u32 {
+    // CHECK-LABEL: fn nested(
+    // CHECK: bb0: {
+    // CHECK:     goto -> [[preheader:bb.*]];
+    // CHECK: bb4: {
+    // CHECK-NOT: Mul(
+    // CHECK:     goto -> bb3;
+    // CHECK: [[preheader]]: {
+    // CHECK-NEXT: {{_.*}} = Mul(copy _1, copy _2);
+    // CHECK-NEXT: goto -> bb1;
+    mir! {
+        let acc: u32;
+        let i: u32;
+        let j: u32;
+        let prod: u32;
+        {
+            acc = 0;
+            i = 0;
+            Goto(outer)
+        }
+        outer = {
+            match i {
+                10 => exit,
+                _ => outer_body,
+            }
+        }
+        outer_body = {
+            j = 0;
+            Goto(inner)
+        }
+        inner = {
+            match j {
+                10 => inner_exit,
+                _ => inner_body,
+            }
+        }
+        inner_body = {
+            // Invariant in both loops, so it is hoisted out of the outer one.
+            prod = Mul(a, b);
+            acc = Add(acc, prod);
+            j = Add(j, 1);
+            Goto(inner)
+        }
+        inner_exit = {
+            i = Add(i, 1);
+            Goto(outer)
+        }
+        exit = {
+            RET = acc;
+            Return()
+        }
+    }
+}
+
+// EMIT_MIR loop_invariant_code_motion.reads.LoopInvariantCodeMotion.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn reads(s: &[u32], o: &Option<u32>, m: &mut u32) -> usize {
+    // CHECK-LABEL: fn reads(
+    // CHECK: bb2: {
+    // CHECK-NOT: Len(
+    // CHECK-NOT: discriminant(
+    // CHECK:     {{_.*}} = copy (*_3);
+    // CHECK: bb4: {
+    // CHECK-NEXT: {{_.*}} = Len((*_1));
+    // CHECK-NEXT: {{_.*}} = discriminant((*_2));
+    // CHECK-NEXT: goto -> bb1;
+    mir! {
+        let acc: usize;
+        let i: usize;
+        let len: usize;
+        let discr: isize;
+        let val: u32;
+        {
+            acc = 0;
+            i = 0;
+            Goto(header)
+        }
+        header = {
+            match i {
+                10 => exit,
+                _ => body,
+            }
+        }
+        body = {
+            // The pointees of shared references to `Freeze` arguments cannot change.
+            len = Len(*s);
+            discr = Discriminant(*o);
+            // Only shared references are known to point to immutable data.
+            val = *m;
+            acc = Add(acc, len);
+            i = Add(i, 1);
+            Goto(header)
+        }
+        exit = {
+            RET = acc;
+            Return()
+        }
+    }
+}
+
+// EMIT_MIR loop_invariant_code_motion.storage.LoopInvariantCodeMotion.diff
+#[custom_mir(dialect = "runtime", phase = "post-cleanup")]
+pub fn storage(a: u32, b: u32) -> u32 {
+    // CHECK-LABEL: fn storage(
+    // CHECK: bb2: {
+    // CHECK-NOT: StorageLive(
+    // CHECK-NOT: Mul(
+    // CHECK:     {{_.*}} = Add(copy [[x:_.*]], copy _2);
+    // CHECK:     StorageDead([[x]]);
+    // CHECK: bb4: {
+    // CHECK-NEXT: {{_.*}} = Mul(copy _1, copy _2);
+    // CHECK-NEXT: goto -> bb1;
+    mir! {
+        let acc: u32;
+        let i: u32;
+        let prod: u32;
+        let x: u32;
+        let y: u32;
+        {
+            acc = 0;
+            i = 0;
+            StorageLive(x);
+            x = Add(a, 1);
+            Goto(header)
+        }
+        header = {
+            match i {
+                10 => exit,
+                _ => body,
+            }
+        }
+        body = {
+            // Hoisted, so its storage markers are removed.
+            StorageLive(prod);
+            prod = Mul(a, b);
+            acc = Add(acc, prod);
+            StorageDead(prod);
+            // `x` is storage-dead after the first iteration, so `y` is not invariant.
+            y = Add(x, b);
+            acc = Add(acc, y);
+            StorageDead(x);
+            i = Add(i, 1);
+            Goto(header)
+        }
+        exit = {
+            RET = acc;
+            Return()
+        }
+    }
+}
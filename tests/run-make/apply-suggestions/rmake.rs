@@ -0,0 +1,32 @@
+// `-Zapply-suggestions` applies machine-applicable suggestions without going through rustfix.
+// This test checks both the in-place and the diff mode, and that the filter restricts the
+// applied suggestions to the given lints.
+
+use run_make_support::{rfs, rustc};
+
+const SOURCE: &str = "pub fn foo() -> i32 {\n    let mut x = 1;\n    let y = (2);\n    x + y\n}\n";
+
+fn main() {
+    rfs::write("diff.rs", SOURCE);
+    rustc()
+        .crate_type("lib")
+        .input("diff.rs")
+        .arg("-Zapply-suggestions=diff")
+        .run()
+        .assert_stdout_contains("-    let mut x = 1;\n+    let x = 1;\n")
+        .assert_stdout_contains("-    let y = (2);\n+    let y = 2;\n");
+    // The diff mode leaves the file alone.
+    assert_eq!(rfs::read_to_string("diff.rs"), SOURCE);
+
+    rfs::write("in_place.rs", SOURCE);
+    rustc()
+        .crate_type("lib")
+        .input("in_place.rs")
+        .arg("-Zapply-suggestions=in-place")
+        .arg("-Zapply-suggestions-filter=unused_mut")
+        .run();
+    assert_eq!(
+        rfs::read_to_string("in_place.rs"),
+        "pub fn foo() -> i32 {\n    let x = 1;\n    let y = (2);\n    x + y\n}\n"
+    );
+}
@@ -0,0 +1,69 @@
+// `-Zdump-mir-format=json` writes the MIR dumps as structured JSON, so that tools don't have to
+// parse the textual MIR. This test checks the overall shape of the dump of a simple function.
+
+use run_make_support::rfs::read_to_string;
+use run_make_support::{path, rustc, serde_json};
+
+fn main() {
+    rustc()
+        .input("foo.rs")
+        .arg("-Zdump-mir=add & PreCodegen")
+        .arg("-Zdump-mir-format=json")
+        .arg("-Zdump-mir-exclude-pass-number")
+        .arg("-Cdebug-assertions=yes")
+        .run();
+
+    // Only the JSON dump is written.
+    assert!(!path("mir_dump/foo.add.PreCodegen.after.mir").exists());
+    let dump = read_to_string("mir_dump/foo.add.PreCodegen.after.json");
+    let dump: serde_json::Value = serde_json::from_str(&dump).expect("failed to parse JSON");
+
+    assert_eq!(dump["def_path"], "add");
+    assert_eq!(dump["pass_name"], "PreCodegen");
+    assert_eq!(dump["disambiguator"], "after");
+    assert_eq!(dump["arg_count"], 2);
+    assert_eq!(dump["span"]["file"], "foo.rs");
+    assert_eq!(dump["span"]["start_line"], 3);
+
+    let locals = dump["locals"].as_array().unwrap();
+    assert_eq!(locals[0]["local"], 0);
+    assert_eq!(locals[0]["ty"], "u32");
+    assert_eq!(locals[1]["ty"], "u32");
+    assert_eq!(dump["var_debug_info"][0]["name"], "a");
+    assert_eq!(dump["scopes"][0]["scope"], 0);
+    assert!(dump["scopes"][0]["parent"].is_null());
+
+    // The overflow check is an `Assert` terminator whose successor is listed separately.
+    let blocks = dump["basic_blocks"].as_array().unwrap();
+    let assert = blocks
+        .iter()
+        .map(|block| &block["terminator"])
+        .find(|terminator| terminator["kind"] == "Assert")
+        .expect("no overflow check");
+    assert_eq!(assert["successors"][0]["label"], "success");
+    assert_eq!(assert["unwind"], "continue");
+    assert_eq!(assert["span"]["start_line"], 4);
+    assert!(assert["text"].as_str().unwrap().starts_with("assert("));
+    // The condition is the overflow flag of the checked addition.
+    assert_eq!(assert["expected"], false);
+    assert_eq!(assert["cond"]["kind"], "Move");
+    assert_eq!(assert["cond"]["place"]["projection"][0]["kind"], "Field");
+    assert_eq!(assert["cond"]["place"]["projection"][0]["field"], 1);
+
+    let statement = blocks[0]["statements"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|statement| statement["rvalue"]["op"] == "AddWithOverflow")
+        .expect("no checked addition");
+    assert_eq!(statement["kind"], "Assign");
+    assert_eq!(statement["span"]["start_line"], 4);
+    assert!(statement["place"]["projection"].as_array().unwrap().is_empty());
+    let rvalue = &statement["rvalue"];
+    assert_eq!(rvalue["kind"], "BinaryOp");
+    assert_eq!(rvalue["lhs"]["kind"], "Copy");
+    assert_eq!(rvalue["lhs"]["place"]["local"], 1);
+    assert_eq!(rvalue["rhs"]["place"]["local"], 2);
+
+    assert!(blocks.iter().any(|block| block["terminator"]["kind"] == "Return"));
+}
@@ -0,0 +1,12 @@
+#![crate_type = "cdylib"]
+
+#[no_mangle]
+pub extern "C" fn mylib_init() {}
+
+#[no_mangle]
+pub extern "C" fn mylib_frob() -> u32 {
+    42
+}
+
+#[no_mangle]
+pub extern "C" fn mylib_test_hook() {}
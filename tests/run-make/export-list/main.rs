@@ -0,0 +1,9 @@
+#[no_mangle]
+pub extern "C" fn mylib_frob() -> u32 {
+    42
+}
+
+#[no_mangle]
+pub extern "C" fn mylib_test_hook() {}
+
+fn main() {}
@@ -0,0 +1,38 @@
+// This test checks that `-Z export-list` versions, hides and aliases the exported symbols of a
+// cdylib as its TOML file says, that the names it lists must be exported by rustc, and that it is
+// ignored with a warning for executables that do not export symbols.
+
+//@ only-linux
+//@ ignore-cross-compile
+
+use run_make_support::{dynamic_lib_name, llvm_readobj, rustc};
+
+fn main() {
+    rustc().input("lib.rs").arg("-Zexport-list=exports.toml").run();
+    let output =
+        llvm_readobj().arg("--dyn-syms").input(dynamic_lib_name("lib")).run().stdout_utf8();
+    assert!(output.contains("mylib_init@@MYLIB_1.0"));
+    assert!(output.contains("mylib_frob@@MYLIB_1.1"));
+    assert!(output.contains("mylib_frobnicate@@MYLIB_1.1"));
+    assert!(output.contains("mylib_frob_weak@@MYLIB_1.0"));
+    assert!(!output.contains("mylib_test_hook"));
+
+    rustc()
+        .input("lib.rs")
+        .arg("-Zexport-list=unknown.toml")
+        .run_fail()
+        .assert_stderr_contains(
+            "version `MYLIB_1.0` in the export list `unknown.toml` is not in its `versions`",
+        )
+        .assert_stderr_contains(
+            "symbol `mylib_missing` in the export list `unknown.toml` is not exported",
+        )
+        .assert_stderr_contains(
+            "alias `mylib_alias` in the export list `unknown.toml` refers to `mylib_gone`, \
+            which is not exported",
+        );
+
+    rustc().input("main.rs").arg("-Zexport-list=exports.toml").run().assert_stderr_contains(
+        "`-Z export-list` has no effect on executables without `-Z export-executable-symbols`",
+    );
+}
@@ -0,0 +1,33 @@
+// `-Z incremental-explain` reports why queries were re-executed in an incremental session.
+// This test edits one of two functions and checks that the re-execution of its `typeck` is
+// attributed to that edit, both in the summary and in the JSON report.
+
+//@ ignore-none
+// reason: no-std is not supported
+
+//@ ignore-nvptx64-nvidia-cuda
+// FIXME: can't find crate for `std`
+
+use run_make_support::{rfs, rustc};
+
+fn main() {
+    rfs::create_dir("incr");
+    rfs::write("lib.rs", "pub fn edited() -> u32 { 1 }\npub fn untouched() -> u32 { 2 }\n");
+    rustc().incremental("incr").crate_type("lib").input("lib.rs").run();
+
+    rfs::write("lib.rs", "pub fn edited() -> u32 { 3 }\npub fn untouched() -> u32 { 2 }\n");
+    rustc()
+        .incremental("incr")
+        .crate_type("lib")
+        .input("lib.rs")
+        .arg("-Zincremental-explain=explain")
+        .run()
+        .assert_stderr_contains("due to an edit at lib.rs:1:1")
+        .assert_stderr_contains("`typeck(edited)` re-executed because")
+        .assert_stderr_not_contains("typeck(untouched)");
+
+    let json = rfs::read_to_string("explain/lib.incremental-explain.json");
+    assert!(json.contains(r#""node":"typeck(edited)""#));
+    assert!(json.contains(r#""location":"lib.rs:1:1""#));
+    assert!(!json.contains("typeck(untouched)"));
+}
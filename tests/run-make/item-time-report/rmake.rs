@@ -0,0 +1,43 @@
+// `-Z item-time-report` prints the items that took the most compile time. This test checks that
+// the report is printed when only checking a crate, and that a full build charges the codegen of
+// the instances of a generic function to that function.
+
+//@ ignore-none
+// reason: no-std is not supported
+
+//@ ignore-nvptx64-nvidia-cuda
+// FIXME: can't find crate for `std`
+
+use run_make_support::{rfs, rustc};
+
+fn main() {
+    rfs::write(
+        "lib.rs",
+        "pub fn checked(x: u32) -> u32 { x + 1 }\n\
+         pub fn generic<T: Copy>(x: T) -> (T, T) { (x, x) }\n\
+         pub fn user() -> ((u8, u8), (u64, u64)) { (generic(1u8), generic(2u64)) }\n",
+    );
+
+    let output = rustc()
+        .crate_type("lib")
+        .emit("metadata")
+        .input("lib.rs")
+        .arg("-Zitem-time-report=100000")
+        .run()
+        .stdout_utf8();
+    assert!(output.starts_with("item-time-report: top "));
+    // Local items are printed without the crate name.
+    assert!(output.lines().any(|line| line.ends_with("  checked")));
+    assert!(output.lines().any(|line| line.ends_with("  typeck")));
+
+    let output = rustc()
+        .crate_type("lib")
+        .input("lib.rs")
+        .arg("-Zitem-time-report=100000")
+        .run()
+        .stdout_utf8();
+    let generic = output.lines().find(|line| line.ends_with("  generic")).unwrap();
+    // The columns are the total time, the codegen time, the number of instances and their size.
+    let columns: Vec<_> = generic.split_whitespace().collect();
+    assert_eq!(columns[2], "2");
+}
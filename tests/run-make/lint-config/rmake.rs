@@ -0,0 +1,40 @@
+// `-Z lint-config` reads lint levels from a TOML file. This test checks that the levels apply at
+// command-line precedence, that `[[overrides]]` only apply to the files under their path, and
+// that unknown lint names are reported with spans into the config file.
+
+use run_make_support::{rfs, rustc};
+
+fn main() {
+    rfs::write(
+        "lints.toml",
+        r#"[lints.rust]
+dead_code = "deny"
+not_a_real_lint = "warn"
+
+[[overrides]]
+path = "legacy"
+lints.rust.dead_code = "allow"
+"#,
+    );
+    rfs::create_dir("legacy");
+    rfs::write("legacy/mod.rs", "fn unused_in_legacy() {}\n");
+    rfs::write("main.rs", "mod legacy;\nfn unused_in_root() {}\nfn main() {}\n");
+
+    rustc()
+        .input("main.rs")
+        .arg("-Zlint-config=lints.toml")
+        .run_fail()
+        .assert_stderr_contains("function `unused_in_root` is never used")
+        .assert_stderr_contains("lints.toml:2:1")
+        .assert_stderr_not_contains("unused_in_legacy")
+        .assert_stderr_contains("unknown lint: `not_a_real_lint`")
+        .assert_stderr_contains("lints.toml:3:1");
+
+    // Command-line flags take precedence over the config file.
+    rustc()
+        .input("main.rs")
+        .arg("-Zlint-config=lints.toml")
+        .arg("-Adead_code")
+        .run()
+        .assert_stderr_not_contains("never used");
+}
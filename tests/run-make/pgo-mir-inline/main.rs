@@ -0,0 +1,30 @@
+use std::hint::black_box;
+
+#[inline]
+fn small(x: u32) -> u32 {
+    x.wrapping_mul(3).wrapping_add(1)
+}
+
+#[no_mangle]
+#[inline(never)]
+pub fn hot_caller(x: u32) -> u32 {
+    small(x)
+}
+
+#[no_mangle]
+#[inline(never)]
+pub fn cold_caller(x: u32) -> u32 {
+    small(x)
+}
+
+fn main() {
+    let mut acc = 0;
+    for i in 0..100_000 {
+        acc = hot_caller(black_box(i) ^ acc);
+    }
+    if std::env::args().count() > 5 {
+        // This branch is never taken at runtime.
+        acc = cold_caller(acc);
+    }
+    println!("{acc}");
+}
@@ -0,0 +1,65 @@
+// This test checks that the MIR inliner uses the profile passed with `-C profile-use`: it
+// doesn't inline into functions that never ran, and uses a larger threshold for hot call sites.
+
+//@ needs-profiler-support
+//@ ignore-cross-compile
+
+use run_make_support::{
+    cwd, has_extension, has_prefix, llvm_profdata, rfs, run, rustc, shallow_find_files,
+};
+
+/// Returns the MIR of the function with the given name.
+fn mir_of<'a>(mir: &'a str, name: &str) -> &'a str {
+    let start = mir.find(&format!("fn {name}(")).unwrap();
+    let len = mir[start..].find("\n}\n").unwrap();
+    &mir[start..start + len]
+}
+
+fn emit_mir(args: &[&str]) -> String {
+    rustc()
+        .opt_level("2")
+        .codegen_units(1)
+        .arg("-Zinline-mir")
+        .args(args)
+        .emit("mir")
+        .input("main.rs")
+        .run();
+    rfs::read_to_string("main.mir")
+}
+
+fn main() {
+    // The profile is collected without MIR inlining, so that it has counts for all functions.
+    rustc()
+        .opt_level("2")
+        .codegen_units(1)
+        .arg("-Zinline-mir=no")
+        .profile_generate(cwd())
+        .input("main.rs")
+        .run();
+    run("main");
+    let profraw_files = shallow_find_files(cwd(), |path| {
+        has_prefix(path, "default") && has_extension(path, "profraw")
+    });
+    let profraw_file = profraw_files.get(0).unwrap();
+    llvm_profdata().merge().output("merged.profdata").input(profraw_file).run();
+
+    // Without a profile, `small` is inlined into both callers.
+    let mir = emit_mir(&[]);
+    assert!(mir_of(&mir, "hot_caller").contains("(inlined small)"));
+    assert!(mir_of(&mir, "cold_caller").contains("(inlined small)"));
+
+    // With it, `small` is not inlined into the caller that never ran.
+    let mir = emit_mir(&["-Cprofile-use=merged.profdata"]);
+    assert!(mir_of(&mir, "hot_caller").contains("(inlined small)"));
+    assert!(!mir_of(&mir, "cold_caller").contains("(inlined small)"));
+
+    // The call site in the hot caller is inlined even if it is above the usual thresholds.
+    let mir = emit_mir(&[
+        "-Cprofile-use=merged.profdata",
+        "-Zinline-mir-threshold=0",
+        "-Zinline-mir-hint-threshold=0",
+    ]);
+    assert!(mir_of(&mir, "hot_caller").contains("(inlined small)"));
+    let mir = emit_mir(&["-Zinline-mir-threshold=0", "-Zinline-mir-hint-threshold=0"]);
+    assert!(!mir_of(&mir, "hot_caller").contains("(inlined small)"));
+}
@@ -0,0 +1,19 @@
+// `--print=lints-json` describes every registered lint as structured data, so that tools don't
+// have to parse the `-W help` table. This test checks that a few well-known entries are present.
+
+use run_make_support::rustc;
+
+fn main() {
+    let output = rustc().print("lints-json").arg("-Zunstable-options").run();
+    output
+        .assert_stdout_contains(r#""name": "unused_imports""#)
+        .assert_stdout_contains(r#""description": "imports that are never used""#)
+        .assert_stdout_contains(r#""name": "unused""#)
+        .assert_stdout_contains(r#""reason": "edition-error""#);
+
+    // The print request is unstable.
+    rustc()
+        .print("lints-json")
+        .run_fail()
+        .assert_stderr_contains("the `-Z unstable-options` flag must also be passed");
+}
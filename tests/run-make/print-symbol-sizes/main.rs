@@ -0,0 +1,19 @@
+struct Foo;
+
+impl Foo {
+    #[inline(never)]
+    fn method(&self) -> u32 {
+        std::hint::black_box(7)
+    }
+}
+
+#[inline(never)]
+fn generic<T: std::fmt::Debug>(x: T) {
+    println!("{x:?}");
+}
+
+fn main() {
+    generic(1u8);
+    generic("a");
+    println!("{}", Foo.method());
+}
@@ -0,0 +1,48 @@
+// This test checks that `-Z linker-map` makes the linker write a map file, and that
+// `-Z print-symbol-sizes` groups the symbols it lists by crate, module and generic item.
+
+//@ only-linux
+
+use run_make_support::{path, rfs, rustc, serde_json};
+
+fn main() {
+    rustc().input("main.rs").arg("-Zlinker-map").run();
+    assert!(path("main.map").exists());
+
+    rustc().input("main.rs").arg("-Zlinker-map=custom.map").run();
+    assert!(!rfs::read_to_string("custom.map").is_empty());
+
+    let output = rustc()
+        .input("main.rs")
+        .arg("-Zprint-symbol-sizes")
+        .arg("-Csymbol-mangling-version=v0")
+        .run()
+        .stdout_utf8();
+    assert!(output.starts_with("print-symbol-size total: "));
+    assert!(output.contains("print-symbol-size crate `main`: "));
+    assert!(output.contains("print-symbol-size     module `main`: "));
+    assert!(output.contains("print-symbol-size         `<main::Foo>::method`: "));
+    assert!(output.contains("print-symbol-size         `main::generic`: "));
+    assert!(output.contains(" bytes in 2 instantiations\n"));
+    assert!(output.contains("print-symbol-size             `main::generic::<u8>`: "));
+    assert!(output.contains("print-symbol-size             `main::generic::<&str>`: "));
+    assert!(output.contains("print-symbol-size crate `std`: "));
+
+    let output = rustc()
+        .input("main.rs")
+        .arg("-Zprint-symbol-sizes")
+        .arg("-Zprint-sizes-format=json")
+        .arg("-Csymbol-mangling-version=v0")
+        .run()
+        .stdout_utf8();
+    let symbols: Vec<serde_json::Value> =
+        output.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    let generic: Vec<_> = symbols.iter().filter(|symbol| symbol["item"] == "generic").collect();
+    assert_eq!(generic.len(), 2);
+    for symbol in generic {
+        assert_eq!(symbol["crate"], "main");
+        assert_eq!(symbol["module"], "");
+        assert_eq!(symbol["instantiation"], true);
+        assert!(symbol["size"].as_u64().unwrap() > 0);
+    }
+}
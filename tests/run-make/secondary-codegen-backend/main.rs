@@ -0,0 +1,14 @@
+#![feature(codegen_backend_attribute)]
+
+#[codegen_backend = "cranelift"]
+fn checksum(data: &[u32]) -> u32 {
+    let mut sum = 0u32;
+    for x in data {
+        sum = sum.wrapping_mul(31).wrapping_add(*x);
+    }
+    sum
+}
+
+fn main() {
+    println!("checksum: {}", checksum(&[1, 2, 3]));
+}
@@ -0,0 +1,31 @@
+// This test checks that a function marked `#[codegen_backend]` is generated by the secondary
+// codegen backend and linked with the rest of the crate, and that a secondary backend that does
+// not support unwinding cannot be used with `-C panic=unwind`.
+
+//@ only-x86_64
+//@ only-linux
+//@ ignore-cross-compile
+
+use run_make_support::{has_prefix, path, run, rustc, shallow_find_files, target};
+
+fn main() {
+    // The Cranelift backend is only built if it is one of `rust.codegen-backends`.
+    let sysroot = rustc().print("sysroot").run().stdout_utf8();
+    let backends = path(sysroot.trim()).join("lib/rustlib").join(target()).join("codegen-backends");
+    if !backends.exists()
+        || shallow_find_files(&backends, |path| has_prefix(path, "librustc_codegen_cranelift"))
+            .is_empty()
+    {
+        eprintln!("the cranelift codegen backend is not built, skipping test");
+        return;
+    }
+
+    rustc().input("main.rs").panic("abort").arg("-Zsecondary-codegen-backends=cranelift").run();
+    run("main").assert_stdout_contains("checksum: 1026");
+
+    rustc()
+        .input("main.rs")
+        .arg("-Zsecondary-codegen-backends=cranelift")
+        .run_fail()
+        .assert_stderr_contains("codegen backend `cranelift` does not support unwinding");
+}
@@ -0,0 +1,130 @@
+//@ run-pass
+//! Test that bodies edited through StableMIR are used for codegen.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+#![feature(control_flow_enum)]
+
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use rustc_smir::rustc_internal::transform::BodyTransform;
+use stable_mir::mir::{
+    BasicBlock, BinOp, Body, ConstOperand, LocalDecl, Mutability, Operand, Place, Rvalue,
+    Statement, StatementKind, Terminator, TerminatorKind, UnwindAction, RETURN_LOCAL,
+};
+use stable_mir::ty::{MirConst, UintTy};
+use stable_mir::{CrateDef, CrateItem};
+use std::io::Write;
+use std::ops::ControlFlow;
+use std::process::Command;
+
+const CRATE_NAME: &str = "input";
+
+/// The bodies are transformed once this returns. Bodies computed here would not be transformed.
+fn test_transform() -> ControlFlow<()> {
+    ControlFlow::Continue(())
+}
+
+/// Replaces the body of `answer` by `helper() + 1`, which needs a new local, a new block and a
+/// call to a function that is not called anywhere else.
+fn call_helper(item: CrateItem, body: &Body) -> Option<Body> {
+    if item.name() != "answer" {
+        return None;
+    }
+    // Transforms run in their own context, so look up the items needed here.
+    let helper = stable_mir::all_local_items()
+        .into_iter()
+        .find(|item| item.name() == "helper")
+        .expect("Failed to find `helper`");
+    let span = body.span;
+    let constant = |const_| Operand::Constant(ConstOperand { span, user_ty: None, const_ });
+
+    let mut locals = body.locals().to_vec();
+    let result = locals.len();
+    locals.push(LocalDecl { ty: body.ret_local().ty, span, mutability: Mutability::Mut });
+
+    let call = Terminator {
+        kind: TerminatorKind::Call {
+            func: constant(MirConst::try_new_zero_sized(helper.ty()).unwrap()),
+            args: vec![],
+            destination: Place::from(result),
+            target: Some(1),
+            unwind: UnwindAction::Continue,
+        },
+        span,
+    };
+    let add = Statement {
+        kind: StatementKind::Assign(
+            Place::from(RETURN_LOCAL),
+            Rvalue::BinaryOp(
+                BinOp::Add,
+                Operand::Copy(Place::from(result)),
+                constant(MirConst::try_from_uint(1, UintTy::U32).unwrap()),
+            ),
+        ),
+        span,
+    };
+    let blocks = vec![
+        BasicBlock { statements: vec![], terminator: call },
+        BasicBlock {
+            statements: vec![add],
+            terminator: Terminator { kind: TerminatorKind::Return, span },
+        },
+    ];
+    Some(Body::new(blocks, locals, 0, body.var_debug_info.clone(), None, span))
+}
+
+/// This test will generate a dummy binary, compile it while transforming one of its functions,
+/// and check that running it observes the transformed function.
+fn main() {
+    let path = "body_transform_input.rs";
+    let exe = format!("body_transform_input{}", std::env::consts::EXE_SUFFIX);
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=bin".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        "-o".to_string(),
+        exe.clone(),
+        path.to_string(),
+    ];
+    run_with_transforms!(args, vec![call_helper as BodyTransform], test_transform).unwrap();
+
+    let output = Command::new(std::env::current_dir().unwrap().join(&exe)).output().unwrap();
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "42\n");
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+        #[inline(never)]
+        fn helper() -> u32 {{
+            41
+        }}
+
+        #[inline(never)]
+        fn answer() -> u32 {{
+            1
+        }}
+
+        fn main() {{
+            println!("{{}}", answer());
+        }}
+        "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,140 @@
+//@ run-pass
+//! Test that users are able to resolve trait references and normalize associated types.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+#![feature(control_flow_enum)]
+
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate serde_json;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::ty::{
+    GenericArgKind, GenericArgs, RigidTy, TraitDef, TraitRef, TraitResolution, Ty, TyKind, UintTy,
+};
+use stable_mir::CrateDef;
+use std::assert_matches::assert_matches;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_traits() -> ControlFlow<()> {
+    let max = *stable_mir::local_crate().trait_decls().first().unwrap();
+    assert_eq!(&max.name(), "Max");
+    let sized = stable_mir::all_trait_decls()
+        .into_iter()
+        .find(|trait_def| trait_def.name() == "std::marker::Sized")
+        .unwrap();
+
+    let impls = max.impls();
+    assert_eq!(impls.len(), 2, "Unexpected impls: {impls:?}");
+    let u64_impl = impls.iter().find(|i| i.name() == "<u64 as Max>").unwrap();
+    let wrapper_impl = impls.iter().find(|i| i.name() == "<Wrapper<T> as Max>").unwrap();
+
+    let u64_ty = Ty::from_rigid_kind(RigidTy::Uint(UintTy::U64));
+    let wrapper = |arg: Ty| {
+        let TyKind::RigidTy(RigidTy::Adt(def, _)) =
+            wrapper_impl.trait_impl().value.self_ty().kind()
+        else {
+            unreachable!()
+        };
+        Ty::from_rigid_kind(RigidTy::Adt(def, GenericArgs(vec![GenericArgKind::Type(arg)])))
+    };
+
+    assert_eq!(
+        resolve(max, u64_ty),
+        TraitResolution::Impl { def: *u64_impl, args: GenericArgs(vec![]) }
+    );
+    let resolution = resolve(max, wrapper(u64_ty));
+    assert_eq!(
+        resolution,
+        TraitResolution::Impl {
+            def: *wrapper_impl,
+            args: GenericArgs(vec![GenericArgKind::Type(u64_ty)])
+        }
+    );
+    assert!(serde_json::to_string(&resolution).unwrap().contains("Impl"));
+
+    // The impl for `Wrapper<T>` requires `T: Max`.
+    assert_eq!(resolve(max, wrapper(Ty::bool_ty())), TraitResolution::NotImplemented);
+    assert!(!resolve(max, Ty::bool_ty()).is_implemented());
+    assert_eq!(resolve(sized, u64_ty), TraitResolution::Builtin);
+    assert_eq!(resolve(max, dyn_max()), TraitResolution::Object);
+
+    let trait_ref = TraitRef::new(max, wrapper(u64_ty), &GenericArgs(vec![]));
+    let output = trait_ref.assoc_ty("Output").unwrap();
+    assert_matches!(output.kind(), TyKind::Alias(..));
+    assert_eq!(output.normalize().unwrap(), u64_ty);
+    assert_eq!(trait_ref.assoc_ty("Missing"), None);
+
+    ControlFlow::Continue(())
+}
+
+fn resolve(trait_def: TraitDef, self_ty: Ty) -> TraitResolution {
+    TraitRef::new(trait_def, self_ty, &GenericArgs(vec![])).resolve()
+}
+
+/// Get `dyn Max<Output = u64>` from the signature of `takes_dyn`.
+fn dyn_max() -> Ty {
+    let item =
+        stable_mir::all_local_items().into_iter().find(|item| item.name() == "takes_dyn").unwrap();
+    let sig = item.ty().kind().fn_sig().unwrap().skip_binder();
+    sig.inputs()[0].kind().builtin_deref(true).unwrap().ty
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "trait_resolution.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_traits).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+        pub trait Max {{
+            type Output;
+            fn max(&self) -> Self::Output;
+        }}
+
+        impl Max for u64 {{
+            type Output = u64;
+            fn max(&self) -> u64 {{ u64::MAX }}
+        }}
+
+        pub struct Wrapper<T>(T);
+
+        impl<T: Max> Max for Wrapper<T> {{
+            type Output = T::Output;
+            fn max(&self) -> T::Output {{ self.0.max() }}
+        }}
+
+        pub fn takes_dyn(_: &dyn Max<Output = u64>) {{}}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,11 @@
+// The copies of `#[inline]` functions are generated by the codegen backends of their callers, so
+// they cannot be assigned to a codegen backend.
+
+//@ build-fail
+
+#![feature(codegen_backend_attribute)]
+#![crate_type = "lib"]
+
+#[codegen_backend = "llvm"] //~ ERROR cannot use `#[codegen_backend]` with `#[inline]`
+#[inline]
+pub fn f() {}
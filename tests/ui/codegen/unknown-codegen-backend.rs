@@ -0,0 +1,10 @@
+// Functions can only be assigned to the main codegen backend and to the ones loaded with
+// `-Z secondary-codegen-backends`.
+
+//@ build-fail
+
+#![feature(codegen_backend_attribute)]
+#![crate_type = "lib"]
+
+#[codegen_backend = "nonexistent"] //~ ERROR codegen backend `nonexistent` is not loaded
+pub fn f() {}
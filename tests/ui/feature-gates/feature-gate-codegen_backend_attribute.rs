@@ -0,0 +1,4 @@
+#![crate_type = "rlib"]
+
+#[codegen_backend = "llvm"] //~ ERROR the `#[codegen_backend]` attribute is an experimental feature
+pub fn f() {}
@@ -0,0 +1,4 @@
+#![debugger_visualizer(lldb_script_file = "feature-gate-lldb-debugger-visualizer.rs")]
+//~^ ERROR embedding LLDB formatters is unstable
+
+fn main() {}
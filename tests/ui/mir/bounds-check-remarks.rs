@@ -0,0 +1,16 @@
+// Checks that `-Zmir-bounds-check-remarks` reports the bounds checks that survive.
+//@ build-pass
+//@ compile-flags: -Zmir-opt-level=2 -Zmir-enable-passes=+BoundsCheckElimination
+//@ compile-flags: -Zmir-bounds-check-remarks
+
+#![crate_type = "lib"]
+
+pub fn masked(table: &[u8; 16], i: usize) -> u8 {
+    table[i & 15]
+}
+
+pub fn too_large(table: &[u8; 16], i: usize) -> u8 {
+    table[i & 31]
+    //~^ NOTE bounds check could not be removed
+    //~| NOTE the index is in `0..=31`, but the length may be as low as 16
+}
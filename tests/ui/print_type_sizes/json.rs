@@ -0,0 +1,17 @@
+//@ compile-flags: -Z print-type-sizes -Z print-vtable-sizes -Z print-sizes-format=json
+//@ compile-flags: --crate-type=lib
+//@ build-pass
+
+// This file illustrates the JSON output of `-Z print-type-sizes` and `-Z print-vtable-sizes`,
+// which includes the field offsets and the largest niche.
+
+#![allow(dead_code)]
+
+struct S {
+    a: bool,
+    g: i32,
+}
+
+trait Tr {
+    fn f(&self);
+}